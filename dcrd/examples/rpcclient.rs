@@ -9,7 +9,7 @@ use std::{fs, path::PathBuf};
 async fn main() {
     pretty_env_logger::init();
     // Get dcrd app directory, if none is found use current path.
-    let mut app_dir = match dcrutil::get_app_data_dir("dcrd".into(), false) {
+    let mut app_dir = match dcrutil::get_app_data_dir("dcrd", false) {
         Some(dir) => dir,
 
         None => PathBuf::new().join("."),
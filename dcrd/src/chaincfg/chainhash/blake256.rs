@@ -0,0 +1,121 @@
+//! BLAKE-256 with 14 rounds, the hash function used for Decred block and transaction hashes.
+
+use super::constants::HASH_SIZE;
+
+/// Block size in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// Number of rounds applied to each block.
+const ROUNDS: usize = 14;
+
+/// Initial chain value, shared with SHA-256.
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Round constants, the leading digits of pi.
+const C: [u32; 16] = [
+    0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344, 0xa4093822, 0x299f31d0, 0x082efa98, 0xec4e6c89,
+    0x452821e6, 0x38d01377, 0xbe5466cf, 0x34e90c6c, 0xc0ac29b7, 0xc97c50dd, 0x3f84d5b5, 0xb5470917,
+];
+
+/// Message word permutations, repeated from the first for rounds past ten.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Returns the BLAKE-256 digest of data.
+pub(super) fn sum256(data: &[u8]) -> [u8; HASH_SIZE] {
+    let bits = (data.len() as u64) * 8;
+
+    // Pad with a one bit, zeros up to the last 65 bits of a block, a one bit and the message
+    // length in bits.
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % BLOCK_SIZE != BLOCK_SIZE - 8 {
+        padded.push(0);
+    }
+    *padded.last_mut().expect("padding is never empty") |= 0x01;
+    padded.extend_from_slice(&bits.to_be_bytes());
+
+    let mut h = IV;
+
+    for (i, block) in padded.chunks_exact(BLOCK_SIZE).enumerate() {
+        // The counter holds the message bits hashed up to the end of the block, or zero for a
+        // block holding padding only.
+        let block_start = (i * BLOCK_SIZE * 8) as u64;
+        let counter = if block_start < bits {
+            bits.min(block_start + (BLOCK_SIZE * 8) as u64)
+        } else {
+            0
+        };
+
+        compress(&mut h, block, counter);
+    }
+
+    let mut digest = [0; HASH_SIZE];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(h.iter()) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+
+    digest
+}
+
+/// Compresses a block into the chain value.
+fn compress(h: &mut [u32; 8], block: &[u8], counter: u64) {
+    let mut m = [0u32; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    let (t0, t1) = (counter as u32, (counter >> 32) as u32);
+
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(h);
+    v[8..12].copy_from_slice(&C[..4]);
+    v[12] = t0 ^ C[4];
+    v[13] = t0 ^ C[5];
+    v[14] = t1 ^ C[6];
+    v[15] = t1 ^ C[7];
+
+    for round in 0..ROUNDS {
+        let s = &SIGMA[round % 10];
+
+        g(&mut v, &m, s, 0, [0, 4, 8, 12]);
+        g(&mut v, &m, s, 1, [1, 5, 9, 13]);
+        g(&mut v, &m, s, 2, [2, 6, 10, 14]);
+        g(&mut v, &m, s, 3, [3, 7, 11, 15]);
+        g(&mut v, &m, s, 4, [0, 5, 10, 15]);
+        g(&mut v, &m, s, 5, [1, 6, 11, 12]);
+        g(&mut v, &m, s, 6, [2, 7, 8, 13]);
+        g(&mut v, &m, s, 7, [3, 4, 9, 14]);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Mixes message words into the state words at `index`.
+fn g(v: &mut [u32; 16], m: &[u32; 16], s: &[usize; 16], i: usize, index: [usize; 4]) {
+    let [a, b, c, d] = index;
+    let (x, y) = (s[2 * i], s[2 * i + 1]);
+
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[x] ^ C[y]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(12);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[y] ^ C[x]);
+    v[d] = (v[d] ^ v[a]).rotate_right(8);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(7);
+}
@@ -10,6 +10,20 @@ use {
 #[derive(Deserialize, Serialize)]
 pub struct Hash([u8; HASH_SIZE]);
 
+impl PartialEq for Hash {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_equal(other)
+    }
+}
+
+impl Eq for Hash {}
+
+impl std::hash::Hash for Hash {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl Clone for Hash {
     fn clone(&self) -> Self {
         Self(*self.bytes())
@@ -17,6 +31,12 @@ impl Clone for Hash {
 }
 
 impl Hash {
+    /// Creates a Hash from its bytes, in internal byte order. Unlike `From`, this can be
+    /// used in constants.
+    pub const fn from_bytes(hash: [u8; HASH_SIZE]) -> Self {
+        Self(hash)
+    }
+
     /// Returns the Hash as the hexadecimal string of the byte-reversed
     /// hash.
     pub fn string(&self) -> Result<String, ChainHashError> {
@@ -74,7 +94,7 @@ impl Hash {
     /// Returns a new Hash from a byte slice.  An error is returned if
     /// the number of bytes passed in is not HASH_SIZE.
     pub fn new(hash: Vec<u8>) -> Result<Self, ChainHashError> {
-        let mut h = Self { 0: [0; 32] };
+        let mut h = Self([0; 32]);
 
         match h.set_bytes(hash) {
             Ok(_) => {}
@@ -89,7 +109,7 @@ impl Hash {
     /// string of a byte-reversed hash, but any missing characters result in zero padding at
     /// at the end of the Hash.
     pub fn new_from_str(value: &str) -> Result<Hash, ChainHashError> {
-        let mut h = Self { 0: [0; HASH_SIZE] };
+        let mut h = Self([0; HASH_SIZE]);
         match h.decode(value) {
             Ok(_) => {}
 
@@ -109,7 +129,7 @@ impl Hash {
 
         // Hex decoder expects the hash to be a multiple of two.  When not, pad
         // with a leading zero.
-        let src_bytes = if src.len() % 2 == 0 {
+        let src_bytes = if src.len().is_multiple_of(2) {
             src.as_bytes().to_vec()
         } else {
             let mut v = Vec::with_capacity(1 + src.len()); //src.as_bytes().to_vec();
@@ -141,6 +161,12 @@ impl Hash {
     }
 }
 
+impl From<[u8; HASH_SIZE]> for Hash {
+    fn from(hash: [u8; HASH_SIZE]) -> Self {
+        Self(hash)
+    }
+}
+
 impl std::fmt::Debug for Hash {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Hash({:?})", self.bytes())
@@ -0,0 +1,11 @@
+use super::{blake256, constants::HASH_SIZE, Hash};
+
+/// Calculates hash(b) and returns the resulting bytes.
+pub fn hash_b(b: &[u8]) -> [u8; HASH_SIZE] {
+    blake256::sum256(b)
+}
+
+/// Calculates hash(b) and returns the resulting bytes as a Hash.
+pub fn hash_h(b: &[u8]) -> Hash {
+    Hash::from(hash_b(b))
+}
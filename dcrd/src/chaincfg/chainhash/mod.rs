@@ -3,10 +3,13 @@
 //! This package provides a generic hash type and associated functions that
 //! allows the specific hash algorithm to be abstracted.
 
+mod blake256;
 pub mod constants;
 mod error;
 mod hash;
+mod hashfuncs;
 mod test;
 
 pub use error::ChainHashError;
 pub use hash::Hash;
+pub use hashfuncs::{hash_b, hash_h};
@@ -112,7 +112,7 @@ mod chain_hash {
 
     #[test]
     fn test_new_hash_from_str() {
-        let tests = vec![
+        let tests = [
             Test {
                 hash_str: String::from(
                     "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
@@ -206,4 +206,34 @@ mod chain_hash {
             }
         }
     }
+
+    #[test]
+    // TestHashFuncs ensures the hash functions return the expected BLAKE-256 digests.
+    fn test_hash_funcs() {
+        use crate::chaincfg::chainhash::{hash_b, hash_h};
+
+        let tests = [
+            (
+                Vec::new(),
+                "716f6e863f744b9ac22c97ec7b76ea5f5908bc5b2f67c61510bfc4751384ea7a",
+            ),
+            (
+                vec![0],
+                "0ce8d4ef4dd7cd8d62dfded9d4edb0a774ae6a41929a74da23109e8f11139c87",
+            ),
+            (
+                vec![0; 72],
+                "d419bad32d504fb7d44d460c42c5593fe544fa4c135dec31e21bd9abdcc22d41",
+            ),
+            (
+                b"The quick brown fox jumps over the lazy dog".to_vec(),
+                "7576698ee9cad30173080678e5965916adbb11cb5245d386bf1ffda1cb26c9d7",
+            ),
+        ];
+
+        for (i, (input, want)) in tests.iter().enumerate() {
+            assert_eq!(hex::encode(hash_b(input)), *want, "hash_b index: {}", i);
+            assert_eq!(hash_h(input).bytes(), &hash_b(input), "hash_h index: {}", i);
+        }
+    }
 }
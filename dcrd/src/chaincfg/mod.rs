@@ -24,3 +24,5 @@
 //! should be unique to the network, but parameter collisions can still occur.
 
 pub mod chainhash;
+pub mod params;
+mod test;
@@ -0,0 +1,135 @@
+//! Network parameters of the standard Decred networks.
+
+use super::chainhash::Hash;
+
+/// Network defines a Decred network by its parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    /// Human-readable name of the network.
+    pub name: &'static str,
+
+    /// Magic bytes identifying messages of the network on the wire.
+    pub net: u32,
+
+    /// Default port of the RPC server.
+    pub rpc_port: u16,
+
+    /// Hash of the first block of the chain.
+    pub genesis_hash: Hash,
+
+    /// Number of blocks between each block subsidy reduction.
+    pub subsidy_reduction_interval: i64,
+
+    /// Height at which stake validation, and hence the proof-of-stake subsidy, begins.
+    pub stake_validation_height: i64,
+
+    /// Compact difficulty bits of the proof of work limit, the target of the minimum
+    /// difficulty.
+    pub pow_limit_bits: u32,
+
+    /// Number of votes included in every block from stake validation height.
+    pub tickets_per_block: u16,
+
+    /// Number of blocks in each ticket price window, after which the stake difficulty is
+    /// recalculated.
+    pub stake_diff_window_size: i64,
+
+    /// Address prefix of secp256k1 ECDSA pay-to-pubkey-hash addresses.
+    pub pubkey_hash_addr_id: [u8; 2],
+
+    /// Address prefix of Ed25519 pay-to-pubkey-hash addresses.
+    pub pkh_edwards_addr_id: [u8; 2],
+
+    /// Address prefix of secp256k1 Schnorr pay-to-pubkey-hash addresses.
+    pub pkh_schnorr_addr_id: [u8; 2],
+
+    /// Address prefix of pay-to-script-hash addresses.
+    pub script_hash_addr_id: [u8; 2],
+}
+
+/// Parameters of the main Decred network.
+pub const MAINNET: Network = Network {
+    name: "mainnet",
+    net: 0xd9b4_00f9,
+    rpc_port: 9109,
+    genesis_hash: Hash::from_bytes([
+        0x80, 0xd9, 0x21, 0x2b, 0xf4, 0xce, 0xb0, 0x66, 0xde, 0xd2, 0x86, 0x6b, 0x39, 0xd4, 0xed,
+        0x89, 0xe0, 0xab, 0x60, 0xf3, 0x35, 0xc1, 0x1d, 0xf8, 0xe7, 0xbf, 0x85, 0xd9, 0xc3, 0x5c,
+        0x8e, 0x29,
+    ]),
+    subsidy_reduction_interval: 6144,
+    stake_validation_height: 4096,
+    pow_limit_bits: 0x1d00_ffff,
+    tickets_per_block: 5,
+    stake_diff_window_size: 144,
+    pubkey_hash_addr_id: [0x07, 0x3f],
+    pkh_edwards_addr_id: [0x07, 0x1f],
+    pkh_schnorr_addr_id: [0x07, 0x01],
+    script_hash_addr_id: [0x07, 0x1a],
+};
+
+/// Parameters of the test network, version 3.
+pub const TESTNET3: Network = Network {
+    name: "testnet3",
+    net: 0xb194_aa75,
+    rpc_port: 19109,
+    genesis_hash: Hash::from_bytes([
+        0xac, 0x9b, 0xa4, 0x34, 0xb6, 0xf7, 0x24, 0x9b, 0x96, 0x98, 0xd1, 0xfc, 0xec, 0x26, 0xd6,
+        0x08, 0x7e, 0x83, 0x58, 0xc8, 0x11, 0xc7, 0xe9, 0x22, 0xf4, 0xca, 0x18, 0x39, 0xe5, 0xdc,
+        0x49, 0xa6,
+    ]),
+    subsidy_reduction_interval: 2048,
+    stake_validation_height: 768,
+    pow_limit_bits: 0x1e00_ffff,
+    tickets_per_block: 5,
+    stake_diff_window_size: 144,
+    pubkey_hash_addr_id: [0x0f, 0x21],
+    pkh_edwards_addr_id: [0x0f, 0x01],
+    pkh_schnorr_addr_id: [0x0e, 0xe3],
+    script_hash_addr_id: [0x0e, 0xfc],
+};
+
+/// Parameters of the simulation test network.
+pub const SIMNET: Network = Network {
+    name: "simnet",
+    net: 0x1214_1c16,
+    rpc_port: 19556,
+    genesis_hash: Hash::from_bytes([
+        0xa6, 0xe8, 0xd7, 0x3b, 0x52, 0x00, 0x8d, 0xe5, 0xf2, 0x6f, 0x09, 0xfe, 0xde, 0x4e, 0xcc,
+        0xff, 0x6f, 0x18, 0x3c, 0x57, 0x3b, 0xdb, 0x94, 0x09, 0x4e, 0x50, 0x40, 0xaf, 0x67, 0x75,
+        0xec, 0x5b,
+    ]),
+    subsidy_reduction_interval: 128,
+    stake_validation_height: 144,
+    pow_limit_bits: 0x207f_ffff,
+    tickets_per_block: 5,
+    stake_diff_window_size: 8,
+    pubkey_hash_addr_id: [0x0e, 0x91],
+    pkh_edwards_addr_id: [0x0e, 0x71],
+    pkh_schnorr_addr_id: [0x0e, 0x53],
+    script_hash_addr_id: [0x0e, 0x6c],
+};
+
+/// Parameters of the regression test network.
+pub const REGNET: Network = Network {
+    name: "regnet",
+    net: 0xe6d3_dc3a,
+    rpc_port: 18656,
+    genesis_hash: Hash::from_bytes([
+        0xb0, 0x7c, 0x05, 0xd3, 0x23, 0x88, 0x51, 0xd9, 0xe2, 0xdc, 0x92, 0x0f, 0x64, 0x9c, 0x64,
+        0x30, 0xd2, 0x32, 0x87, 0x26, 0x43, 0xa0, 0xcf, 0x44, 0xa3, 0xbb, 0x95, 0xae, 0xb4, 0x94,
+        0xed, 0x2c,
+    ]),
+    subsidy_reduction_interval: 128,
+    stake_validation_height: 144,
+    pow_limit_bits: 0x207f_ffff,
+    tickets_per_block: 5,
+    stake_diff_window_size: 8,
+    pubkey_hash_addr_id: [0x0e, 0x00],
+    pkh_edwards_addr_id: [0x0d, 0xe0],
+    pkh_schnorr_addr_id: [0x0d, 0xc2],
+    script_hash_addr_id: [0x0d, 0xdb],
+};
+
+/// The standard Decred networks.
+pub const NETWORKS: [Network; 4] = [MAINNET, TESTNET3, SIMNET, REGNET];
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod params {
+    use crate::chaincfg::params::{MAINNET, NETWORKS, REGNET, SIMNET, TESTNET3};
+
+    #[test]
+    fn test_mainnet_params() {
+        assert_eq!(MAINNET.net, 0xd9b400f9);
+        assert_eq!(
+            MAINNET.genesis_hash.string().unwrap(),
+            "298e5cc3d985bfe7f81dc135f360abe089edd4396b86d2de66b0cef42b21d980"
+        );
+        assert_eq!(MAINNET.rpc_port, 9109);
+        assert_eq!(MAINNET.pubkey_hash_addr_id, [0x07, 0x3f]);
+    }
+
+    #[test]
+    fn test_networks_distinct() {
+        assert_eq!(NETWORKS, [MAINNET, TESTNET3, SIMNET, REGNET]);
+
+        for (i, network) in NETWORKS.iter().enumerate() {
+            for other in &NETWORKS[i + 1..] {
+                assert_ne!(network.net, other.net);
+                assert_ne!(network.rpc_port, other.rpc_port);
+                assert_ne!(network.genesis_hash, other.genesis_hash);
+                assert_ne!(network.pubkey_hash_addr_id, other.pubkey_hash_addr_id);
+                assert_ne!(network.script_hash_addr_id, other.script_hash_addr_id);
+            }
+        }
+    }
+}
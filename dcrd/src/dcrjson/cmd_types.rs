@@ -4,35 +4,129 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-/// EstimateSmartFeeMode defines estimation mode to be used with
-/// the estimatesmartfee command.
-#[derive(Debug, Deserialize)]
-pub enum EstimateSmartFeeMode {
+use crate::dcrutil::amount::Amount;
+
+/// EstimateMode defines estimation mode to be used with
+/// the estimatesmartfee command. Unset leaves the mode out of the request,
+/// letting the server pick its default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EstimateMode {
     Economical,
     Conservative,
+    #[default]
+    Unset,
+}
+
+impl EstimateMode {
+    /// Returns the mode as sent to the server, or None when Unset.
+    pub(crate) fn param(&self) -> Option<serde_json::Value> {
+        match self {
+            EstimateMode::Unset => None,
+
+            mode => Some(serde_json::json!(mode)),
+        }
+    }
 }
 
-impl Serialize for EstimateSmartFeeMode {
+impl Serialize for EstimateMode {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         match self {
-            EstimateSmartFeeMode::Conservative => {
-                serializer.serialize_str(&format!("{}", EstimateSmartFeeMode::Conservative))
-            }
-            EstimateSmartFeeMode::Economical => {
-                serializer.serialize_str(&format!("{}", EstimateSmartFeeMode::Economical))
+            EstimateMode::Conservative | EstimateMode::Economical => {
+                serializer.serialize_str(&self.to_string())
             }
+            EstimateMode::Unset => serializer.serialize_none(),
         }
     }
 }
 
-impl fmt::Display for EstimateSmartFeeMode {
+impl fmt::Display for EstimateMode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            EstimateSmartFeeMode::Conservative => write!(f, "conservative"),
-            EstimateSmartFeeMode::Economical => write!(f, "economical"),
+            EstimateMode::Conservative => write!(f, "conservative"),
+            EstimateMode::Economical => write!(f, "economical"),
+            EstimateMode::Unset => write!(f, "unset"),
+        }
+    }
+}
+
+/// OutPoint describes a transaction outpoint that will be marshalled to and
+/// from JSON, as used by the loadtxfilter command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutPoint {
+    /// Hash of the transaction the output belongs to.
+    pub hash: String,
+    /// Transaction tree of the transaction, 0 for regular and 1 for stake transactions.
+    pub tree: i8,
+    /// Index of the output in the transaction.
+    pub index: u32,
+}
+
+/// TransactionInput represents an output spent by a transaction built with the
+/// createrawtransaction command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransactionInput {
+    /// Value of the spent output, set as the input's value in. Zero when unknown.
+    pub amount: Amount,
+    /// Hash of the transaction the spent output belongs to.
+    pub txid: String,
+    /// Index of the spent output in the transaction.
+    pub vout: u32,
+    /// Transaction tree of the transaction, 0 for regular and 1 for stake transactions.
+    pub tree: i8,
+}
+
+/// Mode of a getblocktemplate request returning a block template to mine on.
+pub const TEMPLATE_MODE_TEMPLATE: &str = "template";
+/// Mode of a getblocktemplate request asking the server to validate a proposed block.
+pub const TEMPLATE_MODE_PROPOSAL: &str = "proposal";
+
+/// TemplateRequest is the request object passed to the getblocktemplate command, such as
+/// through `send_custom_command`. Unset fields are omitted, which the server treats as a
+/// template request without capabilities.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateRequest {
+    /// Either "template", the default when omitted, or "proposal".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// Features supported by the client, such as "longpoll", "coinbasetxn" or "coinbasevalue".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
+    /// Long poll ID of a previous template, returning once the template changes.
+    #[serde(rename = "longpollid", skip_serializing_if = "Option::is_none")]
+    pub long_poll_id: Option<String>,
+    /// Hex encoded serialized block. Required in proposal mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl TemplateRequest {
+    /// Returns a request for a block template, advertising the given capabilities.
+    pub fn template(capabilities: &[&str]) -> Self {
+        TemplateRequest {
+            mode: Some(TEMPLATE_MODE_TEMPLATE.to_string()),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a request asking the server to validate the serialized block without
+    /// submitting it.
+    pub fn proposal(block: &[u8]) -> Self {
+        TemplateRequest {
+            mode: Some(TEMPLATE_MODE_PROPOSAL.to_string()),
+            data: Some(hex::encode(block)),
+            ..Default::default()
         }
     }
+
+    /// Sets the long poll ID, waiting for the template with that ID to change.
+    pub fn with_long_poll_id(mut self, long_poll_id: &str) -> Self {
+        self.long_poll_id = Some(long_poll_id.to_string());
+        self
+    }
 }
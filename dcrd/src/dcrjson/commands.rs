@@ -23,6 +23,10 @@ pub(crate) const NOTIFICATION_METHOD_STAKE_DIFFICULTY: &str = "stakedifficulty";
 /// Notifies that the block chain is in the process of a reorganization.
 pub(crate) const NOTIFICATION_METHOD_REORGANIZATION: &str = "reorganization";
 pub(crate) const NOTIFICATION_METHOD_SPENT_AND_MISSING_TICKETS: &str = "spentandmissedtickets";
+/// Notifies a client of the tickets eligible to vote on a newly connected block.
+pub(crate) const NOTIFICATION_METHOD_WINNING_TICKETS: &str = "winningtickets";
+/// Notifies that an unmined transaction matching the client's transaction filter was accepted.
+pub(crate) const NOTIFICATION_METHOD_RELEVANT_TX_ACCEPTED: &str = "relevanttxaccepted";
 
 /// Issues a notify blocks command to RPC server.
 pub(crate) const METHOD_NOTIFY_BLOCKS: &str = "notifyblocks";
@@ -34,13 +38,82 @@ pub(crate) const METHOD_NOTIFIY_NEW_WORK: &str = "notifywork";
 /// when a new transaction is accepted into the mempool.
 pub(crate) const METHOD_NOTIFY_NEW_TX: &str = "notifynewtransactions";
 pub(crate) const METHOD_NOTIFY_SPEND_AND_MISSED_TICKETS: &str = "notifyspentandmissedtickets";
+/// Registers the client to receive notifications when the stake difficulty changes.
+pub(crate) const METHOD_NOTIFY_STAKE_DIFFICULTY: &str = "notifystakedifficulty";
+/// Loads, or adds to, the transaction filter relevant transaction notifications are matched against.
+pub(crate) const METHOD_LOAD_TX_FILTER: &str = "loadtxfilter";
+/// Registers the client to receive notifications of the tickets eligible to vote on each block.
+pub(crate) const METHOD_NOTIFY_WINNING_TICKETS: &str = "notifywinningtickets";
+/// Cancels block connected and disconnected notifications registered with notifyblocks.
+pub(crate) const METHOD_STOP_NOTIFY_BLOCKS: &str = "stopnotifyblocks";
+/// Cancels transaction accepted notifications registered with notifynewtransactions.
+pub(crate) const METHOD_STOP_NOTIFY_NEW_TX: &str = "stopnotifynewtransactions";
+/// Notification registration commands supported by client.
+pub(crate) const NOTIFICATION_REGISTRATION_METHODS: &[&str] = &[
+    METHOD_NOTIFY_BLOCKS,
+    METHOD_NOTIFY_NEW_TICKETS,
+    METHOD_NOTIFIY_NEW_WORK,
+    METHOD_NOTIFY_NEW_TX,
+    METHOD_NOTIFY_SPEND_AND_MISSED_TICKETS,
+    METHOD_NOTIFY_STAKE_DIFFICULTY,
+    METHOD_NOTIFY_WINNING_TICKETS,
+];
 
 /// Returns information about the current state of the block chain.
 pub(crate) const METHOD_GET_BLOCKCHAIN_INFO: &str = "getblockchaininfo";
 /// Returns the number of blocks in the longest block chain.
 pub(crate) const METHOD_GET_BLOCK_COUNT: &str = "getblockcount";
+/// Returns the hash and height of the best block together.
+pub(crate) const METHOD_GET_BEST_BLOCK: &str = "getbestblock";
+/// Returns the hash of the best block.
+pub(crate) const METHOD_GET_BEST_BLOCK_HASH: &str = "getbestblockhash";
+/// Estimates the minimum, maximum and expected stake difficulty of the next ticket price window.
+pub(crate) const METHOD_ESTIMATE_STAKE_DIFF: &str = "estimatestakediff";
+/// Returns the stake difficulty of the current and next blocks.
+pub(crate) const METHOD_GET_STAKE_DIFFICULTY: &str = "getstakedifficulty";
+/// Returns the network magic of the network the server is on.
+pub(crate) const METHOD_GET_CURRENT_NET: &str = "getcurrentnet";
+/// Returns the hashes of the transactions in the memory pool.
+pub(crate) const METHOD_GET_RAW_MEMPOOL: &str = "getrawmempool";
+/// Returns the number of transactions in the memory pool and their total size.
+pub(crate) const METHOD_GET_MEMPOOL_INFO: &str = "getmempoolinfo";
 /// Returns hash of the block in best block chain at the given height.
 pub(crate) const METHOD_GET_BLOCK_HASH: &str = "getblockhash";
 pub(crate) const METHOD_GET_BLOCK: &str = "getblock";
+/// Returns the header of a block given its hash.
+pub(crate) const METHOD_GET_BLOCK_HEADER: &str = "getblockheader";
+/// Returns the subsidy paid to proof-of-work, proof-of-stake and the treasury at a given height.
+pub(crate) const METHOD_GET_BLOCK_SUBSIDY: &str = "getblocksubsidy";
+/// Returns a hex-encoded bitset describing whether each of the given addresses exists in the blockchain.
+pub(crate) const METHOD_EXISTS_ADDRESSES: &str = "existsaddresses";
 pub(crate) const METHOD_DECODE_RAW_TRANSACTION: &str = "decoderawtransaction";
+/// Returns a serialized transaction spending the given inputs to the given addresses, without signing it.
+pub(crate) const METHOD_CREATE_RAW_TRANSACTION: &str = "createrawtransaction";
+/// Estimates the fee rate transactions should pay to be mined within a number of blocks.
 pub(crate) const METHOD_ESTIMATE_SMART_FEE: &str = "estimatesmartfee";
+/// Returns the fee rate, in DCR/kB, transactions should pay to be mined.
+pub(crate) const METHOD_ESTIMATE_FEE: &str = "estimatefee";
+/// Returns information about an unspent transaction output.
+pub(crate) const METHOD_GET_TX_OUT: &str = "gettxout";
+/// Returns stake version statistics over the most recent stake version intervals.
+pub(crate) const METHOD_GET_STAKE_VERSION_INFO: &str = "getstakeversioninfo";
+/// Returns the vote tally and agendas of the given vote version.
+pub(crate) const METHOD_GET_VOTE_INFO: &str = "getvoteinfo";
+/// Returns information about a transaction given its hash.
+pub(crate) const METHOD_GET_RAW_TRANSACTION: &str = "getrawtransaction";
+/// Returns block headers following the first block of a block locator found in the main chain.
+pub(crate) const METHOD_GET_HEADERS: &str = "getheaders";
+/// Returns the estimated network hashes per second over a window of blocks.
+pub(crate) const METHOD_GET_NETWORK_HASH_PS: &str = "getnetworkhashps";
+/// Returns data about each connected peer.
+pub(crate) const METHOD_GET_PEER_INFO: &str = "getpeerinfo";
+/// Returns the bytes sent and received over all network connections.
+pub(crate) const METHOD_GET_NET_TOTALS: &str = "getnettotals";
+/// Returns mining related information about the server's current block template.
+pub(crate) const METHOD_GET_MINING_INFO: &str = "getmininginfo";
+/// Returns whether serialized transactions would be accepted to the mempool without relaying them.
+pub(crate) const METHOD_TEST_MEMPOOL_ACCEPT: &str = "testmempoolaccept";
+/// Submits a serialized transaction to the server's mempool and relays it.
+pub(crate) const METHOD_SEND_RAW_TRANSACTION: &str = "sendrawtransaction";
+/// Returns transactions involving an address.
+pub(crate) const METHOD_SEARCH_RAW_TRANSACTIONS: &str = "searchrawtransactions";
@@ -0,0 +1,214 @@
+//! JSON Errors.
+//! Contains all possible JSON error for RPC connection.
+
+/// RPC Json errors.
+pub enum RpcServerError {
+    /// Error marshalling server response.
+    Marshaller(serde_json::Error),
+    /// Empty response returned by server.
+    EmptyResponse,
+    /// Invalid response
+    InvalidResponse(String),
+    /// Error returned to client by server.
+    ServerError(super::result_types::RpcError),
+    /// Server did not respond before the request timed out.
+    Timeout,
+    /// Server has no fee estimate for the requested target, with the reasons it gave.
+    NoFeeEstimate(Vec<String>),
+    /// Response of `size` bytes exceeded the maximum response size of `max` bytes and was
+    /// dropped, or the connection it arrived on was lost.
+    ResponseTooLarge { size: usize, max: usize },
+}
+
+impl RpcServerError {
+    /// Returns the code of an error returned by the server.
+    pub fn code(&self) -> Option<RpcErrorCode> {
+        match self {
+            RpcServerError::ServerError(e) => Some(e.code),
+
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RpcServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            RpcServerError::EmptyResponse => write!(f, "Empty response from server."),
+            RpcServerError::InvalidResponse(ref e) => {
+                write!(f, "Invalid response from server, error: {}.", e)
+            }
+            RpcServerError::Marshaller(ref e) => write!(f, "Marshaller error: {}.", e),
+            RpcServerError::ServerError(ref e) => write!(f, "Server returned an error: {:?}.", e),
+            RpcServerError::Timeout => write!(f, "Request timed out waiting for the server."),
+            RpcServerError::NoFeeEstimate(ref e) => {
+                write!(f, "No fee estimate available: {}.", e.join(", "))
+            }
+            RpcServerError::ResponseTooLarge { size, max } => write!(
+                f,
+                "Response of {} bytes exceeds maximum response size of {} bytes.",
+                size, max
+            ),
+        }
+    }
+}
+
+impl std::fmt::Debug for RpcServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            RpcServerError::EmptyResponse => {
+                write!(f, "RpcServerError(Empty response from server)")
+            }
+            RpcServerError::InvalidResponse(ref e) => write!(
+                f,
+                "RpcServerError(Invalid response from server, error: {})",
+                e
+            ),
+            RpcServerError::Marshaller(ref e) => {
+                write!(f, "RpcServerError(Marshaller error: {})", e)
+            }
+            RpcServerError::ServerError(ref e) => {
+                write!(f, "RpcServerError(Server returned an error: {:?})", e)
+            }
+            RpcServerError::Timeout => {
+                write!(
+                    f,
+                    "RpcServerError(Request timed out waiting for the server)"
+                )
+            }
+            RpcServerError::NoFeeEstimate(ref e) => {
+                write!(f, "RpcServerError(No fee estimate available: {:?})", e)
+            }
+            RpcServerError::ResponseTooLarge { size, max } => write!(
+                f,
+                "RpcServerError(Response of {} bytes exceeds maximum response size of {} bytes)",
+                size, max
+            ),
+        }
+    }
+}
+
+/// Error codes returned by the server, covering the standard JSON-RPC codes and the
+/// application codes used by dcrd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RpcErrorCode {
+    /// Invalid JSON was received by the server (-32700).
+    ParseError,
+    /// The JSON sent is not a valid request object (-32600).
+    InvalidRequest,
+    /// The method does not exist or is not available (-32601).
+    MethodNotFound,
+    /// Invalid method parameters (-32602).
+    InvalidParams,
+    /// Internal JSON-RPC error (-32603).
+    InternalError,
+    /// General application defined error (-1).
+    Misc,
+    /// Server is in safe mode and the method is forbidden (-2).
+    ForbiddenBySafeMode,
+    /// Unexpected type was passed as a parameter (-3).
+    Type,
+    /// Invalid address or key. Also returned when no information is available about a
+    /// block or transaction (-5).
+    InvalidAddressOrKey,
+    /// Ran out of memory during the operation (-7).
+    OutOfMemory,
+    /// Invalid, missing or duplicate parameter (-8).
+    InvalidParameter,
+    /// Node is not connected to any peers (-9).
+    ClientNotConnected,
+    /// Node is still downloading the initial blocks (-10).
+    ClientInInitialDownload,
+    /// Database error (-20).
+    Database,
+    /// Error parsing or validating a structure in raw format (-22).
+    Deserialization,
+    /// Node has not been added before (-24).
+    ClientNodeNotAdded,
+    /// General error during transaction or block submission (-25).
+    Verify,
+    /// Server is still warming up (-28).
+    InWarmup,
+    /// Transaction already exists (-40).
+    DuplicateTx,
+    /// Code not known to the client.
+    Unknown(i32),
+}
+
+impl RpcErrorCode {
+    /// Returns the error code for a numeric code returned by the server.
+    pub fn from_i32(code: i32) -> Self {
+        match code {
+            -32700 => RpcErrorCode::ParseError,
+            -32600 => RpcErrorCode::InvalidRequest,
+            -32601 => RpcErrorCode::MethodNotFound,
+            -32602 => RpcErrorCode::InvalidParams,
+            -32603 => RpcErrorCode::InternalError,
+            -1 => RpcErrorCode::Misc,
+            -2 => RpcErrorCode::ForbiddenBySafeMode,
+            -3 => RpcErrorCode::Type,
+            -5 => RpcErrorCode::InvalidAddressOrKey,
+            -7 => RpcErrorCode::OutOfMemory,
+            -8 => RpcErrorCode::InvalidParameter,
+            -9 => RpcErrorCode::ClientNotConnected,
+            -10 => RpcErrorCode::ClientInInitialDownload,
+            -20 => RpcErrorCode::Database,
+            -22 => RpcErrorCode::Deserialization,
+            -24 => RpcErrorCode::ClientNodeNotAdded,
+            -25 => RpcErrorCode::Verify,
+            -28 => RpcErrorCode::InWarmup,
+            -40 => RpcErrorCode::DuplicateTx,
+            code => RpcErrorCode::Unknown(code),
+        }
+    }
+
+    /// Returns the numeric code sent by the server.
+    pub fn to_i32(self) -> i32 {
+        match self {
+            RpcErrorCode::ParseError => -32700,
+            RpcErrorCode::InvalidRequest => -32600,
+            RpcErrorCode::MethodNotFound => -32601,
+            RpcErrorCode::InvalidParams => -32602,
+            RpcErrorCode::InternalError => -32603,
+            RpcErrorCode::Misc => -1,
+            RpcErrorCode::ForbiddenBySafeMode => -2,
+            RpcErrorCode::Type => -3,
+            RpcErrorCode::InvalidAddressOrKey => -5,
+            RpcErrorCode::OutOfMemory => -7,
+            RpcErrorCode::InvalidParameter => -8,
+            RpcErrorCode::ClientNotConnected => -9,
+            RpcErrorCode::ClientInInitialDownload => -10,
+            RpcErrorCode::Database => -20,
+            RpcErrorCode::Deserialization => -22,
+            RpcErrorCode::ClientNodeNotAdded => -24,
+            RpcErrorCode::Verify => -25,
+            RpcErrorCode::InWarmup => -28,
+            RpcErrorCode::DuplicateTx => -40,
+            RpcErrorCode::Unknown(code) => code,
+        }
+    }
+}
+
+impl Default for RpcErrorCode {
+    fn default() -> Self {
+        RpcErrorCode::Unknown(0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RpcErrorCode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        i32::deserialize(deserializer).map(RpcErrorCode::from_i32)
+    }
+}
+
+impl serde::Serialize for RpcErrorCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.to_i32())
+    }
+}
@@ -1,12 +1,15 @@
 pub mod cmd_types;
 pub(crate) mod commands;
-mod error;
+pub mod errors;
 pub mod result_types;
 mod types_test;
+mod unknown_fields;
 
 use crate::chaincfg::chainhash::Hash;
-pub use error::RpcServerError;
+pub use errors::{RpcErrorCode, RpcServerError};
 use log::warn;
+pub(crate) use unknown_fields::decode_result;
+pub use unknown_fields::{DeserializationPolicy, ExtraFields, WithExtraFields};
 
 /// Parse hex string to bytes
 pub(crate) fn parse_hex_parameters(value: &serde_json::Value) -> Option<Vec<u8>> {
@@ -1,18 +1,68 @@
 //! Houses all JSON result types.
 
-use {log::warn, std::collections::HashMap};
+use {
+    super::{unknown_fields::impl_extra_fields, ExtraFields, RpcErrorCode, RpcServerError},
+    crate::{
+        chaincfg::{
+            chainhash::{constants::HASH_SIZE, hash_h, Hash},
+            params::Network,
+        },
+        dcrutil::{amount::Amount, difficulty_from_bits, difficulty_ratio, script},
+    },
+    log::warn,
+    std::collections::HashMap,
+};
+
+impl_extra_fields!(
+    BlockchainInfo,
+    GetBlockVerboseResult,
+    TxRawResult,
+    EstimateSmartFeeResult,
+    GetBlockSubsidyResult,
+    GetStakeVersionInfoResult,
+    GetVoteInfoResult,
+    MempoolAcceptResult,
+    GetPeerInfoResult,
+    GetNetTotalsResult,
+    GetMiningInfoResult,
+    GetBestBlockResult,
+    EstimateStakeDiffResult,
+    GetTxOutResult,
+    GetBlockHeaderVerboseResult,
+    GetMempoolInfoResult,
+    GetRawMempoolVerboseResult,
+    GetStakeDifficultyResult
+);
+
+/// Size in bytes of a serialized block header.
+pub(crate) const BLOCK_HEADER_SIZE: usize = 180;
 
 /// Implements JSON RPC request structure to server.
 #[derive(serde::Serialize)]
 pub(crate) struct JsonRequest<'a> {
     pub jsonrpc: &'a str,
     pub method: &'a str,
-    pub id: u64,
+    pub id: serde_json::Value,
     pub params: &'a [serde_json::Value],
 }
 
+/// Returns the JSON value a request ID is sent as, a string if `as_string` is set.
+pub(crate) fn request_id(id: u64, as_string: bool) -> serde_json::Value {
+    if as_string {
+        serde_json::Value::String(id.to_string())
+    } else {
+        serde_json::Value::from(id)
+    }
+}
+
+/// Returns the request ID a response answers, whether it was sent as a number or a string.
+pub(crate) fn parse_request_id(id: &serde_json::Value) -> Option<u64> {
+    id.as_u64()
+        .or_else(|| id.as_str().and_then(|id| id.parse().ok()))
+}
+
 /// Implements JSON RPC response structure from server.
-#[derive(serde::Deserialize, serde::Serialize, Default, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
 #[serde(default)]
 pub struct JsonResponse {
     pub jsonrpc: String,
@@ -27,12 +77,20 @@ pub struct JsonResponse {
 #[derive(serde::Deserialize, serde::Serialize, Default, Debug)]
 #[serde(default)]
 pub struct RpcError {
-    pub code: i64,
+    pub code: RpcErrorCode,
     pub message: String,
 }
 
+/// Error raised by the client on the server's behalf, carried in the error field of the
+/// response answering a request.
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+pub(crate) enum ClientError {
+    /// Response of `size` bytes exceeded the maximum response size of `max` bytes.
+    ResponseTooLarge { size: usize, max: usize },
+}
+
 /// Provides an overview of an agenda in a consensus deployment.
-#[derive(serde::Deserialize, Default, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug)]
 #[serde(default)]
 pub struct AgendaInfo {
     pub status: String,
@@ -58,12 +116,16 @@ pub struct GetBlockVerboseResult {
     pub merkle_root: String,
     #[serde(rename = "stakeroot")]
     pub stake_root: String,
-    pub tx: Vec<String>,
+    /// Hashes of the transactions in the regular transaction tree.
+    #[serde(with = "hash_strings")]
+    pub tx: Vec<Hash>,
+    /// Regular tree transactions, only returned when verbose_tx is set.
     pub rawtx: Vec<TxRawResult>,
-    #[serde(rename = "stx")]
-    pub stake_tx: Vec<String>,
-    #[serde(rename = "rawstx")]
-    pub raw_stake_tx: Vec<TxRawResult>,
+    /// Hashes of the votes, tickets and revocations in the stake transaction tree.
+    #[serde(with = "hash_strings")]
+    pub stx: Vec<Hash>,
+    /// Stake tree transactions, only returned when verbose_tx is set.
+    pub rawstx: Vec<TxRawResult>,
     pub time: i64,
     #[serde(rename = "mediantime")]
     pub median_time: i64,
@@ -88,14 +150,625 @@ pub struct GetBlockVerboseResult {
     pub difficulty: f64,
     #[serde(rename = "chainwork")]
     pub chain_work: String,
-    #[serde(rename = "previoushash")]
+    #[serde(rename = "previousblockhash", alias = "previoushash")]
     pub previous_hash: String,
     #[serde(rename = "nextblockhash")]
     pub next_block_hash: String,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// Encodes a list of hashes as the byte-reversed hexadecimal strings used by dcrd.
+mod hash_strings {
+    use {
+        crate::chaincfg::chainhash::Hash,
+        serde::{de, ser, ser::SerializeSeq, Deserialize, Deserializer, Serializer},
+    };
+
+    pub fn serialize<S: Serializer>(hashes: &[Hash], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(hashes.len()))?;
+        for hash in hashes {
+            seq.serialize_element(&hash.string().map_err(ser::Error::custom)?)?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Hash>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|hash| Hash::new_from_str(hash).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+/// Encodes a hash as the byte-reversed hexadecimal string used by dcrd.
+mod hash_string {
+    use {
+        crate::{chaincfg::chainhash::Hash, dcrjson::marshal_to_hash},
+        serde::{de, ser, Deserialize, Deserializer, Serializer},
+    };
+
+    pub fn serialize<S: Serializer>(hash: &Hash, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hash.string().map_err(ser::Error::custom)?)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Hash, D::Error> {
+        marshal_to_hash(serde_json::Value::deserialize(deserializer)?)
+            .ok_or_else(|| de::Error::custom("invalid hash"))
+    }
+}
+
+/// Serializes compact difficulty bits as the hex string reported by dcrd.
+mod compact_bits {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bits: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:08x}", bits))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+        u32::from_str_radix(&String::deserialize(deserializer)?, 16).map_err(de::Error::custom)
+    }
+}
+
+/// GetBlockHeaderVerboseResult models the data from the getblockheader command when
+/// the verbose flag is set.  When the verbose flag is not set, getblockheader
+/// returns a hex-encoded string.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetBlockHeaderVerboseResult {
+    pub hash: String,
+    pub confirmations: i64,
+    pub version: i32,
+    #[serde(rename = "merkleroot")]
+    pub merkle_root: String,
+    #[serde(rename = "stakeroot")]
+    pub stake_root: String,
+    #[serde(rename = "votebits")]
+    pub vote_bits: u16,
+    #[serde(rename = "finalstate")]
+    pub final_state: String,
+    pub voters: u16,
+    #[serde(rename = "freshstake")]
+    pub fresh_stake: u8,
+    pub revocations: u8,
+    #[serde(rename = "poolsize")]
+    pub pool_size: u32,
+    /// Compact difficulty bits, reported by dcrd as a hex string.
+    #[serde(with = "compact_bits")]
+    pub bits: u32,
+    #[serde(rename = "sbits")]
+    pub stake_bits: Amount,
+    pub height: u32,
+    pub size: u32,
+    pub time: i64,
+    #[serde(rename = "mediantime")]
+    pub median_time: i64,
+    pub nonce: u32,
+    #[serde(rename = "extradata")]
+    pub extra_data: String,
+    #[serde(rename = "stakeversion")]
+    pub stake_version: u32,
+    pub difficulty: f64,
+    #[serde(rename = "chainwork")]
+    pub chain_work: String,
+    #[serde(rename = "previousblockhash", skip_serializing_if = "String::is_empty")]
+    pub previous_hash: String,
+    #[serde(rename = "nextblockhash", skip_serializing_if = "String::is_empty")]
+    pub next_hash: String,
+
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+impl GetBlockHeaderVerboseResult {
+    /// Decodes a serialized block header, such as the header sent with block connected and
+    /// disconnected notifications, computing its hash.
+    ///
+    /// Only fields committed to by the header are set. Confirmations, median time, chain work
+    /// and the next block hash depend on the server's chain and are left at their defaults. The
+    /// difficulty is computed from the bits relative to the mainnet proof of work limit, use
+    /// `difficulty_ratio` for other networks.
+    pub fn from_bytes(header: &[u8]) -> Result<Self, RpcServerError> {
+        if header.len() != BLOCK_HEADER_SIZE {
+            return Err(RpcServerError::InvalidResponse(format!(
+                "block header is {} bytes, expected {} bytes",
+                header.len(),
+                BLOCK_HEADER_SIZE
+            )));
+        }
+
+        let u16_at = |i: usize| u16::from_le_bytes([header[i], header[i + 1]]);
+        let u32_at =
+            |i: usize| u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]]);
+        let hash_at = |i: usize| {
+            let mut hash = [0; HASH_SIZE];
+            hash.copy_from_slice(&header[i..i + HASH_SIZE]);
+
+            Hash::from(hash)
+                .string()
+                .map_err(|e| RpcServerError::InvalidResponse(e.to_string()))
+        };
+
+        let mut stake_bits = [0; 8];
+        stake_bits.copy_from_slice(&header[120..128]);
+
+        let previous_hash = hash_at(4)?;
+
+        Ok(GetBlockHeaderVerboseResult {
+            hash: hash_h(header)
+                .string()
+                .map_err(|e| RpcServerError::InvalidResponse(e.to_string()))?,
+            version: u32_at(0) as i32,
+            merkle_root: hash_at(36)?,
+            stake_root: hash_at(68)?,
+            vote_bits: u16_at(100),
+            final_state: hex::encode(&header[102..108]),
+            voters: u16_at(108),
+            fresh_stake: header[110],
+            revocations: header[111],
+            pool_size: u32_at(112),
+            bits: u32_at(116),
+            difficulty: difficulty_from_bits(u32_at(116)),
+            stake_bits: Amount::from(i64::from_le_bytes(stake_bits)),
+            height: u32_at(128),
+            size: u32_at(132),
+            time: i64::from(u32_at(136)),
+            nonce: u32_at(140),
+            extra_data: hex::encode(&header[144..176]),
+            stake_version: u32_at(176),
+            previous_hash,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the difficulty of the header's bits on network, the ratio of the network's
+    /// minimum difficulty target to the header's target. This is the difficulty reported by
+    /// dcrd, computed locally from the bits.
+    pub fn difficulty_ratio(&self, network: &Network) -> f64 {
+        difficulty_ratio(self.bits, network.pow_limit_bits)
+    }
+}
+
+/// GetHeadersResult models the data returned from the getheaders command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug)]
+#[serde(default)]
+pub struct GetHeadersResult {
+    /// Hex-encoded serialized block headers.
+    pub headers: Vec<String>,
+}
+
+impl GetHeadersResult {
+    /// Decodes the returned headers.
+    pub fn decode(&self) -> Result<Vec<GetBlockHeaderVerboseResult>, RpcServerError> {
+        self.headers
+            .iter()
+            .map(|header| {
+                let header = hex::decode(header)
+                    .map_err(|e| RpcServerError::InvalidResponse(e.to_string()))?;
+
+                GetBlockHeaderVerboseResult::from_bytes(&header)
+            })
+            .collect()
+    }
+}
+
+/// GetBestBlockResult models the data returned from the getbestblock command.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+pub struct GetBestBlockResult {
+    /// Hash of the block in the longest (best) chain.
+    #[serde(with = "hash_string")]
+    pub hash: Hash,
+    /// Height of the block in the longest (best) chain.
+    pub height: i64,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// GetStakeDifficultyResult models the data returned from the getstakedifficulty command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetStakeDifficultyResult {
+    /// Price of tickets purchased in the next block.
+    #[serde(rename = "current")]
+    pub current: Amount,
+    /// Price of tickets in the next ticket price window, computed once the current window's
+    /// last block is reached and equal to `current` until then.
+    #[serde(rename = "next")]
+    pub next: Amount,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// EstimateStakeDiffResult models the data returned from the estimatestakediff command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct EstimateStakeDiffResult {
+    /// Lowest possible stake difficulty of the next window.
+    pub min: Amount,
+    /// Highest possible stake difficulty of the next window.
+    pub max: Amount,
+    /// Stake difficulty of the next window if tickets keep being purchased at the current rate.
+    pub expected: Amount,
+    /// Stake difficulty of the next window if the requested number of tickets is purchased.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<Amount>,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// GetTxOutResult models the data returned from the gettxout command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetTxOutResult {
+    /// Hash of the best block the output was looked up at.
+    #[serde(rename = "bestblock")]
+    pub best_block: String,
+    /// Confirmations of the transaction, zero when it is in the mempool.
+    pub confirmations: i64,
+    pub value: Amount,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: ScriptPubKeyResult,
+    pub version: u16,
+    pub coinbase: bool,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// GetMempoolInfoResult models the data returned from the getmempoolinfo command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetMempoolInfoResult {
+    /// Number of transactions in the memory pool.
+    pub size: i64,
+    /// Total size in bytes of the transactions in the memory pool.
+    pub bytes: i64,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// GetRawMempoolVerboseResult models the data returned from the getrawmempool command for a
+/// single transaction when the verbose flag is set.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetRawMempoolVerboseResult {
+    /// Size of the transaction in bytes.
+    pub size: i32,
+    /// Fee paid by the transaction.
+    pub fee: Amount,
+    /// Time the transaction entered the memory pool, in seconds since the Unix epoch.
+    pub time: i64,
+    /// Height of the best chain when the transaction entered the memory pool.
+    pub height: i64,
+    #[serde(rename = "startingpriority")]
+    pub starting_priority: f64,
+    #[serde(rename = "currentpriority")]
+    pub current_priority: f64,
+    /// Hashes of the unconfirmed transactions whose outputs the transaction spends.
+    pub depends: Vec<String>,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+impl GetRawMempoolVerboseResult {
+    /// Returns the fee rate paid by the transaction per kB, rounded down to the Atom, zero if
+    /// its size is unknown.
+    pub fn fee_rate(&self) -> Amount {
+        if self.size <= 0 {
+            return Amount::from(0);
+        }
+
+        Amount::from(i64::from(self.fee) * 1000 / i64::from(self.size))
+    }
+}
+
+/// Fee rate helpers on the memory pool transactions returned by get_raw_mempool_verbose, keyed
+/// by transaction hash.
+pub trait MempoolFeeRates {
+    /// Returns the transactions sorted by fee rate, highest first. Transactions paying the same
+    /// fee rate are ordered by hash.
+    fn by_fee_rate(&self) -> Vec<(&String, &GetRawMempoolVerboseResult)>;
+
+    /// Returns the transactions paying at least `min_fee_rate` per kB, sorted by fee rate,
+    /// highest first.
+    fn above_fee_rate(&self, min_fee_rate: Amount) -> Vec<(&String, &GetRawMempoolVerboseResult)>;
+}
+
+impl MempoolFeeRates for HashMap<String, GetRawMempoolVerboseResult> {
+    fn by_fee_rate(&self) -> Vec<(&String, &GetRawMempoolVerboseResult)> {
+        let mut transactions: Vec<_> = self.iter().collect();
+        transactions.sort_by(|(hash_a, tx_a), (hash_b, tx_b)| {
+            tx_b.fee_rate()
+                .cmp(&tx_a.fee_rate())
+                .then_with(|| hash_a.cmp(hash_b))
+        });
+
+        transactions
+    }
+
+    fn above_fee_rate(&self, min_fee_rate: Amount) -> Vec<(&String, &GetRawMempoolVerboseResult)> {
+        let mut transactions = self.by_fee_rate();
+        transactions.retain(|(_, tx)| tx.fee_rate() >= min_fee_rate);
+
+        transactions
+    }
+}
+
+/// GetBlockSubsidyResult models the data returned from the getblocksubsidy command.
+/// All values are in atoms.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetBlockSubsidyResult {
+    pub developer: i64,
+    pub pos: i64,
+    pub pow: i64,
+    pub total: i64,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// ExistsAddressesResult pairs each address passed to the existsaddresses command with
+/// whether it was found in the blockchain, so callers don't have to align the
+/// server's bitset with their input themselves.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ExistsAddressesResult {
+    pub addresses: Vec<(String, bool)>,
+}
+
+impl ExistsAddressesResult {
+    /// Decodes the bitset returned by existsaddresses. Bit `i` of the bitset, stored
+    /// least significant bit first in byte `i / 8`, is set when the address at index `i`
+    /// exists.
+    ///
+    /// An error is returned if the bitset does not hold exactly one bit per address rounded up
+    /// to whole bytes, as the flags could not be aligned with the addresses.
+    pub(crate) fn from_bitset(
+        addresses: Vec<String>,
+        bitset: &[u8],
+    ) -> Result<Self, RpcServerError> {
+        let expected = addresses.len().div_ceil(8);
+        if bitset.len() != expected {
+            return Err(RpcServerError::InvalidResponse(format!(
+                "exists addresses bitset is {} bytes, expected {} bytes for {} addresses",
+                bitset.len(),
+                expected,
+                addresses.len()
+            )));
+        }
+
+        let addresses = addresses
+            .into_iter()
+            .enumerate()
+            .map(|(i, address)| {
+                let exists = bitset[i / 8] & (1 << (i % 8)) != 0;
+
+                (address, exists)
+            })
+            .collect();
+
+        Ok(ExistsAddressesResult { addresses })
+    }
+
+    /// Returns an iterator over each address and whether it exists.
+    pub fn iter(&self) -> impl Iterator<Item = &(String, bool)> {
+        self.addresses.iter()
+    }
+
+    /// Consumes the result returning only addresses that exist, in input order.
+    pub fn into_present(self) -> Vec<String> {
+        self.addresses
+            .into_iter()
+            .filter_map(|(address, exists)| if exists { Some(address) } else { None })
+            .collect()
+    }
+}
+
+/// VersionCount models a count of votes or blocks for a version.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct VersionCount {
+    pub version: u32,
+    pub count: u32,
+}
+
+/// VersionInterval models the stake versions seen over a stake version interval.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct VersionInterval {
+    #[serde(rename = "startheight")]
+    pub start_height: i64,
+    #[serde(rename = "endheight")]
+    pub end_height: i64,
+    #[serde(rename = "posversions")]
+    pub pos_versions: Vec<VersionCount>,
+    #[serde(rename = "voteversions")]
+    pub vote_versions: Vec<VersionCount>,
+}
+
+/// GetPeerInfoResult models the data returned from the getpeerinfo command for a single peer.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetPeerInfoResult {
+    pub id: i32,
+    pub addr: String,
+    #[serde(rename = "addrlocal")]
+    pub addr_local: String,
+    pub services: String,
+    #[serde(rename = "relaytxes")]
+    pub relay_txes: bool,
+    #[serde(rename = "lastsend")]
+    pub last_send: i64,
+    #[serde(rename = "lastrecv")]
+    pub last_recv: i64,
+    #[serde(rename = "bytessent")]
+    pub bytes_sent: u64,
+    #[serde(rename = "bytesrecv")]
+    pub bytes_recv: u64,
+    #[serde(rename = "conntime")]
+    pub conn_time: i64,
+    #[serde(rename = "timeoffset")]
+    pub time_offset: i64,
+    /// Round trip time of the last answered ping in microseconds, zero if no ping was answered.
+    #[serde(rename = "pingtime")]
+    pub ping_time: f64,
+    /// Time in microseconds an unanswered ping has been waiting for.
+    #[serde(rename = "pingwait")]
+    pub ping_wait: f64,
+    pub version: u32,
+    #[serde(rename = "subver")]
+    pub sub_ver: String,
+    pub inbound: bool,
+    #[serde(rename = "startingheight")]
+    pub starting_height: i64,
+    #[serde(rename = "currentheight")]
+    pub current_height: i64,
+    #[serde(rename = "banscore")]
+    pub ban_score: i32,
+    #[serde(rename = "syncnode")]
+    pub sync_node: bool,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// GetNetTotalsResult models the data returned from the getnettotals command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetNetTotalsResult {
+    /// Bytes received over all network connections since the server started.
+    #[serde(rename = "totalbytesrecv")]
+    pub total_bytes_recv: u64,
+    /// Bytes sent over all network connections since the server started.
+    #[serde(rename = "totalbytessent")]
+    pub total_bytes_sent: u64,
+    /// Server time the totals were taken at, in milliseconds since the Unix epoch.
+    #[serde(rename = "timemillis")]
+    pub time_millis: i64,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// GetMiningInfoResult models the data returned from the getmininginfo command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetMiningInfoResult {
+    /// Height of the most recent block.
+    pub blocks: i64,
+    /// Size in bytes of the last block template generated.
+    #[serde(rename = "currentblocksize")]
+    pub current_block_size: u64,
+    /// Number of transactions in the last block template generated.
+    #[serde(rename = "currentblocktx")]
+    pub current_block_tx: u64,
+    /// Proof-of-work difficulty as a multiple of the minimum difficulty.
+    pub difficulty: f64,
+    /// Price in atoms of a ticket for the current stake difficulty window.
+    #[serde(rename = "stakedifficulty")]
+    pub stake_difficulty: i64,
+    /// Any network or blockchain warnings.
+    pub errors: String,
+    /// Whether the server's CPU miner is enabled.
+    pub generate: bool,
+    /// Number of processors the CPU miner is limited to.
+    #[serde(rename = "genproclimit")]
+    pub gen_proc_limit: i32,
+    /// Hashes per second of the server's CPU miner.
+    #[serde(rename = "hashespersec")]
+    pub hashes_per_sec: i64,
+    /// Estimated network hashes per second.
+    #[serde(rename = "networkhashps")]
+    pub network_hash_ps: i64,
+    /// Number of transactions in the memory pool.
+    #[serde(rename = "pooledtx")]
+    pub pooled_tx: u64,
+    /// Whether the server is running on the test network.
+    #[serde(rename = "testnet")]
+    pub test_net: bool,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// GetStakeVersionInfoResult models the data returned from the getstakeversioninfo command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetStakeVersionInfoResult {
+    #[serde(rename = "currentheight")]
+    pub current_height: i64,
+    pub hash: String,
+    pub intervals: Vec<VersionInterval>,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
+}
+
+/// Choice models an individual choice inside an Agenda.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct Choice {
+    pub id: String,
+    pub description: String,
+    pub bits: u16,
+    #[serde(rename = "isabstain")]
+    pub is_abstain: bool,
+    #[serde(rename = "isno")]
+    pub is_no: bool,
+    pub count: u32,
+    pub progress: f64,
+}
+
+/// Agenda models an individual agenda including its choices.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct Agenda {
+    pub id: String,
+    pub description: String,
+    pub mask: u16,
+    #[serde(rename = "starttime")]
+    pub start_time: u64,
+    #[serde(rename = "expiretime")]
+    pub expire_time: u64,
+    pub status: String,
+    #[serde(rename = "quorumprogress")]
+    pub quorum_progress: f64,
+    pub choices: Vec<Choice>,
+}
+
+/// GetVoteInfoResult models the data returned from the getvoteinfo command.
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GetVoteInfoResult {
+    #[serde(rename = "currentheight")]
+    pub current_height: i64,
+    #[serde(rename = "startheight")]
+    pub start_height: i64,
+    #[serde(rename = "endheight")]
+    pub end_height: i64,
+    pub hash: String,
+    #[serde(rename = "voteversion")]
+    pub vote_version: u32,
+    pub quorum: u32,
+    #[serde(rename = "totalvotes")]
+    pub total_votes: u32,
+    pub agendas: Vec<Agenda>,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
 }
 
 /// BlockchainInfo models the data returned from the get_blockchain_info command.
-#[derive(serde::Deserialize, Default, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug)]
 #[serde(default)]
 pub struct BlockchainInfo {
     pub chain: String,
@@ -117,6 +790,9 @@ pub struct BlockchainInfo {
     #[serde(rename = "maxblocksize")]
     pub max_block_size: i64,
     pub deployments: HashMap<String, AgendaInfo>,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
@@ -129,8 +805,17 @@ pub struct ScriptPubKeyResult {
     #[serde(rename = "type")]
     pub script_type: String,
     pub addresses: Vec<String>,
-    #[serde(rename = "commitamt")]
-    pub commit_amount: f64,
+    /// Amount committed by a ticket commitment output, absent from other outputs.
+    #[serde(rename = "commitamt", skip_serializing_if = "Option::is_none")]
+    pub commit_amount: Option<Amount>,
+}
+
+impl ScriptPubKeyResult {
+    /// Returns the disassembly of the script, the one sent by the server or, when the server
+    /// sent only the hex, one computed from the hex.
+    pub fn disasm(&self) -> String {
+        disasm_script(&self.asm, &self.hex)
+    }
 }
 
 /// ScriptSig models a signature script.  It is defined separately since it only
@@ -143,15 +828,48 @@ pub struct ScriptSig {
     pub hex: String,
 }
 
-// TransactionInput represents the inputs to a transaction.  Specifically a
-// transaction hash and output number pair. Contains Decred additions.
+impl ScriptSig {
+    /// Returns the disassembly of the script, the one sent by the server or, when the server
+    /// sent only the hex, one computed from the hex.
+    pub fn disasm(&self) -> String {
+        disasm_script(&self.asm, &self.hex)
+    }
+}
+
+/// Returns asm, or the disassembly of the hex-encoded script when asm is empty.
+fn disasm_script(asm: &str, hex: &str) -> String {
+    if !asm.is_empty() {
+        return asm.to_string();
+    }
+
+    match hex::decode(hex) {
+        Ok(script) => script::disasm(&script),
+
+        Err(e) => {
+            warn!("error decoding hex-encoded script, error: {}", e);
+            "[error]".to_string()
+        }
+    }
+}
+
+pub use super::cmd_types::TransactionInput;
+
+/// MempoolAcceptResult models the acceptance of a single transaction from the
+/// testmempoolaccept command.
 #[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
 #[serde(default)]
-pub struct TransactionInput {
-    pub amount: f64,
+pub struct MempoolAcceptResult {
     pub txid: String,
-    pub vout: u32,
-    pub tree: i8,
+    pub allowed: bool,
+    /// Reason the transaction would be rejected, set when not allowed.
+    #[serde(rename = "reject-reason", skip_serializing_if = "Option::is_none")]
+    pub reject_reason: Option<String>,
+    /// Fee paid by the transaction, set when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<Amount>,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
 }
 
 // TxRawDecodeResult models the data from the decoderawtransaction command.
@@ -172,6 +890,9 @@ pub struct EstimateSmartFeeResult {
     pub feerate: f64,
     pub errors: Vec<String>,
     pub blocks: i64,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
 }
 
 /// TxRawResult models the data from the getrawtransaction command.
@@ -196,6 +917,9 @@ pub struct TxRawResult {
     pub confirmations: i64,
     pub time: i64,
     pub blocktime: i64,
+    /// Fields returned by the server that are not part of this result.
+    #[serde(skip)]
+    pub extra_fields: ExtraFields,
 }
 
 /// Vin models parts of the tx data. It is defined separately since getrawtransaction, decoderawtransaction, and searchrawtransaction use the same structure.
@@ -210,7 +934,7 @@ pub struct Vin {
     pub tree: i8,
     pub sequence: u32,
     #[serde(rename = "amountin")]
-    pub amount_in: f64,
+    pub amount_in: Amount,
     #[serde(rename = "blockheight")]
     pub block_height: u32,
     #[serde(rename = "blockindex")]
@@ -224,7 +948,7 @@ pub struct Vin {
 #[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone)]
 #[serde(default)]
 pub struct Vout {
-    pub value: f64,
+    pub value: Amount,
     pub n: u32,
     pub version: u16,
     #[serde(rename = "scriptPubKey")]
@@ -246,7 +970,7 @@ impl Vin {
     pub fn marshal_json(&self) -> Result<Vec<u8>, super::RpcServerError> {
         #[derive(serde::Serialize)]
         pub struct CoinbaseStruct {
-            pub amountin: f64,
+            pub amountin: Amount,
             pub blockheight: u32,
             pub blockindex: u32,
             #[serde(skip_serializing_if = "Option::is_none")]
@@ -297,7 +1021,7 @@ impl Vin {
             pub vout: u32,
             pub tree: i8,
             pub sequence: u32,
-            pub amountin: f64,
+            pub amountin: Amount,
             pub blockheight: u32,
             pub blockindex: u32,
         }
@@ -322,3 +1046,128 @@ impl Vin {
         }
     }
 }
+
+/// SubmissionReject is the reason a server gives for rejecting a submitted block or work.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionReject {
+    /// Block was already accepted ("duplicate").
+    Duplicate,
+    /// Block was already rejected as invalid ("duplicate-invalid").
+    DuplicateInvalid,
+    /// Block was already submitted but not yet validated ("duplicate-inconclusive").
+    DuplicateInconclusive,
+    /// Block is valid but was not connected to the best chain ("inconclusive").
+    Inconclusive,
+    /// Block does not build on the best block ("inconclusive-not-best-prevblk").
+    NotBestPrevBlock,
+    /// Work was for a block template that is no longer current ("stale").
+    Stale,
+    /// Block's previous block is invalid ("bad-prevblk").
+    BadPrevBlock,
+    /// Block hash does not meet its target difficulty ("high-hash").
+    HighHash,
+    /// Block's difficulty bits are incorrect ("bad-diffbits").
+    BadDiffBits,
+    /// Block's timestamp is too far in the past ("time-too-old").
+    TimeTooOld,
+    /// Block's timestamp is too far in the future ("time-too-new").
+    TimeTooNew,
+    /// Block contains an invalid transaction ("bad-txns-..."), with the reason following
+    /// the prefix, such as "inputs-missingorspent".
+    BadTransactions(String),
+    /// Reason not known to the client.
+    Other(String),
+}
+
+impl SubmissionReject {
+    /// Returns the rejection for a reason string returned by the server. A leading
+    /// "rejected: " is ignored.
+    pub fn from_reason(reason: &str) -> Self {
+        let reason = reason.trim();
+        let reason = reason.strip_prefix("rejected: ").unwrap_or(reason);
+
+        match reason {
+            "duplicate" => SubmissionReject::Duplicate,
+            "duplicate-invalid" => SubmissionReject::DuplicateInvalid,
+            "duplicate-inconclusive" => SubmissionReject::DuplicateInconclusive,
+            "inconclusive" => SubmissionReject::Inconclusive,
+            "inconclusive-not-best-prevblk" => SubmissionReject::NotBestPrevBlock,
+            "stale" => SubmissionReject::Stale,
+            "bad-prevblk" => SubmissionReject::BadPrevBlock,
+            "high-hash" => SubmissionReject::HighHash,
+            "bad-diffbits" => SubmissionReject::BadDiffBits,
+            "time-too-old" => SubmissionReject::TimeTooOld,
+            "time-too-new" => SubmissionReject::TimeTooNew,
+            reason => match reason.strip_prefix("bad-txns-") {
+                Some(detail) => SubmissionReject::BadTransactions(detail.to_string()),
+
+                None => SubmissionReject::Other(reason.to_string()),
+            },
+        }
+    }
+
+    /// Returns the reason string sent by the server.
+    pub fn reason(&self) -> String {
+        match self {
+            SubmissionReject::Duplicate => "duplicate".to_string(),
+            SubmissionReject::DuplicateInvalid => "duplicate-invalid".to_string(),
+            SubmissionReject::DuplicateInconclusive => "duplicate-inconclusive".to_string(),
+            SubmissionReject::Inconclusive => "inconclusive".to_string(),
+            SubmissionReject::NotBestPrevBlock => "inconclusive-not-best-prevblk".to_string(),
+            SubmissionReject::Stale => "stale".to_string(),
+            SubmissionReject::BadPrevBlock => "bad-prevblk".to_string(),
+            SubmissionReject::HighHash => "high-hash".to_string(),
+            SubmissionReject::BadDiffBits => "bad-diffbits".to_string(),
+            SubmissionReject::TimeTooOld => "time-too-old".to_string(),
+            SubmissionReject::TimeTooNew => "time-too-new".to_string(),
+            SubmissionReject::BadTransactions(detail) => format!("bad-txns-{}", detail),
+            SubmissionReject::Other(reason) => reason.clone(),
+        }
+    }
+
+    /// Returns whether the submission was valid but arrived too late to extend the best chain,
+    /// such as a block already accepted or no longer building on the best block. Stale
+    /// submissions call for fresh work rather than a fix to the miner.
+    pub fn is_stale(&self) -> bool {
+        matches!(
+            self,
+            SubmissionReject::Duplicate
+                | SubmissionReject::DuplicateInconclusive
+                | SubmissionReject::Inconclusive
+                | SubmissionReject::NotBestPrevBlock
+                | SubmissionReject::Stale
+        )
+    }
+
+    /// Returns whether the submission breaks a consensus rule.
+    pub fn is_invalid(&self) -> bool {
+        matches!(
+            self,
+            SubmissionReject::DuplicateInvalid
+                | SubmissionReject::BadPrevBlock
+                | SubmissionReject::HighHash
+                | SubmissionReject::BadDiffBits
+                | SubmissionReject::TimeTooOld
+                | SubmissionReject::TimeTooNew
+                | SubmissionReject::BadTransactions(_)
+        )
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SubmissionReject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(|reason| SubmissionReject::from_reason(&reason))
+    }
+}
+
+impl serde::Serialize for SubmissionReject {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.reason())
+    }
+}
@@ -23,7 +23,7 @@ mod dcr_types_test {
             result: Vin,
         }
 
-        let tests = vec![
+        let tests = [
             Value {
                 name: "custom vin marshal with coinbase",
                 expected: serde_json::json!({
@@ -85,4 +85,601 @@ mod dcr_types_test {
             )
         }
     }
+
+    #[test]
+    fn test_exists_addresses_alignment() {
+        use crate::dcrjson::{result_types::ExistsAddressesResult, RpcServerError};
+
+        let addresses: Vec<String> = (0..10).map(|i| format!("Dsaddr{}", i)).collect();
+
+        // Addresses 0, 3, 7 and 9 exist. Bits are ordered least significant first
+        // within each byte so address 9 is bit 1 of the second byte.
+        let bitset = [0b1000_1001, 0b0000_0010];
+
+        let result = ExistsAddressesResult::from_bitset(addresses.clone(), &bitset).unwrap();
+
+        for (i, (address, exists)) in result.iter().enumerate() {
+            assert_eq!(address, &addresses[i], "address {} misaligned", i);
+            assert_eq!(
+                *exists,
+                matches!(i, 0 | 3 | 7 | 9),
+                "address {} existence mismatch",
+                i
+            );
+        }
+
+        assert_eq!(
+            result.into_present(),
+            vec!["Dsaddr0", "Dsaddr3", "Dsaddr7", "Dsaddr9"]
+        );
+
+        // Bitsets too short or too long for the addresses can't be aligned.
+        for bitset in [&[0xff][..], &[0xff, 0x00, 0x00]] {
+            assert!(matches!(
+                ExistsAddressesResult::from_bitset(addresses.clone(), bitset),
+                Err(RpcServerError::InvalidResponse(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_result_round_trip() {
+        use crate::dcrjson::result_types::{
+            BlockchainInfo, GetStakeVersionInfoResult, GetVoteInfoResult, TxRawResult, Vout,
+        };
+        use crate::dcrutil::amount::Denomination;
+
+        fn round_trip<T>(wire: serde_json::Value)
+        where
+            T: serde::de::DeserializeOwned + serde::Serialize,
+        {
+            let result: T = serde_json::from_value(wire.clone()).unwrap();
+            let marshalled = serde_json::to_value(&result).unwrap();
+
+            assert_eq!(
+                marshalled,
+                wire,
+                "{} did not round trip",
+                std::any::type_name::<T>()
+            );
+        }
+
+        round_trip::<BlockchainInfo>(serde_json::json!({
+            "chain": "mainnet",
+            "blocks": 700000,
+            "headers": 700000,
+            "syncheight": 700000,
+            "bestblockhash": "0000000000000000201ba6e6a1b1e6bbd1c79abf2d9d1b8a4c4f1c86a0a6e6e1",
+            "difficulty": 404091118,
+            "difficultyratio": 7024.5,
+            "verificationprogress": 1.5,
+            "chainwork": "0000000000000000000000000000000000000000001c7d4f1b9c2e2e6b1a2d38",
+            "initialblockdownload": false,
+            "maxblocksize": 393216,
+            "deployments": {
+                "reverttreasurypolicy": {
+                    "status": "started",
+                    "since": 697344,
+                    "starttime": 1631750400,
+                    "expiretime": 1694822400,
+                },
+            },
+        }));
+
+        round_trip::<GetStakeVersionInfoResult>(serde_json::json!({
+            "currentheight": 700000,
+            "hash": "0000000000000000201ba6e6a1b1e6bbd1c79abf2d9d1b8a4c4f1c86a0a6e6e1",
+            "intervals": [{
+                "startheight": 699840,
+                "endheight": 700000,
+                "posversions": [{ "version": 10, "count": 160 }],
+                "voteversions": [{ "version": 10, "count": 780 }],
+            }],
+        }));
+
+        round_trip::<GetVoteInfoResult>(serde_json::json!({
+            "currentheight": 700000,
+            "startheight": 693504,
+            "endheight": 701567,
+            "hash": "0000000000000000201ba6e6a1b1e6bbd1c79abf2d9d1b8a4c4f1c86a0a6e6e1",
+            "voteversion": 10,
+            "quorum": 4032,
+            "totalvotes": 3820,
+            "agendas": [{
+                "id": "reverttreasurypolicy",
+                "description": "Change maximum treasury expenditure policy",
+                "mask": 6,
+                "starttime": 1631750400,
+                "expiretime": 1694822400,
+                "status": "started",
+                "quorumprogress": 0.75,
+                "choices": [{
+                    "id": "abstain",
+                    "description": "abstain voting for change",
+                    "bits": 0,
+                    "isabstain": true,
+                    "isno": false,
+                    "count": 120,
+                    "progress": 0.25,
+                }],
+            }],
+        }));
+
+        round_trip::<TxRawResult>(serde_json::json!({
+            "hex": "0100",
+            "txid": "4fd4e9f66f1e9dd7b44a0e1df9f8ee1bb8e1c4c71cfc9c03b8d3ad2a04ff2741",
+            "version": 1,
+            "locktime": 0,
+            "expiry": 0,
+            "vin": [{
+                "coinbase": "",
+                "stakebase": "",
+                "txid": "8a8c6ac2ab4b0e0e8fdc3eb2c4e7e5b1c2e0e4c1b7e5e6f7a8b9c0d1e2f3a4b5",
+                "vout": 1,
+                "tree": 0,
+                "sequence": 4294967295_u32,
+                "amountin": 2.5,
+                "blockheight": 699990,
+                "blockindex": 3,
+                "scriptSig": { "asm": "0", "hex": "00" },
+            }],
+            "vout": [{
+                "value": 2.25,
+                "n": 0,
+                "version": 0,
+                "scriptPubKey": {
+                    "asm": "OP_DUP",
+                    "hex": "76",
+                    "reqSigs": 1,
+                    "type": "pubkeyhash",
+                    "addresses": ["DsQxuVRvS4eaJ42dhQEsCXauMWjvopWgrVg"],
+                    "commitamt": 0.5,
+                },
+            }],
+            "blockhash": "0000000000000000201ba6e6a1b1e6bbd1c79abf2d9d1b8a4c4f1c86a0a6e6e1",
+            "blockheight": 700000,
+            "blockindex": 2,
+            "confirmations": 6,
+            "time": 1640995200,
+            "blocktime": 1640995200,
+        }));
+
+        // Amounts keep every Atom of the decimal text sent by the server.
+        let vout: Vout = serde_json::from_str(r#"{ "value": 76476099.53243772 }"#).unwrap();
+        assert_eq!(
+            vout.value.to_unit(Denomination::AmountAtom),
+            7647609953243772.0
+        );
+    }
+
+    #[test]
+    fn test_rpc_error_codes() {
+        use crate::dcrjson::{
+            errors::{RpcErrorCode, RpcServerError},
+            result_types::RpcError,
+        };
+
+        let codes = [
+            (-32700, RpcErrorCode::ParseError),
+            (-32600, RpcErrorCode::InvalidRequest),
+            (-32601, RpcErrorCode::MethodNotFound),
+            (-32602, RpcErrorCode::InvalidParams),
+            (-32603, RpcErrorCode::InternalError),
+            (-1, RpcErrorCode::Misc),
+            (-2, RpcErrorCode::ForbiddenBySafeMode),
+            (-3, RpcErrorCode::Type),
+            (-5, RpcErrorCode::InvalidAddressOrKey),
+            (-7, RpcErrorCode::OutOfMemory),
+            (-8, RpcErrorCode::InvalidParameter),
+            (-9, RpcErrorCode::ClientNotConnected),
+            (-10, RpcErrorCode::ClientInInitialDownload),
+            (-20, RpcErrorCode::Database),
+            (-22, RpcErrorCode::Deserialization),
+            (-24, RpcErrorCode::ClientNodeNotAdded),
+            (-25, RpcErrorCode::Verify),
+            (-28, RpcErrorCode::InWarmup),
+            (-40, RpcErrorCode::DuplicateTx),
+            (-4, RpcErrorCode::Unknown(-4)),
+        ];
+
+        for (code, expected) in codes {
+            assert_eq!(RpcErrorCode::from_i32(code), expected, "code {}", code);
+            assert_eq!(expected.to_i32(), code, "{:?}", expected);
+        }
+
+        let error: RpcError =
+            serde_json::from_str(r#"{"code":-32601,"message":"Method not found"}"#).unwrap();
+        assert_eq!(error.code, RpcErrorCode::MethodNotFound);
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({"code": -32601, "message": "Method not found"})
+        );
+        assert_eq!(
+            RpcServerError::ServerError(error).code(),
+            Some(RpcErrorCode::MethodNotFound)
+        );
+    }
+
+    #[test]
+    fn test_submission_reject() {
+        use crate::dcrjson::result_types::SubmissionReject;
+
+        let reasons = [
+            ("duplicate", SubmissionReject::Duplicate, true, false),
+            (
+                "duplicate-invalid",
+                SubmissionReject::DuplicateInvalid,
+                false,
+                true,
+            ),
+            (
+                "inconclusive-not-best-prevblk",
+                SubmissionReject::NotBestPrevBlock,
+                true,
+                false,
+            ),
+            ("stale", SubmissionReject::Stale, true, false),
+            ("high-hash", SubmissionReject::HighHash, false, true),
+            (
+                "bad-txns-inputs-missingorspent",
+                SubmissionReject::BadTransactions("inputs-missingorspent".to_string()),
+                false,
+                true,
+            ),
+            (
+                "unexpected-reason",
+                SubmissionReject::Other("unexpected-reason".to_string()),
+                false,
+                false,
+            ),
+        ];
+
+        for (reason, expected, stale, invalid) in reasons {
+            let reject = SubmissionReject::from_reason(reason);
+
+            assert_eq!(reject, expected, "reason {}", reason);
+            assert_eq!(reject.reason(), reason);
+            assert_eq!(reject.is_stale(), stale, "reason {}", reason);
+            assert_eq!(reject.is_invalid(), invalid, "reason {}", reason);
+
+            assert_eq!(
+                serde_json::from_value::<SubmissionReject>(serde_json::json!(reason)).unwrap(),
+                expected
+            );
+            assert_eq!(
+                serde_json::to_value(&expected).unwrap(),
+                serde_json::json!(reason)
+            );
+        }
+
+        assert_eq!(
+            SubmissionReject::from_reason("rejected: duplicate"),
+            SubmissionReject::Duplicate
+        );
+    }
+
+    #[test]
+    fn test_template_request() {
+        use crate::dcrjson::cmd_types::TemplateRequest;
+
+        let template = TemplateRequest::template(&["longpoll", "coinbasetxn"]).with_long_poll_id(
+            "0000000000000000000000000000000000000000000000000000000000000000123",
+        );
+        assert_eq!(
+            serde_json::to_value(&template).unwrap(),
+            serde_json::json!({
+                "mode": "template",
+                "capabilities": ["longpoll", "coinbasetxn"],
+                "longpollid": "0000000000000000000000000000000000000000000000000000000000000000123",
+            })
+        );
+
+        let proposal = TemplateRequest::proposal(&[0x01, 0x00, 0xff]);
+        assert_eq!(
+            serde_json::to_value(&proposal).unwrap(),
+            serde_json::json!({ "mode": "proposal", "data": "0100ff" })
+        );
+
+        // Omitted fields are restored to their defaults.
+        assert_eq!(
+            serde_json::from_value::<TemplateRequest>(
+                serde_json::json!({ "mode": "proposal", "data": "0100ff" })
+            )
+            .unwrap(),
+            proposal
+        );
+        assert_eq!(
+            serde_json::to_value(TemplateRequest::default()).unwrap(),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn test_block_header_difficulty_ratio() {
+        use crate::{
+            chaincfg::params::{MAINNET, SIMNET, TESTNET3},
+            dcrjson::result_types::GetBlockHeaderVerboseResult,
+        };
+
+        let header: GetBlockHeaderVerboseResult = serde_json::from_value(serde_json::json!({
+            "hash": "000000000000437482b6d47f82f374cde539440ddb108b0a76886f0d87d126b9",
+            "height": 1,
+            "bits": "1b01ffff",
+            "difficulty": 32767.74999809,
+        }))
+        .unwrap();
+
+        assert_eq!(header.bits, 0x1b01ffff);
+        assert_eq!(serde_json::to_value(&header).unwrap()["bits"], "1b01ffff");
+
+        // Target 0x1ffff << 192 against the mainnet limit 0xffff << 208.
+        let expected = f64::from(0xffff_u32 << 16) / f64::from(0x1ffff_u32);
+        let ratio = header.difficulty_ratio(&MAINNET);
+        assert_eq!(ratio, expected);
+        assert!((ratio - header.difficulty).abs() < 1e-6);
+
+        // The testnet limit is a byte larger, the simnet limit 0x7fffff << 232.
+        assert_eq!(header.difficulty_ratio(&TESTNET3), expected * 256.0);
+        assert_eq!(
+            header.difficulty_ratio(&SIMNET),
+            f64::from(0x7fffff_u32) * 2f64.powi(40) / f64::from(0x1ffff_u32)
+        );
+
+        assert!(serde_json::from_value::<GetBlockHeaderVerboseResult>(
+            serde_json::json!({ "bits": "not hex" })
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_coinbase_tx_raw_result() {
+        use crate::dcrjson::result_types::TxRawResult;
+
+        // A mainnet coinbase as returned by getrawtransaction, without its hex, paying the
+        // treasury, committing to the block height and paying the miner.
+        let tx: TxRawResult = serde_json::from_value(serde_json::json!({
+            "txid": "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90",
+            "version": 1,
+            "locktime": 0,
+            "expiry": 0,
+            "vin": [{
+                "coinbase": "0000000000000000000000000000000000000000000000000000000000000000",
+                "sequence": 4294967295_u32,
+                "amountin": 15.93036788,
+                "blockheight": 0,
+                "blockindex": 4294967295_u32,
+            }],
+            "vout": [
+                {
+                    "value": 1.59303679,
+                    "n": 0,
+                    "version": 0,
+                    "scriptPubKey": {
+                        "asm": "OP_HASH160 f5916158e3e2c4551c1796708db8367207ed13bb OP_EQUAL",
+                        "hex": "a914f5916158e3e2c4551c1796708db8367207ed13bb87",
+                        "reqSigs": 1,
+                        "type": "scripthash",
+                        "addresses": ["Dcur2mcGjmENx4DhNqDctW5wJCVyT3Qeqkx"],
+                    },
+                },
+                {
+                    "value": 0,
+                    "n": 1,
+                    "version": 0,
+                    "scriptPubKey": {
+                        "asm": "OP_RETURN 30ae0a0000000000",
+                        "hex": "6a0830ae0a0000000000",
+                        "type": "nulldata",
+                    },
+                },
+                {
+                    "value": 14.33733109,
+                    "n": 2,
+                    "version": 0,
+                    "scriptPubKey": {
+                        "asm": "OP_DUP OP_HASH160 0c8b2e3f5f3ab3b1e3f4d6c2c0f0e2a1b1c3d4e5 OP_EQUALVERIFY OP_CHECKSIG",
+                        "hex": "76a9140c8b2e3f5f3ab3b1e3f4d6c2c0f0e2a1b1c3d4e588ac",
+                        "reqSigs": 1,
+                        "type": "pubkeyhash",
+                        "addresses": ["DsQxuVRvS4eaJ42dhQEsCXauMWjvopWgrVg"],
+                    },
+                },
+            ],
+            "blockhash": "0000000000000000201ba6e6a1b1e6bbd1c79abf2d9d1b8a4c4f1c86a0a6e6e1",
+            "blockheight": 700464,
+            "blockindex": 0,
+            "confirmations": 12,
+            "time": 1665331200,
+            "blocktime": 1665331200,
+        }))
+        .unwrap();
+
+        assert!(tx.hex.is_none());
+        assert_eq!(tx.block_height, 700464);
+        assert_eq!(tx.confirmations, 12);
+        assert!(tx.extra_fields.is_empty());
+
+        // Coinbase inputs spend no output and have no signature script.
+        let vin = &tx.vin[0];
+        assert_eq!(tx.vin.len(), 1);
+        assert!(vin.is_coin_base());
+        assert!(!vin.is_stake_base());
+        assert!(vin.tx_id.is_empty());
+        assert!(vin.script_sig.is_none());
+        assert_eq!(vin.amount_in.to_coin_string(), "15.93036788");
+        assert_eq!(vin.block_index, 4294967295);
+
+        assert_eq!(tx.vout.len(), 3);
+        assert_eq!(
+            tx.vout.iter().map(|vout| vout.n).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        let treasury = &tx.vout[0].script_pub_key;
+        assert_eq!(treasury.script_type, "scripthash");
+        assert_eq!(
+            treasury.addresses,
+            vec!["Dcur2mcGjmENx4DhNqDctW5wJCVyT3Qeqkx"]
+        );
+
+        // Null data outputs have no required signatures or addresses.
+        let height_commitment = &tx.vout[1].script_pub_key;
+        assert_eq!(height_commitment.script_type, "nulldata");
+        assert_eq!(height_commitment.req_sigs, 0);
+        assert!(height_commitment.addresses.is_empty());
+        assert_eq!(tx.vout[1].value.to_coin_string(), "0.0");
+
+        assert_eq!(tx.vout[2].script_pub_key.script_type, "pubkeyhash");
+        assert_eq!(tx.vout[2].value.to_coin_string(), "14.33733109");
+    }
+
+    #[test]
+    fn test_script_disasm() {
+        use crate::dcrjson::result_types::{ScriptPubKeyResult, ScriptSig};
+
+        // The disassembly sent by the server is kept.
+        let script_pub_key: ScriptPubKeyResult = serde_json::from_value(serde_json::json!({
+            "asm": "OP_RETURN 30ae0a0000000000",
+            "hex": "6a0830ae0a0000000000",
+        }))
+        .unwrap();
+        assert_eq!(script_pub_key.disasm(), "OP_RETURN 30ae0a0000000000");
+
+        // Scripts sent only as hex are disassembled locally.
+        let script_pub_key: ScriptPubKeyResult = serde_json::from_value(serde_json::json!({
+            "hex": "a914f5916158e3e2c4551c1796708db8367207ed13bb87",
+        }))
+        .unwrap();
+        assert_eq!(
+            script_pub_key.disasm(),
+            "OP_HASH160 f5916158e3e2c4551c1796708db8367207ed13bb OP_EQUAL"
+        );
+
+        let script_sig: ScriptSig =
+            serde_json::from_value(serde_json::json!({ "hex": "0251ab" })).unwrap();
+        assert_eq!(script_sig.disasm(), "51ab");
+
+        let script_sig: ScriptSig =
+            serde_json::from_value(serde_json::json!({ "hex": "zz" })).unwrap();
+        assert_eq!(script_sig.disasm(), "[error]");
+    }
+
+    #[test]
+    fn test_stake_difficulty_result() {
+        use crate::dcrjson::result_types::GetStakeDifficultyResult;
+        use crate::dcrutil::amount::Amount;
+
+        let result: GetStakeDifficultyResult = serde_json::from_value(serde_json::json!({
+            "current": 231.75,
+            "next": 229.00360341,
+        }))
+        .unwrap();
+
+        assert_eq!(result.current, Amount::from(23_175_000_000));
+        assert_eq!(result.next, Amount::from(22_900_360_341));
+
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"current":231.75,"next":229.00360341}"#
+        );
+    }
+
+    #[test]
+    fn test_estimate_mode() {
+        use crate::dcrjson::cmd_types::EstimateMode;
+
+        assert_eq!(
+            serde_json::to_value(EstimateMode::Conservative).unwrap(),
+            serde_json::json!("conservative")
+        );
+        assert_eq!(
+            serde_json::to_value(EstimateMode::Economical).unwrap(),
+            serde_json::json!("economical")
+        );
+        assert_eq!(
+            EstimateMode::Economical.param(),
+            Some(serde_json::json!("economical"))
+        );
+
+        // Unset is left out of the request for the server to pick its default mode.
+        assert_eq!(EstimateMode::default(), EstimateMode::Unset);
+        assert_eq!(EstimateMode::Unset.param(), None);
+    }
+
+    #[test]
+    fn test_mempool_fee_rates() {
+        use crate::dcrjson::result_types::{GetRawMempoolVerboseResult, MempoolFeeRates};
+        use crate::dcrutil::amount::Amount;
+        use std::collections::HashMap;
+
+        let mempool: HashMap<String, GetRawMempoolVerboseResult> =
+            serde_json::from_value(serde_json::json!({
+                // 0.0001 DCR/kB.
+                "aa": { "size": 1000, "fee": 0.0001 },
+                // 0.0005 DCR/kB, paying the highest fee rate despite a lower fee than "cc".
+                "bb": { "size": 200, "fee": 0.0001 },
+                // 0.0002 DCR/kB.
+                "cc": { "size": 2500, "fee": 0.0005 },
+                // Same fee rate as "cc", ordered after it by hash.
+                "dd": { "size": 500, "fee": 0.0001 },
+                "ee": { "size": 0, "fee": 0.0 },
+            }))
+            .unwrap();
+
+        assert_eq!(mempool["bb"].fee_rate(), Amount::from(50_000));
+        assert_eq!(mempool["ee"].fee_rate(), Amount::from(0));
+
+        let hashes = |transactions: Vec<(&String, &GetRawMempoolVerboseResult)>| {
+            transactions
+                .into_iter()
+                .map(|(hash, _)| hash.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            hashes(mempool.by_fee_rate()),
+            vec!["bb", "cc", "dd", "aa", "ee"]
+        );
+        assert_eq!(
+            hashes(mempool.above_fee_rate(Amount::from(20_000))),
+            vec!["bb", "cc", "dd"]
+        );
+        assert!(mempool.above_fee_rate(Amount::from(100_000)).is_empty());
+    }
+
+    #[test]
+    fn test_get_mining_info_result() {
+        use crate::dcrjson::result_types::GetMiningInfoResult;
+
+        let info: GetMiningInfoResult = serde_json::from_str(
+            r#"{
+                "blocks": 650012,
+                "currentblocksize": 11843,
+                "currentblocktx": 7,
+                "difficulty": 3125456512.6745195,
+                "stakedifficulty": 21475918347,
+                "errors": "",
+                "generate": false,
+                "genproclimit": -1,
+                "hashespersec": 0,
+                "networkhashps": 213460731289838780,
+                "pooledtx": 18,
+                "testnet": false
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(info.blocks, 650012);
+        assert_eq!(info.current_block_size, 11843);
+        assert_eq!(info.current_block_tx, 7);
+        assert_eq!(info.difficulty, 3125456512.6745195);
+        assert_eq!(info.stake_difficulty, 21475918347);
+        assert!(info.errors.is_empty());
+        assert!(!info.generate);
+        assert_eq!(info.gen_proc_limit, -1);
+        assert_eq!(info.hashes_per_sec, 0);
+        assert_eq!(info.network_hash_ps, 213460731289838780);
+        assert_eq!(info.pooled_tx, 18);
+        assert!(!info.test_net);
+    }
 }
@@ -0,0 +1,140 @@
+//! Unknown Fields.
+//! Detects fields returned by the server that a result type does not know about, such as fields
+//! added by a newer server version, and applies the configured policy to them.
+
+use {
+    super::RpcServerError,
+    log::warn,
+    serde::de::{self, Deserializer, Visitor},
+    std::collections::HashMap,
+};
+
+/// Fields returned by the server that the result type does not know about, keyed by name.
+pub type ExtraFields = HashMap<String, serde_json::Value>;
+
+/// How results with fields unknown to their result type are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeserializationPolicy {
+    /// Fails the call with an invalid response error listing the unknown fields.
+    Strict,
+    /// Keeps unknown fields in the result's extra fields. This is the default.
+    #[default]
+    Lenient,
+    /// Keeps unknown fields in the result's extra fields and logs a warning naming them.
+    Warn,
+}
+
+/// Result types keeping the fields returned by the server that they do not know about.
+pub trait WithExtraFields {
+    /// Returns the fields returned by the server that are not part of the result type.
+    fn extra_fields(&self) -> &ExtraFields;
+
+    /// Sets the fields returned by the server that are not part of the result type.
+    fn set_extra_fields(&mut self, extra_fields: ExtraFields);
+}
+
+/// Implements `WithExtraFields` for result types with an `extra_fields` field.
+macro_rules! impl_extra_fields {
+    ($($result_type: ty),*) => {
+        $(
+            impl $crate::dcrjson::WithExtraFields for $result_type {
+                fn extra_fields(&self) -> &$crate::dcrjson::ExtraFields {
+                    &self.extra_fields
+                }
+
+                fn set_extra_fields(&mut self, extra_fields: $crate::dcrjson::ExtraFields) {
+                    self.extra_fields = extra_fields;
+                }
+            }
+        )*
+    };
+}
+
+pub(super) use impl_extra_fields;
+
+/// Decodes a result object, applying policy to the fields it has that the result type does
+/// not know about.
+pub(crate) fn decode_result<T>(
+    result: serde_json::Value,
+    policy: DeserializationPolicy,
+) -> Result<T, RpcServerError>
+where
+    T: serde::de::DeserializeOwned + WithExtraFields,
+{
+    let known = struct_fields::<T>();
+
+    let extra_fields: ExtraFields = match result.as_object() {
+        Some(object) => object
+            .iter()
+            .filter(|(name, _)| !known.contains(&name.as_str()))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect(),
+
+        None => ExtraFields::new(),
+    };
+
+    if !extra_fields.is_empty() {
+        let mut names: Vec<&str> = extra_fields.keys().map(String::as_str).collect();
+        names.sort_unstable();
+
+        match policy {
+            DeserializationPolicy::Strict => {
+                return Err(RpcServerError::InvalidResponse(format!(
+                    "unknown fields {}",
+                    names.join(", ")
+                )));
+            }
+
+            DeserializationPolicy::Warn => {
+                warn!("Server returned unknown fields {}", names.join(", "))
+            }
+
+            DeserializationPolicy::Lenient => {}
+        }
+    }
+
+    let mut value: T = serde_json::from_value(result).map_err(RpcServerError::Marshaller)?;
+    value.set_extra_fields(extra_fields);
+
+    Ok(value)
+}
+
+/// Returns the serialized field names of a struct deriving `Deserialize`.
+fn struct_fields<'de, T: serde::Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields = None;
+    let _ = T::deserialize(FieldsDeserializer {
+        fields: &mut fields,
+    });
+
+    fields.unwrap_or_default()
+}
+
+/// Deserializer recording the field names a struct asks for, then failing.
+struct FieldsDeserializer<'a> {
+    fields: &'a mut Option<&'static [&'static str]>,
+}
+
+impl<'de, 'a> Deserializer<'de> for FieldsDeserializer<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.fields = Some(fields);
+
+        Err(de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
@@ -0,0 +1,177 @@
+//! Decred address encoding and decoding.
+
+use {
+    super::base58::{self, Base58Error, CHECKSUM_SIZE},
+    crate::chaincfg::{chainhash::hash_b, params::Network, params::NETWORKS},
+    ripemd::{Digest, Ripemd160},
+};
+
+/// Length of the hash160 payload of pay-to-pubkey-hash and pay-to-script-hash addresses.
+const HASH160_SIZE: usize = 20;
+
+/// Length of a compressed secp256k1 public key.
+const PUBKEY_COMPRESSED_SIZE: usize = 33;
+
+/// Length of an uncompressed secp256k1 public key.
+const PUBKEY_UNCOMPRESSED_SIZE: usize = 65;
+
+/// Length of the network and type prefix of an address.
+const PREFIX_SIZE: usize = 2;
+
+/// Address related errors.
+#[derive(PartialEq, Eq)]
+pub enum AddressError {
+    /// The address contains a character outside the base58 alphabet.
+    InvalidCharacter(char),
+
+    /// The decoded address is not of the expected length.
+    InvalidLength(usize),
+
+    /// The address checksum does not match its contents.
+    Checksum,
+
+    /// The address belongs to another network.
+    WrongNetwork(&'static str),
+
+    /// The address prefix is not known to any network.
+    UnknownPrefix([u8; 2]),
+
+    /// The public key is not a serialized secp256k1 public key.
+    InvalidPubKey,
+}
+
+impl std::fmt::Display for AddressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            AddressError::InvalidCharacter(c) => write!(f, "Invalid base58 character {:?}.", c),
+            AddressError::InvalidLength(len) => {
+                write!(f, "Invalid decoded address length {}.", len)
+            }
+            AddressError::Checksum => write!(f, "Address checksum mismatch."),
+            AddressError::WrongNetwork(name) => write!(f, "Address is for network {}.", name),
+            AddressError::UnknownPrefix(prefix) => {
+                write!(f, "Unknown address prefix {}.", hex::encode(prefix))
+            }
+            AddressError::InvalidPubKey => write!(f, "Invalid secp256k1 public key."),
+        }
+    }
+}
+
+impl From<Base58Error> for AddressError {
+    fn from(e: Base58Error) -> Self {
+        match e {
+            Base58Error::InvalidCharacter(c) => AddressError::InvalidCharacter(c),
+            Base58Error::InvalidLength(len) => AddressError::InvalidLength(len),
+            Base58Error::Checksum => AddressError::Checksum,
+        }
+    }
+}
+
+impl std::fmt::Debug for AddressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AddressError({})", self)
+    }
+}
+
+/// Script types of the addresses that can be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    /// Pay to the hash of a secp256k1 public key, signed with ECDSA.
+    PubKeyHashEcdsaSecp256k1,
+
+    /// Pay to the hash of an Ed25519 public key.
+    PubKeyHashEd25519,
+
+    /// Pay to the hash of a secp256k1 public key, signed with Schnorr.
+    PubKeyHashSchnorrSecp256k1,
+
+    /// Pay to the hash of a redeem script.
+    ScriptHash,
+}
+
+/// DecodedAddress is the script type and hash160 an address pays to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodedAddress {
+    /// Script type the address pays to.
+    pub address_type: AddressType,
+
+    /// RIPEMD-160 hash of the BLAKE-256 hash of the public key or script.
+    pub hash160: [u8; HASH160_SIZE],
+}
+
+/// Decodes and validates an address of the given network locally, without requesting the
+/// server's validateaddress.
+pub fn decode(addr: &str, params: &Network) -> Result<DecodedAddress, AddressError> {
+    let decoded = base58::decode_check(addr)?;
+
+    if decoded.len() != PREFIX_SIZE + HASH160_SIZE {
+        return Err(AddressError::InvalidLength(decoded.len() + CHECKSUM_SIZE));
+    }
+
+    let prefix = [decoded[0], decoded[1]];
+
+    let address_type = match address_type(&prefix, params) {
+        Some(address_type) => address_type,
+
+        None => {
+            return match NETWORKS
+                .iter()
+                .find(|network| address_type(&prefix, network).is_some())
+            {
+                Some(network) => Err(AddressError::WrongNetwork(network.name)),
+
+                None => Err(AddressError::UnknownPrefix(prefix)),
+            };
+        }
+    };
+
+    let mut hash160 = [0; HASH160_SIZE];
+    hash160.copy_from_slice(&decoded[PREFIX_SIZE..]);
+
+    Ok(DecodedAddress {
+        address_type,
+        hash160,
+    })
+}
+
+/// Returns the RIPEMD-160 hash of the BLAKE-256 hash of data, the hash paid to by
+/// pay-to-pubkey-hash and pay-to-script-hash addresses.
+pub fn hash160(data: &[u8]) -> [u8; HASH160_SIZE] {
+    Ripemd160::digest(hash_b(data)).into()
+}
+
+/// Returns the pay-to-pubkey-hash address of a serialized secp256k1 public key on the given
+/// network, signed with ECDSA, without requesting the server.
+///
+/// Compressed and uncompressed keys are hashed as given, so they pay to different addresses.
+pub fn pubkey_to_p2pkh(pubkey: &[u8], net: &Network) -> Result<String, AddressError> {
+    let valid = match pubkey.first() {
+        Some(0x02) | Some(0x03) => pubkey.len() == PUBKEY_COMPRESSED_SIZE,
+        Some(0x04) => pubkey.len() == PUBKEY_UNCOMPRESSED_SIZE,
+        _ => false,
+    };
+
+    if !valid {
+        return Err(AddressError::InvalidPubKey);
+    }
+
+    let mut payload = net.pubkey_hash_addr_id.to_vec();
+    payload.extend_from_slice(&hash160(pubkey));
+
+    Ok(base58::encode_check(&payload))
+}
+
+/// Returns the script type of addresses with the given prefix on a network.
+fn address_type(prefix: &[u8; 2], params: &Network) -> Option<AddressType> {
+    if *prefix == params.pubkey_hash_addr_id {
+        Some(AddressType::PubKeyHashEcdsaSecp256k1)
+    } else if *prefix == params.pkh_edwards_addr_id {
+        Some(AddressType::PubKeyHashEd25519)
+    } else if *prefix == params.pkh_schnorr_addr_id {
+        Some(AddressType::PubKeyHashSchnorrSecp256k1)
+    } else if *prefix == params.script_hash_addr_id {
+        Some(AddressType::ScriptHash)
+    } else {
+        None
+    }
+}
@@ -77,8 +77,91 @@ pub fn new(amount: f64) -> Result<Amount, AmountError> {
     Ok(round(amount * constants::ATOMS_PER_COIN))
 }
 
+/// Creates an Amount from the decimal representation of a coin value, such as
+/// `"21000000.00000001"` or `"1e-08"`, without going through a floating point value.
+/// Errors if the string is not a valid number, has more precision than an Atom or
+/// does not fit in an Amount.
+///
+/// Floating point values lose Atoms once an amount exceeds 2^53 Atoms, so this should be
+/// preferred when the exact decimal text is available.
+pub fn from_coin_str(amount: &str) -> Result<Amount, AmountError> {
+    let (mantissa, exponent) = match amount.find(['e', 'E']) {
+        Some(i) => match amount[i + 1..].parse::<i32>() {
+            Ok(exponent) => (&amount[..i], exponent),
+
+            Err(_) => return Err(AmountError::InvalidCoinAmount),
+        },
+
+        None => (amount, 0),
+    };
+
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => (true, mantissa),
+
+        None => (false, mantissa),
+    };
+
+    let (integer, fraction) = match mantissa.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+
+        None => (mantissa, ""),
+    };
+
+    if integer.is_empty()
+        || !integer.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(AmountError::InvalidCoinAmount);
+    }
+
+    let digits = format!("{}{}", integer, fraction);
+    let digits = digits.trim_start_matches('0');
+
+    // Power of ten the digits are multiplied by to count Atoms. Computed in i64 as the exponent
+    // and fraction length can each span the range of an i32.
+    let scale = i64::from(exponent)
+        - fraction.len() as i64
+        - i64::from(Denomination::AmountAtom.precision());
+
+    let atoms = if digits.is_empty() {
+        0
+    } else if scale < 0 {
+        let dropped = usize::try_from(-scale).unwrap_or(usize::MAX);
+        let kept = digits.len().saturating_sub(dropped);
+
+        // Only trailing zeros may be dropped below an Atom.
+        if !digits[kept..].bytes().all(|b| b == b'0') {
+            return Err(AmountError::InvalidCoinAmount);
+        }
+
+        match &digits[..kept] {
+            "" => 0,
+
+            kept => match kept.parse::<i64>() {
+                Ok(atoms) => atoms,
+
+                Err(_) => return Err(AmountError::InvalidCoinAmount),
+            },
+        }
+    } else {
+        match digits.parse::<i64>().ok().and_then(|atoms| {
+            u32::try_from(scale)
+                .ok()
+                .and_then(|scale| 10i64.checked_pow(scale))
+                .and_then(|multiplier| atoms.checked_mul(multiplier))
+        }) {
+            Some(atoms) => atoms,
+
+            None => return Err(AmountError::InvalidCoinAmount),
+        }
+    };
+
+    Ok(Amount(if negative { -atoms } else { atoms }))
+}
+
 /// Amount represents the base coin monetary unit (colloquially referred
 /// to as an `Atom').  A single Amount is equal to 1e-8 of a coin.
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Amount(i64);
 
 impl Amount {
@@ -108,26 +191,82 @@ impl Amount {
     pub fn mul_f64(&self, f: f64) -> Amount {
         round(self.0 as f64 * f)
     }
+
+    /// Returns the exact decimal representation of the amount in coins, keeping at least one
+    /// fractional digit, such as `"0.0"` or `"21000000.00000001"`.
+    pub fn to_coin_string(&self) -> String {
+        let atoms = self.0.unsigned_abs();
+        let atoms_per_coin = constants::ATOMS_PER_COIN as u64;
+
+        let fraction = format!("{:08}", atoms % atoms_per_coin);
+        let fraction = match fraction.trim_end_matches('0') {
+            "" => "0",
+
+            fraction => fraction,
+        };
+
+        let sign = if self.0 < 0 { "-" } else { "" };
+
+        format!("{}{}.{}", sign, atoms / atoms_per_coin, fraction)
+    }
+}
+
+/// Creates an Amount from a quantity of Atoms.
+impl From<i64> for Amount {
+    fn from(atoms: i64) -> Self {
+        Amount(atoms)
+    }
+}
+
+/// Returns the quantity of Atoms of an Amount.
+impl From<Amount> for i64 {
+    fn from(amount: Amount) -> Self {
+        amount.0
+    }
+}
+
+/// Deserializes an Amount from a number of coins.
+///
+/// JSON numbers are read from their exact decimal text. Other formats pass numbers as integers
+/// or floats, and a float is read from its shortest decimal representation, which has already
+/// lost Atoms for amounts above 2^53 Atoms.
+impl<'de> serde::Deserialize<'de> for Amount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let number = serde_json::Number::deserialize(deserializer)?;
+
+        from_coin_str(&number.to_string()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes an Amount as a number of coins written with its exact decimal text.
+///
+/// The number is passed on as a `serde_json::Number`, which formats other than JSON receive as
+/// serde_json's arbitrary precision representation rather than a plain number.
+impl serde::Serialize for Amount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error;
+
+        let number: serde_json::Number = self.to_coin_string().parse().map_err(S::Error::custom)?;
+
+        number.serialize(serializer)
+    }
 }
 
-impl ToString for Amount {
-    fn to_string(&self) -> String {
-        self.format(Denomination::AmountCoin)
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.format(Denomination::AmountCoin))
     }
 }
 
 impl std::cmp::PartialOrd for Amount {
     fn partial_cmp(&self, other: &Amount) -> Option<Ordering> {
-        let amount = self.to_coin();
-        let other_amount = other.to_coin();
-
-        if amount > other_amount {
-            Some(Ordering::Greater)
-        } else if amount < other_amount {
-            Some(Ordering::Less)
-        } else {
-            Some(Ordering::Equal)
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -0,0 +1,92 @@
+//! Base58check encoding.
+//! Encodes and decodes the base58 strings used by addresses and private keys with the checksum
+//! of Decred's base58check.
+
+use crate::chaincfg::chainhash::hash_b;
+
+/// Length of the checksum appended by base58check.
+pub(super) const CHECKSUM_SIZE: usize = 4;
+
+/// Base58 related errors.
+#[derive(PartialEq, Eq)]
+pub enum Base58Error {
+    /// The string contains a character outside the base58 alphabet.
+    InvalidCharacter(char),
+
+    /// The decoded data is too short to hold a checksum.
+    InvalidLength(usize),
+
+    /// The checksum does not match the decoded data.
+    Checksum,
+}
+
+impl std::fmt::Display for Base58Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Base58Error::InvalidCharacter(c) => write!(f, "Invalid base58 character {:?}.", c),
+            Base58Error::InvalidLength(len) => {
+                write!(f, "Decoded length {} is shorter than the checksum.", len)
+            }
+            Base58Error::Checksum => write!(f, "Base58 checksum mismatch."),
+        }
+    }
+}
+
+impl std::fmt::Debug for Base58Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Base58Error({})", self)
+    }
+}
+
+/// encode_check returns the base58check encoding of data, the base58 encoding of data followed
+/// by the first four bytes of its double BLAKE-256 hash.
+pub fn encode_check(data: &[u8]) -> String {
+    let mut checked = Vec::with_capacity(data.len() + CHECKSUM_SIZE);
+    checked.extend_from_slice(data);
+    checked.extend_from_slice(&checksum(data));
+
+    bs58::encode(checked).into_string()
+}
+
+/// decode_check decodes a base58check string and verifies its trailing checksum, the first four
+/// bytes of the double BLAKE-256 hash of the data before it. The data is returned without the
+/// checksum.
+///
+/// Leading zero bytes are part of the checksummed data, so a string with leading '1's added or
+/// removed fails the checksum rather than decoding to the same data.
+pub fn decode_check(s: &str) -> Result<Vec<u8>, Base58Error> {
+    let mut decoded = bs58::decode(s).into_vec().map_err(|e| match e {
+        bs58::decode::Error::InvalidCharacter { character, .. } => {
+            Base58Error::InvalidCharacter(character)
+        }
+
+        bs58::decode::Error::NonAsciiCharacter { index } => {
+            Base58Error::InvalidCharacter(s[index..].chars().next().unwrap_or_default())
+        }
+
+        // Decoding into a vector sized for the input never runs out of space.
+        e => unreachable!("unexpected base58 decoding error: {}", e),
+    })?;
+
+    if decoded.len() < CHECKSUM_SIZE {
+        return Err(Base58Error::InvalidLength(decoded.len()));
+    }
+
+    let data_len = decoded.len() - CHECKSUM_SIZE;
+
+    if checksum(&decoded[..data_len]) != decoded[data_len..] {
+        return Err(Base58Error::Checksum);
+    }
+
+    decoded.truncate(data_len);
+
+    Ok(decoded)
+}
+
+/// Returns the first four bytes of the double BLAKE-256 hash of data.
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let mut checksum = [0; CHECKSUM_SIZE];
+    checksum.copy_from_slice(&hash_b(&hash_b(data))[..CHECKSUM_SIZE]);
+
+    checksum
+}
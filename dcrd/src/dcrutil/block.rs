@@ -0,0 +1,408 @@
+//! Serialized blocks.
+//! Splits serialized blocks into their header and transactions, hashes transactions and
+//! verifies blocks against the hash and merkle roots committed to by their header.
+
+use {
+    super::{
+        merkle::{calc_combined_tx_tree_merkle_root, calc_merkle_root},
+        pow::BLOCK_HEADER_SIZE,
+    },
+    crate::chaincfg::chainhash::{constants::HASH_SIZE, hash_b, hash_h, Hash},
+};
+
+/// Offset of the merkle root of the regular transactions in a serialized block header.
+const MERKLE_ROOT_OFFSET: usize = 36;
+
+/// Offset of the stake root in a serialized block header.
+const STAKE_ROOT_OFFSET: usize = 68;
+
+/// Transaction serialization types, encoded in the upper 16 bits of the transaction version.
+pub(super) const TX_SERIALIZE_FULL: u16 = 0;
+pub(super) const TX_SERIALIZE_NO_WITNESS: u16 = 1;
+pub(super) const TX_SERIALIZE_ONLY_WITNESS: u16 = 2;
+
+/// Serialized size of a transaction input's previous outpoint and sequence.
+const TX_IN_PREFIX_SIZE: usize = HASH_SIZE + 4 + 1 + 4;
+
+/// Serialized size of a transaction input's witness, excluding its signature script.
+const TX_IN_WITNESS_SIZE: usize = 8 + 4 + 4;
+
+/// Block related errors.
+#[derive(PartialEq, Eq)]
+pub enum BlockError {
+    /// The serialized block or transaction ends before its last field.
+    UnexpectedEof,
+
+    /// The serialized block has bytes following its last stake transaction.
+    TrailingBytes(usize),
+
+    /// The transaction serialization type is unknown or not valid in this context.
+    InvalidSerializationType(u16),
+
+    /// The block hash differs from the requested hash.
+    HashMismatch {
+        /// Requested block hash.
+        expected: Hash,
+        /// Hash of the block header.
+        found: Hash,
+    },
+
+    /// The merkle root of the transactions differs from the header's merkle root.
+    MerkleRootMismatch,
+
+    /// The merkle root of the stake transactions differs from the header's stake root.
+    StakeRootMismatch,
+
+    /// The transaction has a different number of input witnesses than inputs.
+    WitnessCountMismatch {
+        /// Number of inputs in the transaction prefix.
+        inputs: usize,
+        /// Number of input witnesses.
+        witnesses: usize,
+    },
+}
+
+impl std::fmt::Display for BlockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockError::UnexpectedEof => write!(f, "Unexpected end of serialized data."),
+            BlockError::TrailingBytes(len) => {
+                write!(f, "Serialized block has {} trailing bytes.", len)
+            }
+            BlockError::InvalidSerializationType(ser_type) => {
+                write!(f, "Invalid transaction serialization type {}.", ser_type)
+            }
+            BlockError::HashMismatch { expected, found } => write!(
+                f,
+                "Block hash {} does not match requested hash {}.",
+                found.string().unwrap_or_default(),
+                expected.string().unwrap_or_default()
+            ),
+            BlockError::MerkleRootMismatch => {
+                write!(f, "Transactions do not match the header's merkle root.")
+            }
+            BlockError::StakeRootMismatch => {
+                write!(
+                    f,
+                    "Stake transactions do not match the header's stake root."
+                )
+            }
+            BlockError::WitnessCountMismatch { inputs, witnesses } => write!(
+                f,
+                "Transaction has {} input witnesses for {} inputs.",
+                witnesses, inputs
+            ),
+        }
+    }
+}
+
+impl std::fmt::Debug for BlockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BlockError({})", self)
+    }
+}
+
+/// Block is a serialized block split into its header and transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    /// Serialized block header.
+    pub header: [u8; BLOCK_HEADER_SIZE],
+    /// Serialized regular transactions, starting with the coinbase.
+    pub transactions: Vec<Vec<u8>>,
+    /// Serialized stake transactions.
+    pub stake_transactions: Vec<Vec<u8>>,
+}
+
+impl Block {
+    /// Splits a serialized block, as returned by `getblock` with verbose unset, into its header
+    /// and fully serialized transactions.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, BlockError> {
+        let mut reader = Reader { bytes, pos: 0 };
+
+        let mut header = [0; BLOCK_HEADER_SIZE];
+        header.copy_from_slice(reader.take(BLOCK_HEADER_SIZE)?);
+
+        let transactions = reader.transactions()?;
+        let stake_transactions = reader.transactions()?;
+
+        if reader.pos != bytes.len() {
+            return Err(BlockError::TrailingBytes(bytes.len() - reader.pos));
+        }
+
+        Ok(Block {
+            header,
+            transactions,
+            stake_transactions,
+        })
+    }
+
+    /// Returns the block hash, the BLAKE-256 hash of the serialized header.
+    pub fn hash(&self) -> Hash {
+        hash_h(&self.header)
+    }
+
+    /// verify checks the block hashes to hash and its transactions match the merkle roots
+    /// committed to by its header.
+    ///
+    /// Headers committing to the combined merkle root of the full transaction hashes, as
+    /// required once header commitments (DCP0005) are active, have their stake root replaced by
+    /// a commitment root of data not in the block, which is not checked. Otherwise the merkle
+    /// and stake roots must match the transaction hashes of the regular and stake transactions.
+    pub fn verify(&self, hash: &Hash) -> Result<(), BlockError> {
+        let found = self.hash();
+
+        if found != *hash {
+            return Err(BlockError::HashMismatch {
+                expected: hash.clone(),
+                found,
+            });
+        }
+
+        let merkle_root = self.header_hash(MERKLE_ROOT_OFFSET);
+
+        let regular = tx_hashes(&self.transactions, tx_hash_full)?;
+        let stake = tx_hashes(&self.stake_transactions, tx_hash_full)?;
+
+        if merkle_root == calc_combined_tx_tree_merkle_root(&regular, &stake) {
+            return Ok(());
+        }
+
+        let regular = tx_hashes(&self.transactions, tx_hash)?;
+        if merkle_root != calc_merkle_root(&regular) {
+            return Err(BlockError::MerkleRootMismatch);
+        }
+
+        let stake = tx_hashes(&self.stake_transactions, tx_hash)?;
+        if self.header_hash(STAKE_ROOT_OFFSET) != calc_merkle_root(&stake) {
+            return Err(BlockError::StakeRootMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hash in the header at offset.
+    fn header_hash(&self, offset: usize) -> Hash {
+        let mut hash = [0; HASH_SIZE];
+        hash.copy_from_slice(&self.header[offset..offset + HASH_SIZE]);
+
+        Hash::from(hash)
+    }
+}
+
+/// tx_hash returns the hash of a serialized transaction, the BLAKE-256 hash of its prefix
+/// serialization. This is the hash transactions are identified by and doesn't commit to
+/// signature scripts.
+pub fn tx_hash(tx: &[u8]) -> Result<Hash, BlockError> {
+    let layout = TxLayout::parse(tx)?;
+
+    if layout.ser_type == TX_SERIALIZE_ONLY_WITNESS {
+        return Err(BlockError::InvalidSerializationType(layout.ser_type));
+    }
+
+    Ok(hash_h(&layout.reencode(
+        tx,
+        TX_SERIALIZE_NO_WITNESS,
+        4..layout.prefix_end,
+    )))
+}
+
+/// tx_hash_full returns the full hash of a fully serialized transaction, the BLAKE-256 hash of
+/// the concatenation of its prefix and witness hashes. Unlike `tx_hash`, it commits to
+/// signature scripts.
+pub fn tx_hash_full(tx: &[u8]) -> Result<Hash, BlockError> {
+    let layout = TxLayout::parse(tx)?;
+
+    if layout.ser_type != TX_SERIALIZE_FULL {
+        return Err(BlockError::InvalidSerializationType(layout.ser_type));
+    }
+
+    let mut hashes = [0; HASH_SIZE * 2];
+    hashes[..HASH_SIZE].copy_from_slice(&hash_b(&layout.reencode(
+        tx,
+        TX_SERIALIZE_NO_WITNESS,
+        4..layout.prefix_end,
+    )));
+    hashes[HASH_SIZE..].copy_from_slice(&hash_b(&layout.reencode(
+        tx,
+        TX_SERIALIZE_ONLY_WITNESS,
+        layout.prefix_end..layout.end,
+    )));
+
+    Ok(hash_h(&hashes))
+}
+
+/// Hashes each transaction with hasher.
+fn tx_hashes(
+    transactions: &[Vec<u8>],
+    hasher: fn(&[u8]) -> Result<Hash, BlockError>,
+) -> Result<Vec<Hash>, BlockError> {
+    transactions.iter().map(|tx| hasher(tx)).collect()
+}
+
+/// Reads serialized blocks and transactions.
+pub(super) struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, pos: 0 }
+    }
+
+    /// Returns the number of bytes read.
+    pub(super) fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub(super) fn take(&mut self, len: usize) -> Result<&'a [u8], BlockError> {
+        let end = self.pos.checked_add(len).ok_or(BlockError::UnexpectedEof)?;
+        let bytes = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(BlockError::UnexpectedEof)?;
+        self.pos = end;
+
+        Ok(bytes)
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, BlockError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, BlockError> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.take(2)?);
+
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, BlockError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64, BlockError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Reads a variable length integer.
+    pub(super) fn var_int(&mut self) -> Result<usize, BlockError> {
+        let len = match self.take(1)?[0] {
+            0xfd => 2,
+            0xfe => 4,
+            0xff => 8,
+            value => return Ok(usize::from(value)),
+        };
+
+        let mut bytes = [0; 8];
+        bytes[..len].copy_from_slice(self.take(len)?);
+
+        usize::try_from(u64::from_le_bytes(bytes)).map_err(|_| BlockError::UnexpectedEof)
+    }
+
+    /// Reads a variable length byte array.
+    pub(super) fn var_bytes(&mut self) -> Result<&'a [u8], BlockError> {
+        let len = self.var_int()?;
+        self.take(len)
+    }
+
+    /// Skips a variable length byte array.
+    fn skip_var_bytes(&mut self) -> Result<(), BlockError> {
+        self.var_bytes().map(|_| ())
+    }
+
+    /// Reads a transaction tree, returning each fully serialized transaction.
+    fn transactions(&mut self) -> Result<Vec<Vec<u8>>, BlockError> {
+        let count = self.var_int()?;
+
+        // Every transaction takes at least a byte, bounding the preallocation.
+        let mut transactions = Vec::with_capacity(count.min(self.bytes.len() - self.pos));
+
+        for _ in 0..count {
+            let layout = TxLayout::parse(&self.bytes[self.pos..])?;
+
+            if layout.ser_type != TX_SERIALIZE_FULL {
+                return Err(BlockError::InvalidSerializationType(layout.ser_type));
+            }
+
+            transactions.push(self.take(layout.end)?.to_vec());
+        }
+
+        Ok(transactions)
+    }
+}
+
+/// Where the parts of a serialized transaction end.
+struct TxLayout {
+    version: u16,
+    ser_type: u16,
+    /// End of the prefix, which is also the start of the witness.
+    prefix_end: usize,
+    /// End of the transaction.
+    end: usize,
+}
+
+impl TxLayout {
+    /// Parses the layout of the transaction at the start of bytes.
+    fn parse(bytes: &[u8]) -> Result<Self, BlockError> {
+        let mut reader = Reader { bytes, pos: 0 };
+
+        let version = reader.u32()?;
+        let ser_type = (version >> 16) as u16;
+
+        if ser_type > TX_SERIALIZE_ONLY_WITNESS {
+            return Err(BlockError::InvalidSerializationType(ser_type));
+        }
+
+        if ser_type != TX_SERIALIZE_ONLY_WITNESS {
+            let inputs = reader.var_int()?;
+            for _ in 0..inputs {
+                reader.take(TX_IN_PREFIX_SIZE)?;
+            }
+
+            let outputs = reader.var_int()?;
+            for _ in 0..outputs {
+                // Value and script version.
+                reader.take(8 + 2)?;
+                reader.skip_var_bytes()?;
+            }
+
+            // Lock time and expiry.
+            reader.take(4 + 4)?;
+        }
+
+        let prefix_end = reader.pos;
+
+        if ser_type != TX_SERIALIZE_NO_WITNESS {
+            let witnesses = reader.var_int()?;
+            for _ in 0..witnesses {
+                reader.take(TX_IN_WITNESS_SIZE)?;
+                reader.skip_var_bytes()?;
+            }
+        }
+
+        Ok(TxLayout {
+            version: version as u16,
+            ser_type,
+            prefix_end,
+            end: reader.pos,
+        })
+    }
+
+    /// Returns the part of the transaction in range serialized as ser_type.
+    fn reencode(&self, tx: &[u8], ser_type: u16, range: std::ops::Range<usize>) -> Vec<u8> {
+        let version = u32::from(self.version) | u32::from(ser_type) << 16;
+
+        let mut bytes = Vec::with_capacity(4 + range.len());
+        bytes.extend_from_slice(&version.to_le_bytes());
+        bytes.extend_from_slice(&tx[range]);
+
+        bytes
+    }
+}
@@ -0,0 +1,51 @@
+//! Block Locator
+//! Builds block locators used to request headers and blocks from a server.
+
+use {crate::chaincfg::chainhash::Hash, std::collections::BTreeMap};
+
+/// Number of consecutive blocks back from the tip included before the distance between
+/// included blocks starts doubling.
+const CONSECUTIVE_LOCATOR_BLOCKS: usize = 11;
+
+/// build_block_locator returns a block locator for a chain known as the given heights and
+/// block hashes, such as the locator passed to `getheaders`.
+///
+/// The locator starts at the highest known block and walks back towards the lowest known
+/// block, which is always included. The first blocks are consecutive and the distance
+/// between included blocks then doubles at each step, so a locator of a long chain stays
+/// short while still finding a recent fork point quickly. Heights visited by the walk that
+/// are not known are skipped. Later entries for a repeated height replace earlier ones.
+pub fn build_block_locator(known_heights: &[(i64, Hash)]) -> Vec<Hash> {
+    let known: BTreeMap<i64, &Hash> = known_heights
+        .iter()
+        .map(|(height, hash)| (*height, hash))
+        .collect();
+
+    let (lowest, tip) = match (known.keys().next(), known.keys().next_back()) {
+        (Some(lowest), Some(tip)) => (*lowest, *tip),
+
+        _ => return Vec::new(),
+    };
+
+    let mut locator = Vec::new();
+    let mut height = tip;
+    let mut step = 1;
+    let mut visited = 0;
+
+    loop {
+        if let Some(hash) = known.get(&height) {
+            locator.push((*hash).clone());
+        }
+
+        if height == lowest {
+            return locator;
+        }
+
+        height = height.saturating_sub(step).max(lowest);
+
+        visited += 1;
+        if visited >= CONSECUTIVE_LOCATOR_BLOCKS {
+            step = step.saturating_mul(2);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Merkle trees.
+//! Computes the merkle roots committed to by block headers.
+
+use crate::chaincfg::chainhash::{constants::HASH_SIZE, hash_b, hash_h, Hash};
+
+/// calc_merkle_root returns the root of the merkle tree of leaves, mirroring dcrd's
+/// `standalone.CalcMerkleRoot`. Each node is the BLAKE-256 hash of the concatenation of its
+/// children, and a level with an odd number of nodes pairs its last node with itself. The
+/// root of no leaves is the zero hash.
+pub fn calc_merkle_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return Hash::from([0; HASH_SIZE]);
+    }
+
+    let mut level: Vec<[u8; HASH_SIZE]> = leaves.iter().map(|leaf| *leaf.bytes()).collect();
+
+    while level.len() > 1 {
+        if !level.len().is_multiple_of(2) {
+            level.push(level[level.len() - 1]);
+        }
+
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut node = [0; HASH_SIZE * 2];
+                node[..HASH_SIZE].copy_from_slice(&pair[0]);
+                node[HASH_SIZE..].copy_from_slice(&pair[1]);
+
+                hash_b(&node)
+            })
+            .collect();
+    }
+
+    Hash::from(level[0])
+}
+
+/// calc_combined_tx_tree_merkle_root returns the merkle root committed to by block headers
+/// once header commitments (DCP0005) are active, mirroring dcrd's
+/// `standalone.CalcCombinedTxTreeMerkleRoot`. It is the hash of the merkle roots of the full
+/// hashes of the regular and stake transactions.
+pub fn calc_combined_tx_tree_merkle_root(regular: &[Hash], stake: &[Hash]) -> Hash {
+    let mut roots = [0; HASH_SIZE * 2];
+    roots[..HASH_SIZE].copy_from_slice(calc_merkle_root(regular).bytes());
+    roots[HASH_SIZE..].copy_from_slice(calc_merkle_root(stake).bytes());
+
+    hash_h(&roots)
+}
@@ -1,6 +1,21 @@
-///! DCR Utilities
+//! DCR Utilities
+pub mod address;
 pub mod amount;
 mod app_data;
+pub mod base58;
+mod block;
+mod block_locator;
+mod merkle;
+mod pow;
+pub mod script;
 mod test;
+pub mod tx;
 
 pub use app_data::get_app_data_dir;
+pub use block::{tx_hash, tx_hash_full, Block, BlockError};
+pub use block_locator::build_block_locator;
+pub use merkle::{calc_combined_tx_tree_merkle_root, calc_merkle_root};
+pub use pow::{
+    check_proof_of_work, compact_to_target, difficulty_from_bits, difficulty_ratio,
+    target_to_compact, PowError,
+};
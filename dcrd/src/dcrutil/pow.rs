@@ -0,0 +1,175 @@
+//! Proof of work verification.
+//! Checks block header hashes against the target encoded by their compact difficulty bits and
+//! converts between compact bits, targets and difficulties.
+
+use crate::chaincfg::chainhash::{constants::HASH_SIZE, hash_b};
+
+/// Length of a serialized block header.
+pub(super) const BLOCK_HEADER_SIZE: usize = 180;
+
+/// Offset of the compact difficulty bits in a serialized block header.
+const BITS_OFFSET: usize = 116;
+
+/// Compact bits of the mainnet proof of work limit, the target of difficulty 1.
+const MAINNET_POW_LIMIT_BITS: u32 = 0x1d00ffff;
+
+/// Proof of work related errors.
+#[derive(PartialEq, Eq)]
+pub enum PowError {
+    /// The serialized block header is not of the expected length.
+    InvalidHeaderLength(usize),
+
+    /// The difficulty bits encode a target that is zero or negative.
+    TargetNotPositive(u32),
+
+    /// The difficulty bits encode a target larger than 256 bits.
+    TargetOverflow(u32),
+}
+
+impl std::fmt::Display for PowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            PowError::InvalidHeaderLength(len) => write!(
+                f,
+                "Block header is {} bytes, expected {} bytes.",
+                len, BLOCK_HEADER_SIZE
+            ),
+            PowError::TargetNotPositive(bits) => {
+                write!(f, "Target of bits {:08x} is not positive.", bits)
+            }
+            PowError::TargetOverflow(bits) => {
+                write!(f, "Target of bits {:08x} exceeds 256 bits.", bits)
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for PowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PowError({})", self)
+    }
+}
+
+/// check_proof_of_work returns whether the BLAKE-256 hash of the serialized block header,
+/// read as a little-endian number, is at most the target encoded by the header's difficulty
+/// bits. The header itself is otherwise not validated, so this doesn't check the bits are the
+/// difficulty required by the chain, only that the header meets the difficulty it claims.
+pub fn check_proof_of_work(header: &[u8]) -> Result<bool, PowError> {
+    if header.len() != BLOCK_HEADER_SIZE {
+        return Err(PowError::InvalidHeaderLength(header.len()));
+    }
+
+    let mut bits = [0; 4];
+    bits.copy_from_slice(&header[BITS_OFFSET..BITS_OFFSET + 4]);
+    let bits = u32::from_le_bytes(bits);
+
+    let target = decode_compact(bits)?;
+
+    if target == [0; HASH_SIZE] {
+        return Err(PowError::TargetNotPositive(bits));
+    }
+
+    // Hashes are little-endian numbers, targets are big-endian.
+    let mut hash = hash_b(header);
+    hash.reverse();
+
+    Ok(hash <= target)
+}
+
+/// compact_to_target returns the big-endian 256-bit target encoded by compact difficulty bits,
+/// mirroring dcrd's `CompactToBig`. Bits encoding a negative target or a target larger than
+/// 256 bits aren't valid targets and return a zero target, which no hash meets.
+pub fn compact_to_target(bits: u32) -> [u8; HASH_SIZE] {
+    decode_compact(bits).unwrap_or([0; HASH_SIZE])
+}
+
+/// target_to_compact returns the compact difficulty bits of a big-endian 256-bit target,
+/// mirroring dcrd's `BigToCompact`. Only the three most significant bytes of the target are
+/// kept, so decoding the bits returns the target rounded down to that precision.
+pub fn target_to_compact(target: &[u8; HASH_SIZE]) -> u32 {
+    let start = match target.iter().position(|byte| *byte != 0) {
+        Some(start) => start,
+
+        None => return 0,
+    };
+
+    let mut size = HASH_SIZE - start;
+
+    let mut mantissa = [0; 4];
+    let len = size.min(3);
+    mantissa[4 - len..].copy_from_slice(&target[start..start + len]);
+    let mut mantissa = u32::from_be_bytes(mantissa) << (8 * (3 - len));
+
+    // The sign bit is set, so the mantissa is shifted into the next byte.
+    if mantissa & 0x0080_0000 != 0 {
+        mantissa >>= 8;
+        size += 1;
+    }
+
+    (size as u32) << 24 | mantissa
+}
+
+/// difficulty_from_bits returns the difficulty of compact difficulty bits, the ratio of the
+/// mainnet proof of work limit, encoded as bits 1d00ffff, to the bits' target. This matches the
+/// difficulty reported by dcrd for mainnet blocks. Bits that don't encode a valid target have
+/// a difficulty of zero.
+pub fn difficulty_from_bits(bits: u32) -> f64 {
+    difficulty_ratio(bits, MAINNET_POW_LIMIT_BITS)
+}
+
+/// difficulty_ratio returns the ratio of the target encoded by pow_limit_bits, the minimum
+/// difficulty of a network, to the target encoded by bits, mirroring dcrd's
+/// `getDifficultyRatio`. The limit is compared in its compact form, as blocks encode it, rather
+/// than the exact proof of work limit. Bits that don't encode a valid target have a ratio of
+/// zero.
+pub fn difficulty_ratio(bits: u32, pow_limit_bits: u32) -> f64 {
+    let target = target_to_f64(&compact_to_target(bits));
+
+    if target == 0.0 {
+        return 0.0;
+    }
+
+    target_to_f64(&compact_to_target(pow_limit_bits)) / target
+}
+
+/// Converts a big-endian 256-bit target to the nearest floating point number.
+fn target_to_f64(target: &[u8; HASH_SIZE]) -> f64 {
+    target
+        .iter()
+        .fold(0.0, |value, byte| value * 256.0 + f64::from(*byte))
+}
+
+/// Decodes compact difficulty bits to a big-endian 256-bit target. The compact form stores
+/// the number of bytes of the target in the high byte and its most significant bytes in the
+/// low 23 bits, with bit 23 as the sign.
+fn decode_compact(bits: u32) -> Result<[u8; HASH_SIZE], PowError> {
+    let exponent = (bits >> 24) as usize;
+    let mantissa = bits & 0x007f_ffff;
+
+    if mantissa != 0 && bits & 0x0080_0000 != 0 {
+        return Err(PowError::TargetNotPositive(bits));
+    }
+
+    let mut target = [0; HASH_SIZE];
+
+    for (i, byte) in mantissa.to_le_bytes()[..3].iter().enumerate() {
+        // Bytes shifted below the least significant byte are dropped.
+        let power = match (exponent + i).checked_sub(3) {
+            Some(power) => power,
+
+            None => continue,
+        };
+
+        if power >= HASH_SIZE {
+            if *byte != 0 {
+                return Err(PowError::TargetOverflow(bits));
+            }
+
+            continue;
+        }
+
+        target[HASH_SIZE - 1 - power] = *byte;
+    }
+
+    Ok(target)
+}
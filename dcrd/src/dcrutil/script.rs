@@ -0,0 +1,191 @@
+//! Scripts.
+//! Disassembles transaction scripts into the one-line form dcrd reports as `asm`, so scripts
+//! can be inspected from their serialized bytes.
+
+/// Opcode pushing the next byte's worth of bytes.
+const OP_PUSHDATA1: u8 = 0x4c;
+
+/// Opcode pushing the next two bytes' worth of bytes.
+const OP_PUSHDATA2: u8 = 0x4d;
+
+/// Opcode pushing the next four bytes' worth of bytes.
+const OP_PUSHDATA4: u8 = 0x4e;
+
+/// disasm returns the one-line disassembly of a version 0 script, as dcrd's `DisasmString`.
+///
+/// Opcodes are written by name, except small integer opcodes which are written as their value,
+/// such as `0`, `-1` or `16`, and data pushes which are written as the hex of the pushed data.
+/// A script ending in the middle of a data push ends with `[error]`.
+pub fn disasm(script: &[u8]) -> String {
+    let mut tokens = Vec::new();
+    let mut rest = script;
+
+    while let Some((&op, tail)) = rest.split_first() {
+        // Size of the data length following the opcode.
+        let prefix = match op {
+            0x01..=0x4b => 0,
+            OP_PUSHDATA1 => 1,
+            OP_PUSHDATA2 => 2,
+            OP_PUSHDATA4 => 4,
+            _ => {
+                tokens.push(opcode_name(op));
+                rest = tail;
+                continue;
+            }
+        };
+
+        let length = match prefix {
+            0 => Some(usize::from(op)),
+            _ => read_length(tail, prefix),
+        };
+
+        match length {
+            Some(length) if tail.len() >= prefix + length => {
+                tokens.push(hex::encode(&tail[prefix..prefix + length]));
+                rest = &tail[prefix + length..];
+            }
+
+            _ => {
+                tokens.push("[error]".to_string());
+                break;
+            }
+        }
+    }
+
+    tokens.join(" ")
+}
+
+/// Returns the little-endian length of size bytes at the start of data.
+fn read_length(data: &[u8], size: usize) -> Option<usize> {
+    let bytes = data.get(..size)?;
+
+    Some(
+        bytes
+            .iter()
+            .rev()
+            .fold(0, |length, byte| length << 8 | usize::from(*byte)),
+    )
+}
+
+/// Returns the name of an opcode in its one-line form, where small integers are written as their
+/// value.
+fn opcode_name(op: u8) -> String {
+    let name = match op {
+        0x00 => "0",
+        0x01..=0x4b => return format!("OP_DATA_{}", op),
+        OP_PUSHDATA1 => "OP_PUSHDATA1",
+        OP_PUSHDATA2 => "OP_PUSHDATA2",
+        OP_PUSHDATA4 => "OP_PUSHDATA4",
+        0x4f => "-1",
+        0x51..=0x60 => return (op - 0x50).to_string(),
+        0xc4..=0xf9 | 0xfc => return format!("OP_UNKNOWN{}", op),
+
+        0x50 => "OP_RESERVED",
+        0x61 => "OP_NOP",
+        0x62 => "OP_VER",
+        0x63 => "OP_IF",
+        0x64 => "OP_NOTIF",
+        0x65 => "OP_VERIF",
+        0x66 => "OP_VERNOTIF",
+        0x67 => "OP_ELSE",
+        0x68 => "OP_ENDIF",
+        0x69 => "OP_VERIFY",
+        0x6a => "OP_RETURN",
+        0x6b => "OP_TOALTSTACK",
+        0x6c => "OP_FROMALTSTACK",
+        0x6d => "OP_2DROP",
+        0x6e => "OP_2DUP",
+        0x6f => "OP_3DUP",
+        0x70 => "OP_2OVER",
+        0x71 => "OP_2ROT",
+        0x72 => "OP_2SWAP",
+        0x73 => "OP_IFDUP",
+        0x74 => "OP_DEPTH",
+        0x75 => "OP_DROP",
+        0x76 => "OP_DUP",
+        0x77 => "OP_NIP",
+        0x78 => "OP_OVER",
+        0x79 => "OP_PICK",
+        0x7a => "OP_ROLL",
+        0x7b => "OP_ROT",
+        0x7c => "OP_SWAP",
+        0x7d => "OP_TUCK",
+        0x7e => "OP_CAT",
+        0x7f => "OP_SUBSTR",
+        0x80 => "OP_LEFT",
+        0x81 => "OP_RIGHT",
+        0x82 => "OP_SIZE",
+        0x83 => "OP_INVERT",
+        0x84 => "OP_AND",
+        0x85 => "OP_OR",
+        0x86 => "OP_XOR",
+        0x87 => "OP_EQUAL",
+        0x88 => "OP_EQUALVERIFY",
+        0x89 => "OP_ROTR",
+        0x8a => "OP_ROTL",
+        0x8b => "OP_1ADD",
+        0x8c => "OP_1SUB",
+        0x8d => "OP_2MUL",
+        0x8e => "OP_2DIV",
+        0x8f => "OP_NEGATE",
+        0x90 => "OP_ABS",
+        0x91 => "OP_NOT",
+        0x92 => "OP_0NOTEQUAL",
+        0x93 => "OP_ADD",
+        0x94 => "OP_SUB",
+        0x95 => "OP_MUL",
+        0x96 => "OP_DIV",
+        0x97 => "OP_MOD",
+        0x98 => "OP_LSHIFT",
+        0x99 => "OP_RSHIFT",
+        0x9a => "OP_BOOLAND",
+        0x9b => "OP_BOOLOR",
+        0x9c => "OP_NUMEQUAL",
+        0x9d => "OP_NUMEQUALVERIFY",
+        0x9e => "OP_NUMNOTEQUAL",
+        0x9f => "OP_LESSTHAN",
+        0xa0 => "OP_GREATERTHAN",
+        0xa1 => "OP_LESSTHANOREQUAL",
+        0xa2 => "OP_GREATERTHANOREQUAL",
+        0xa3 => "OP_MIN",
+        0xa4 => "OP_MAX",
+        0xa5 => "OP_WITHIN",
+        0xa6 => "OP_RIPEMD160",
+        0xa7 => "OP_SHA1",
+        0xa8 => "OP_BLAKE256",
+        0xa9 => "OP_HASH160",
+        0xaa => "OP_HASH256",
+        0xab => "OP_CODESEPARATOR",
+        0xac => "OP_CHECKSIG",
+        0xad => "OP_CHECKSIGVERIFY",
+        0xae => "OP_CHECKMULTISIG",
+        0xaf => "OP_CHECKMULTISIGVERIFY",
+        0xb0 => "OP_NOP1",
+        0xb1 => "OP_CHECKLOCKTIMEVERIFY",
+        0xb2 => "OP_CHECKSEQUENCEVERIFY",
+        0xb3 => "OP_NOP4",
+        0xb4 => "OP_NOP5",
+        0xb5 => "OP_NOP6",
+        0xb6 => "OP_NOP7",
+        0xb7 => "OP_NOP8",
+        0xb8 => "OP_NOP9",
+        0xb9 => "OP_NOP10",
+        0xba => "OP_SSTX",
+        0xbb => "OP_SSGEN",
+        0xbc => "OP_SSRTX",
+        0xbd => "OP_SSTXCHANGE",
+        0xbe => "OP_CHECKSIGALT",
+        0xbf => "OP_CHECKSIGALTVERIFY",
+        0xc0 => "OP_SHA256",
+        0xc1 => "OP_TADD",
+        0xc2 => "OP_TSPEND",
+        0xc3 => "OP_TGEN",
+        0xfa => "OP_SMALLINTEGER",
+        0xfb => "OP_PUBKEYS",
+        0xfd => "OP_PUBKEYHASH",
+        0xfe => "OP_PUBKEY",
+        0xff => "OP_INVALIDOPCODE",
+    };
+
+    name.to_string()
+}
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(unexpected_cfgs)]
 mod app_data_dir {
     #[allow(unused_imports)]
     use std::{env, path::PathBuf};
@@ -98,3 +99,819 @@ mod app_data_dir {
         )
     }
 }
+
+#[cfg(test)]
+mod amount_precision {
+    use crate::dcrutil::amount::{self, Amount, Denomination};
+
+    #[test]
+    fn test_amount_exact_parsing() {
+        let json = "76476099.53243772";
+        let atoms = 7647609953243772.0;
+
+        // Scaling the nearest f64 to Atoms is off by two Atoms.
+        let lossy = amount::new(serde_json::from_str::<f64>(json).unwrap()).unwrap();
+        assert_ne!(lossy.to_unit(Denomination::AmountAtom), atoms);
+
+        let exact: Amount = serde_json::from_str(json).unwrap();
+        assert_eq!(exact.to_unit(Denomination::AmountAtom), atoms);
+
+        // Results are unmarshalled from a JSON value which must retain the decimal text.
+        let value: serde_json::Value = serde_json::from_str(&format!("[{}]", json)).unwrap();
+        let exact: Amount = serde_json::from_value(value[0].clone()).unwrap();
+        assert_eq!(exact.to_unit(Denomination::AmountAtom), atoms);
+
+        let tests = [
+            ("1e-08", Some(1.0)),
+            ("1E-8", Some(1.0)),
+            ("-0.5", Some(-50000000.0)),
+            ("21000000.00000001", Some(2100000000000001.0)),
+            ("1.10000000000", Some(110000000.0)),
+            ("0", Some(0.0)),
+            ("0.000000001", None),
+            ("1e300", None),
+            ("0e2147483647", Some(0.0)),
+            ("1e2147483647", None),
+            ("1e-2147483648", None),
+            ("-9.9e2147483647", None),
+            (".5", None),
+            ("abc", None),
+        ];
+
+        for (coins, expected) in tests.iter() {
+            let amount = amount::from_coin_str(coins)
+                .ok()
+                .map(|amount| amount.to_unit(Denomination::AmountAtom));
+
+            assert_eq!(amount, *expected, "failed parsing {}", coins);
+        }
+    }
+
+    #[test]
+    fn test_amount_exact_serializing() {
+        let tests = [
+            ("76476099.53243772", "76476099.53243772"),
+            ("1e-08", "0.00000001"),
+            ("-0.5", "-0.5"),
+            ("21000000", "21000000.0"),
+            ("0", "0.0"),
+        ];
+
+        for (coins, expected) in tests.iter() {
+            let amount = amount::from_coin_str(coins).unwrap();
+            assert_eq!(amount.to_coin_string(), *expected);
+
+            let marshalled = serde_json::to_string(&amount).unwrap();
+            assert_eq!(marshalled, *expected);
+            assert_eq!(serde_json::from_str::<Amount>(&marshalled).unwrap(), amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod block_locator {
+    use crate::{chaincfg::chainhash::Hash, dcrutil::build_block_locator};
+
+    fn block_hash(height: i64) -> Hash {
+        let mut hash = [0; 32];
+        hash[..8].copy_from_slice(&height.to_le_bytes());
+
+        Hash::from(hash)
+    }
+
+    fn block_height(hash: &Hash) -> i64 {
+        let mut height = [0; 8];
+        height.copy_from_slice(&hash.bytes()[..8]);
+
+        i64::from_le_bytes(height)
+    }
+
+    #[test]
+    fn test_build_block_locator() {
+        let chain: Vec<(i64, Hash)> = (0..=100_000).map(|h| (h, block_hash(h))).collect();
+
+        let heights: Vec<i64> = build_block_locator(&chain)
+            .iter()
+            .map(block_height)
+            .collect();
+
+        // Eleven consecutive blocks back from the tip, then steps of 2, 4, 8, ... ending at
+        // genesis.
+        let mut expected: Vec<i64> = (0..=11).map(|i| 100_000 - i).collect();
+        let mut step = 2;
+        while expected.last().unwrap() - step > 0 {
+            expected.push(expected.last().unwrap() - step);
+            step *= 2;
+        }
+        expected.push(0);
+
+        assert_eq!(heights, expected);
+
+        // Unknown heights are skipped while the lowest known block is always included.
+        let sparse: Vec<(i64, Hash)> = [500, 499, 497, 300]
+            .iter()
+            .map(|h| (*h, block_hash(*h)))
+            .collect();
+        let heights: Vec<i64> = build_block_locator(&sparse)
+            .iter()
+            .map(block_height)
+            .collect();
+        assert_eq!(heights, vec![500, 499, 497, 300]);
+
+        assert!(build_block_locator(&[]).is_empty());
+        assert_eq!(build_block_locator(&chain[..1]).len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod address {
+    use crate::{
+        chaincfg::params::{MAINNET, REGNET, SIMNET, TESTNET3},
+        dcrutil::address::{decode, AddressError, AddressType},
+    };
+
+    #[test]
+    fn test_decode() {
+        let tests = [
+            (
+                "DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJu",
+                MAINNET,
+                AddressType::PubKeyHashEcdsaSecp256k1,
+                "2789d58cfa0957d206f025c2af056fc8a77cebb0",
+            ),
+            (
+                "DcuQKx8BES9wU7C6Q5VmLBjw436r27hayjS",
+                MAINNET,
+                AddressType::ScriptHash,
+                "f0b4e85100aee1a996f22915eb3c3f764d53779a",
+            ),
+            (
+                "DebkfTKk5j8963LDAtAChRSqRmZXJn3e99R",
+                MAINNET,
+                AddressType::PubKeyHashEd25519,
+                "2789d58cfa0957d206f025c2af056fc8a77cebb0",
+            ),
+            (
+                "DSXcZv4oSRiEoWL2a9aD8sgfptRo1YEXNKj",
+                MAINNET,
+                AddressType::PubKeyHashSchnorrSecp256k1,
+                "2789d58cfa0957d206f025c2af056fc8a77cebb0",
+            ),
+            (
+                "Tso2MVTUeVrjHTBFedFhiyM7yVTbieqp91h",
+                TESTNET3,
+                AddressType::PubKeyHashEcdsaSecp256k1,
+                "f15da1cb8d1bcb162c6ab446c95757a6e791c916",
+            ),
+            (
+                "TccWLgcquqvwrfBocq5mcK5kBiyw8MvyvCi",
+                TESTNET3,
+                AddressType::ScriptHash,
+                "36c1ca10a8a6a4b5d4204ac970853979903aa284",
+            ),
+            (
+                "SsXxLAHsCzyM4oTxHXo49gRcuP2bDuihSQV",
+                SIMNET,
+                AddressType::PubKeyHashEcdsaSecp256k1,
+                "2789d58cfa0957d206f025c2af056fc8a77cebb0",
+            ),
+            (
+                "SceT1jGqYjJCeYRo5dGjMjnYo6N4Ka2U4Pb",
+                SIMNET,
+                AddressType::ScriptHash,
+                "2789d58cfa0957d206f025c2af056fc8a77cebb0",
+            ),
+            (
+                "RsBwsPYeS3pA2DcjiyxkwNF9q7tESnV4pgp",
+                REGNET,
+                AddressType::PubKeyHashEcdsaSecp256k1,
+                "2789d58cfa0957d206f025c2af056fc8a77cebb0",
+            ),
+        ];
+
+        for (addr, network, address_type, hash160) in tests {
+            let decoded = decode(addr, &network).unwrap();
+
+            assert_eq!(decoded.address_type, address_type, "{}", addr);
+            assert_eq!(hex::encode(decoded.hash160), hash160, "{}", addr);
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert_eq!(
+            decode("DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJu", &TESTNET3),
+            Err(AddressError::WrongNetwork("mainnet"))
+        );
+        assert_eq!(
+            decode("Tso2MVTUeVrjHTBFedFhiyM7yVTbieqp91h", &MAINNET),
+            Err(AddressError::WrongNetwork("testnet3"))
+        );
+
+        // Last character changed.
+        assert_eq!(
+            decode("DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJv", &MAINNET),
+            Err(AddressError::Checksum)
+        );
+
+        // '0' is not part of the base58 alphabet.
+        assert_eq!(
+            decode("DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJ0", &MAINNET),
+            Err(AddressError::InvalidCharacter('0'))
+        );
+
+        assert!(matches!(
+            decode("DsUZxxoHJSty8DCfwfartwTYbuhmVct7t", &MAINNET),
+            Err(AddressError::Checksum) | Err(AddressError::InvalidLength(_))
+        ));
+        assert!(matches!(
+            decode("", &MAINNET),
+            Err(AddressError::InvalidLength(0))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod base58 {
+    use crate::dcrutil::base58::{decode_check, encode_check, Base58Error};
+
+    #[test]
+    fn test_encode_decode_check() {
+        // Mainnet secp256k1 pay-to-pubkey-hash prefix followed by the hash160.
+        let data = hex::decode("073f2789d58cfa0957d206f025c2af056fc8a77cebb0").unwrap();
+        let addr = "DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJu";
+
+        assert_eq!(encode_check(&data), addr);
+        assert_eq!(decode_check(addr).unwrap(), data);
+
+        // Leading zero bytes are encoded as leading '1's and covered by the checksum.
+        for data in [&[][..], &[0], &[0, 0, 1], &[0, 0xff, 0]] {
+            let encoded = encode_check(data);
+            assert_eq!(decode_check(&encoded).unwrap(), data, "{}", encoded);
+        }
+
+        let encoded = encode_check(&[0, 0, 1]);
+        assert!(encoded.starts_with("11"));
+        assert_eq!(decode_check(&encoded[1..]), Err(Base58Error::Checksum));
+        assert_eq!(
+            decode_check(&format!("1{}", encoded)),
+            Err(Base58Error::Checksum)
+        );
+
+        // Last character changed.
+        assert_eq!(
+            decode_check("DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJv"),
+            Err(Base58Error::Checksum)
+        );
+
+        // 'l' is not part of the base58 alphabet.
+        assert_eq!(
+            decode_check("DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJl"),
+            Err(Base58Error::InvalidCharacter('l'))
+        );
+        assert_eq!(
+            decode_check("DsUZxxoHJSty8DCfwfartwTYbuhmVct7tJé"),
+            Err(Base58Error::InvalidCharacter('é'))
+        );
+
+        assert_eq!(decode_check(""), Err(Base58Error::InvalidLength(0)));
+        assert_eq!(decode_check("111"), Err(Base58Error::InvalidLength(3)));
+    }
+}
+
+#[cfg(test)]
+mod pow {
+    use crate::{
+        chaincfg::{chainhash::hash_h, params},
+        dcrutil::{check_proof_of_work, PowError},
+    };
+
+    /// Serialized simnet genesis block header, with difficulty bits 207fffff.
+    const SIMNET_GENESIS_HEADER: &str = concat!(
+        "01000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0dc101dfc3c6a2eb10ca0c5374e10d28feb53f7eabcc850511ceadb99174aa66",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000",
+        "ffff7f20",
+        "0000000000000000",
+        "00000000000000004506865300000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000",
+    );
+
+    /// Serialized mainnet genesis block header, with difficulty bits 1b01ffff.
+    const MAINNET_GENESIS_HEADER: &str = concat!(
+        "01000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0dc101dfc3c6a2eb10ca0c5374e10d28feb53f7eabcc850511ceadb99174aa66",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000",
+        "ffff011b",
+        "00c2eb0b00000000",
+        "0000000000000000a0d7b85600000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000",
+    );
+
+    #[test]
+    fn test_check_proof_of_work() {
+        let simnet = hex::decode(SIMNET_GENESIS_HEADER).unwrap();
+        assert_eq!(hash_h(&simnet), params::SIMNET.genesis_hash);
+        assert_eq!(check_proof_of_work(&simnet), Ok(true));
+
+        // Tampering with the header changes its hash, which no longer meets the harder
+        // mainnet target the tampered bits claim.
+        let mut tampered = simnet.clone();
+        tampered[116..120].copy_from_slice(&0x1b01ffff_u32.to_le_bytes());
+        assert_eq!(check_proof_of_work(&tampered), Ok(false));
+
+        // The mainnet genesis block was never mined, so its hash doesn't meet its own target.
+        let mainnet = hex::decode(MAINNET_GENESIS_HEADER).unwrap();
+        assert_eq!(hash_h(&mainnet), params::MAINNET.genesis_hash);
+        assert_eq!(check_proof_of_work(&mainnet), Ok(false));
+
+        assert_eq!(
+            check_proof_of_work(&simnet[..179]),
+            Err(PowError::InvalidHeaderLength(179))
+        );
+
+        let invalid_bits = [
+            (0x1b81ffff, PowError::TargetNotPositive(0x1b81ffff)),
+            (0x1b000000, PowError::TargetNotPositive(0x1b000000)),
+            (0x01003456, PowError::TargetNotPositive(0x01003456)),
+            (0x2101ffff, PowError::TargetOverflow(0x2101ffff)),
+        ];
+
+        for (bits, err) in invalid_bits {
+            let mut header = simnet.clone();
+            header[116..120].copy_from_slice(&u32::to_le_bytes(bits));
+            assert_eq!(check_proof_of_work(&header), Err(err), "bits {:08x}", bits);
+        }
+    }
+}
+
+#[cfg(test)]
+mod compact_bits {
+    use crate::dcrutil::{compact_to_target, difficulty_from_bits, target_to_compact};
+
+    /// Returns a big-endian target from its hex encoding, left padded to 256 bits.
+    fn target(hex_target: &str) -> [u8; 32] {
+        let mut target = [0; 32];
+        let bytes = hex::decode(format!("{:0>64}", hex_target)).unwrap();
+        target.copy_from_slice(&bytes);
+        target
+    }
+
+    #[test]
+    fn test_compact_bits() {
+        // Bits of the mainnet, testnet3 and simnet proof of work limits and of the mainnet
+        // genesis block.
+        let vectors = [
+            (
+                0x1d00ffff,
+                "00000000ffff0000000000000000000000000000000000000000000000000000",
+                1.0,
+            ),
+            (
+                0x1e00ffff,
+                "000000ffff000000000000000000000000000000000000000000000000000000",
+                1.0 / 256.0,
+            ),
+            (
+                0x207fffff,
+                "7fffff0000000000000000000000000000000000000000000000000000000000",
+                f64::from(0xffff_u32) / f64::from(0x7fffff_u32) / 2.0f64.powi(24),
+            ),
+            (
+                0x1b01ffff,
+                "000000000001ffff000000000000000000000000000000000000000000000000",
+                f64::from(0xffff_u32 << 16) / f64::from(0x1ffff_u32),
+            ),
+        ];
+
+        for (bits, hex_target, difficulty) in vectors {
+            assert_eq!(
+                compact_to_target(bits),
+                target(hex_target),
+                "bits {:08x}",
+                bits
+            );
+            assert_eq!(target_to_compact(&target(hex_target)), bits);
+            assert!(
+                (difficulty_from_bits(bits) - difficulty).abs() <= difficulty * 1e-12,
+                "bits {:08x}, difficulty {}",
+                bits,
+                difficulty_from_bits(bits)
+            );
+        }
+
+        // Targets with more than three significant bytes are rounded down.
+        assert_eq!(target_to_compact(&target("1234567890")), 0x05123456);
+        assert_eq!(compact_to_target(0x05123456), target("1234560000"));
+
+        // A set sign bit moves the mantissa into the next byte.
+        assert_eq!(target_to_compact(&target("80")), 0x02008000);
+        assert_eq!(compact_to_target(0x02008000), target("80"));
+
+        // Small exponents shift the mantissa right.
+        assert_eq!(compact_to_target(0x01123456), target("12"));
+        assert_eq!(target_to_compact(&target("12")), 0x01120000);
+
+        assert_eq!(target_to_compact(&[0; 32]), 0);
+
+        // Negative and overflowing bits aren't valid targets.
+        for bits in [0x1d80ffff, 0x2101ffff, 0x00000000] {
+            assert_eq!(compact_to_target(bits), [0; 32], "bits {:08x}", bits);
+            assert_eq!(difficulty_from_bits(bits), 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod block {
+    use crate::{
+        chaincfg::chainhash::{hash_b, hash_h, Hash},
+        dcrutil::{
+            calc_combined_tx_tree_merkle_root, calc_merkle_root, tx_hash, tx_hash_full, Block,
+            BlockError,
+        },
+    };
+
+    /// Prefix of a version 1 coinbase transaction, following its version.
+    const COINBASE_PREFIX: &str = concat!(
+        "01",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "ffffffff",
+        "00",
+        "ffffffff",
+        "01",
+        "00e1f50500000000",
+        "0000",
+        "19",
+        "76a914000102030405060708090a0b0c0d0e0f1011121388ac",
+        "00000000",
+        "00000000",
+    );
+
+    /// Witness of the coinbase transaction, following its version.
+    const COINBASE_WITNESS: &str = concat!(
+        "01",
+        "00e1f50500000000",
+        "00000000",
+        "ffffffff",
+        "02",
+        "0000",
+    );
+
+    fn coinbase() -> Vec<u8> {
+        hex::decode(format!("01000000{}{}", COINBASE_PREFIX, COINBASE_WITNESS)).unwrap()
+    }
+
+    fn serialize(header: &[u8], transactions: &[Vec<u8>]) -> Vec<u8> {
+        let mut block = header.to_vec();
+        block.push(transactions.len() as u8);
+        for tx in transactions {
+            block.extend_from_slice(tx);
+        }
+        // No stake transactions.
+        block.push(0);
+        block
+    }
+
+    #[test]
+    fn test_tx_hash() {
+        let tx = coinbase();
+
+        let prefix = hash_b(&hex::decode(format!("01000100{}", COINBASE_PREFIX)).unwrap());
+        let witness = hash_b(&hex::decode(format!("01000200{}", COINBASE_WITNESS)).unwrap());
+
+        assert_eq!(tx_hash(&tx), Ok(Hash::from(prefix)));
+        assert_eq!(tx_hash_full(&tx), Ok(hash_h(&[prefix, witness].concat())));
+
+        assert_eq!(tx_hash(&tx[..tx.len() - 1]), Err(BlockError::UnexpectedEof));
+    }
+
+    #[test]
+    fn test_calc_merkle_root() {
+        let leaves: Vec<Hash> = (1..=3u8).map(|n| Hash::from([n; 32])).collect();
+        let node = |a: &[u8; 32], b: &[u8; 32]| hash_b(&[*a, *b].concat());
+
+        assert_eq!(calc_merkle_root(&[]), Hash::from([0; 32]));
+        assert_eq!(calc_merkle_root(&leaves[..1]), leaves[0]);
+
+        // The odd leaf is paired with itself.
+        let left = node(leaves[0].bytes(), leaves[1].bytes());
+        let right = node(leaves[2].bytes(), leaves[2].bytes());
+        assert_eq!(calc_merkle_root(&leaves), Hash::from(node(&left, &right)));
+
+        assert_eq!(
+            calc_combined_tx_tree_merkle_root(&leaves[..1], &[]),
+            Hash::from(node(leaves[0].bytes(), &[0; 32]))
+        );
+    }
+
+    #[test]
+    fn test_verify_block() {
+        let tx = coinbase();
+
+        // Header committing to the transaction hash as merkle root and an empty stake tree.
+        let mut header = [0; 180];
+        header[36..68].copy_from_slice(tx_hash(&tx).unwrap().bytes());
+        let hash = hash_h(&header);
+
+        let block = Block::deserialize(&serialize(&header, std::slice::from_ref(&tx))).unwrap();
+        assert_eq!(block.transactions, vec![tx.clone()]);
+        assert!(block.stake_transactions.is_empty());
+        assert_eq!(block.verify(&hash), Ok(()));
+
+        assert!(matches!(
+            block.verify(&Hash::from([0; 32])),
+            Err(BlockError::HashMismatch { .. })
+        ));
+
+        // Signature scripts aren't committed to by the legacy merkle root, but the output is.
+        let mut tampered = tx;
+        tampered[60] ^= 1;
+        let block = Block::deserialize(&serialize(&header, &[tampered])).unwrap();
+        assert_eq!(block.verify(&hash), Err(BlockError::MerkleRootMismatch));
+
+        let mut bytes = serialize(&header, &[coinbase()]);
+        bytes.push(0);
+        assert_eq!(
+            Block::deserialize(&bytes),
+            Err(BlockError::TrailingBytes(1))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tx {
+    use crate::{
+        chaincfg::chainhash::Hash,
+        dcrutil::{
+            tx::{MsgTx, OutPoint, TxIn, TxOut, TX_TREE_REGULAR},
+            tx_hash, tx_hash_full, BlockError,
+        },
+    };
+
+    /// Transaction spending one output to a pay-to-pubkey-hash output and a change output.
+    const TX: &str = concat!(
+        // Version 1, full serialization.
+        "01000000",
+        // One input spending output 2 of the regular tree transaction, final sequence.
+        "01",
+        "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90",
+        "02000000",
+        "00",
+        "ffffffff",
+        // Two outputs of 1 and 0.49 DCR paying to hash160s.
+        "02",
+        "00e1f50500000000",
+        "0000",
+        "19",
+        "76a9142789d58cfa0957d206f025c2af056fc8a77cebb088ac",
+        "40aeeb0200000000",
+        "0000",
+        "19",
+        "76a914f15da1cb8d1bcb162c6ab446c95757a6e791c91688ac",
+        // Lock time and expiry.
+        "00000000",
+        "40e20900",
+        // One witness with 1.5 DCR in from block 650000, index 3.
+        "01",
+        "80d1f00800000000",
+        "10eb0900",
+        "03000000",
+        "06",
+        "0102030405ff",
+    );
+
+    fn expected() -> MsgTx {
+        let mut hash = [0; 32];
+        hash.copy_from_slice(
+            &hex::decode("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90")
+                .unwrap(),
+        );
+
+        let mut tx_in = TxIn::new(
+            OutPoint {
+                hash: Hash::from(hash),
+                index: 2,
+                tree: TX_TREE_REGULAR,
+            },
+            150_000_000,
+            vec![0x01, 0x02, 0x03, 0x04, 0x05, 0xff],
+        );
+        tx_in.block_height = 650_000;
+        tx_in.block_index = 3;
+
+        MsgTx {
+            tx_in: vec![tx_in],
+            tx_out: vec![
+                TxOut {
+                    value: 100_000_000,
+                    version: 0,
+                    pk_script: hex::decode("76a9142789d58cfa0957d206f025c2af056fc8a77cebb088ac")
+                        .unwrap(),
+                },
+                TxOut {
+                    value: 49_000_000,
+                    version: 0,
+                    pk_script: hex::decode("76a914f15da1cb8d1bcb162c6ab446c95757a6e791c91688ac")
+                        .unwrap(),
+                },
+            ],
+            expiry: 647_744,
+            ..MsgTx::new()
+        }
+    }
+
+    #[test]
+    fn test_serialize() {
+        let bytes = hex::decode(TX).unwrap();
+        let tx = expected();
+
+        assert_eq!(hex::encode(tx.serialize()), TX);
+        assert_eq!(MsgTx::deserialize(&bytes).unwrap(), tx);
+
+        // The hashes match those of the serialized transaction.
+        assert_eq!(tx.tx_hash(), tx_hash(&bytes).unwrap());
+        assert_eq!(tx.tx_hash_full(), tx_hash_full(&bytes).unwrap());
+
+        // Signing changes the full hash only.
+        let mut signed = tx.clone();
+        signed.tx_in[0].signature_script = vec![0x51];
+        assert_eq!(signed.tx_hash(), tx.tx_hash());
+        assert_ne!(signed.tx_hash_full(), tx.tx_hash_full());
+
+        // Scripts of 253 bytes or more have a three byte length.
+        let mut large = tx.clone();
+        large.tx_out[0].pk_script = vec![0x6a; 300];
+        let serialized = large.serialize();
+        assert_eq!(serialized.len(), bytes.len() + 300 - 25 + 2);
+        assert_eq!(MsgTx::deserialize(&serialized).unwrap(), large);
+
+        // Version, no inputs or outputs, lock time, expiry and no witnesses.
+        assert_eq!(
+            hex::encode(MsgTx::new().serialize()),
+            concat!("01000000", "00", "00", "00000000", "00000000", "00")
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        let bytes = hex::decode(TX).unwrap();
+
+        // A prefix serialization leaves the witness fields unset.
+        let witness_start = TX.find("0180d1f0").unwrap() / 2;
+        let mut prefix = bytes[..witness_start].to_vec();
+        prefix[2] = 1;
+
+        let tx = MsgTx::deserialize(&prefix).unwrap();
+        assert_eq!(tx.tx_hash(), expected().tx_hash());
+        assert!(tx.tx_in[0].signature_script.is_empty());
+
+        let mut only_witness = bytes.clone();
+        only_witness[2] = 2;
+        assert_eq!(
+            MsgTx::deserialize(&only_witness),
+            Err(BlockError::InvalidSerializationType(2))
+        );
+
+        assert_eq!(
+            MsgTx::deserialize(&bytes[..bytes.len() - 1]),
+            Err(BlockError::UnexpectedEof)
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            MsgTx::deserialize(&trailing),
+            Err(BlockError::TrailingBytes(1))
+        );
+
+        let mut missing_witness = bytes[..witness_start].to_vec();
+        missing_witness.push(0);
+        assert_eq!(
+            MsgTx::deserialize(&missing_witness),
+            Err(BlockError::WitnessCountMismatch {
+                inputs: 1,
+                witnesses: 0
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod script {
+    use crate::dcrutil::script::disasm;
+
+    const HASH160: &str = "f5916158e3e2c4551c1796708db8367207ed13bb";
+
+    #[test]
+    fn test_disasm_p2pkh() {
+        let script = hex::decode(format!("76a914{}88ac", HASH160)).unwrap();
+
+        assert_eq!(
+            disasm(&script),
+            format!("OP_DUP OP_HASH160 {} OP_EQUALVERIFY OP_CHECKSIG", HASH160)
+        );
+
+        // Stake tagged outputs are prefixed with their stake opcode.
+        let vote_reward = [&[0xbb], script.as_slice()].concat();
+        assert_eq!(
+            disasm(&vote_reward),
+            format!(
+                "OP_SSGEN OP_DUP OP_HASH160 {} OP_EQUALVERIFY OP_CHECKSIG",
+                HASH160
+            )
+        );
+    }
+
+    #[test]
+    fn test_disasm_p2sh() {
+        let script = hex::decode(format!("a914{}87", HASH160)).unwrap();
+
+        assert_eq!(disasm(&script), format!("OP_HASH160 {} OP_EQUAL", HASH160));
+    }
+
+    #[test]
+    fn test_disasm_pushes() {
+        // Small integers are written as their value.
+        assert_eq!(
+            disasm(&[0x00, 0x4f, 0x51, 0x60, 0x50]),
+            "0 -1 1 16 OP_RESERVED"
+        );
+
+        // Data pushes of every width are written as their data.
+        assert_eq!(
+            disasm(&[0x6a, 0x4c, 0x02, 0xab, 0xcd, 0x4d, 0x01, 0x00, 0xef]),
+            "OP_RETURN abcd ef"
+        );
+        assert_eq!(disasm(&[0x4e, 0x01, 0x00, 0x00, 0x00, 0x01]), "01");
+        assert_eq!(disasm(&[0x4c, 0x00]), "");
+
+        assert_eq!(
+            disasm(&[0xc4, 0xf9, 0xfc, 0xff]),
+            "OP_UNKNOWN196 OP_UNKNOWN249 OP_UNKNOWN252 OP_INVALIDOPCODE"
+        );
+        assert_eq!(disasm(&[]), "");
+
+        // Truncated pushes end the disassembly.
+        assert_eq!(disasm(&[0x76, 0x14, 0x01]), "OP_DUP [error]");
+        assert_eq!(disasm(&[0x4d, 0x01]), "[error]");
+    }
+}
+
+#[cfg(test)]
+mod pubkey_address {
+    use crate::{
+        chaincfg::params::{MAINNET, TESTNET3},
+        dcrutil::address::{decode, hash160, pubkey_to_p2pkh, AddressError, AddressType},
+    };
+
+    #[test]
+    fn test_pubkey_to_p2pkh() {
+        let tests = [
+            (
+                "028f53838b7639563f27c94845549a41e5146bcd52e7fef0ea6da143a02b0fe2ed",
+                MAINNET,
+                "DsT4FDqBKYG1Xr8aGrT1rKP3kiv6TZ5K5th",
+            ),
+            (
+                "03e925aafc1edd44e7c7f1ea4fb7d265dc672f204c3d0c81930389c10b81fb75de",
+                MAINNET,
+                "DsfiE2y23CGwKNxSGjbfPGeEW4xw1tamZdc",
+            ),
+            (
+                "026a40c403e74670c4de7656a09caa2353d4b383a9ce66eef51e1220eacf4be06e",
+                TESTNET3,
+                "Tso9sQD3ALqRsmEkAm7KvPrkGbeG2Vun7Kv",
+            ),
+            (
+                "030844ee70d8384d5250e9bb3a6a73d4b5bec770e8b31d6a0ae9fb739009d91af5",
+                TESTNET3,
+                "TsWZ1EzypJfMwBKAEDYKuyHRGctqGAxMje2",
+            ),
+        ];
+
+        for (pubkey, net, expected) in tests {
+            let pubkey = hex::decode(pubkey).unwrap();
+
+            let addr = pubkey_to_p2pkh(&pubkey, &net).unwrap();
+            assert_eq!(addr, expected);
+
+            let decoded = decode(&addr, &net).unwrap();
+            assert_eq!(decoded.address_type, AddressType::PubKeyHashEcdsaSecp256k1);
+            assert_eq!(decoded.hash160, hash160(&pubkey));
+        }
+
+        for pubkey in [&[][..], &[0x02; 32], &[0x05; 33], &[0x04; 33]] {
+            assert_eq!(
+                pubkey_to_p2pkh(pubkey, &MAINNET),
+                Err(AddressError::InvalidPubKey)
+            );
+        }
+    }
+}
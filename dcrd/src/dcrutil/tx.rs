@@ -0,0 +1,295 @@
+//! Transactions.
+//! Builds, serializes and hashes transactions in Decred's wire format, so transactions can be
+//! constructed and signed locally before being sent with `send_raw_transaction`.
+
+use {
+    super::block::{
+        BlockError, Reader, TX_SERIALIZE_FULL, TX_SERIALIZE_NO_WITNESS, TX_SERIALIZE_ONLY_WITNESS,
+    },
+    crate::chaincfg::chainhash::{constants::HASH_SIZE, hash_b, hash_h, Hash},
+};
+
+/// Current transaction version.
+pub const TX_VERSION: u16 = 1;
+
+/// Transaction tree of regular transactions.
+pub const TX_TREE_REGULAR: i8 = 0;
+
+/// Transaction tree of votes, tickets and revocations.
+pub const TX_TREE_STAKE: i8 = 1;
+
+/// Sequence number disabling relative lock times and allowing the lock time to be final.
+pub const MAX_TX_IN_SEQUENCE_NUM: u32 = 0xffff_ffff;
+
+/// Value in of inputs whose amount is not committed to.
+pub const NULL_VALUE_IN: i64 = -1;
+
+/// Block index of inputs whose containing block is not committed to.
+pub const NULL_BLOCK_INDEX: u32 = 0xffff_ffff;
+
+/// OutPoint identifies a transaction output spent by an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutPoint {
+    /// Hash of the transaction holding the output.
+    pub hash: Hash,
+    /// Index of the output in the transaction.
+    pub index: u32,
+    /// Tree of the transaction holding the output.
+    pub tree: i8,
+}
+
+/// TxIn is a transaction input. The previous outpoint and sequence are part of the transaction
+/// prefix, the remaining fields of its witness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    /// Output spent by the input.
+    pub previous_out_point: OutPoint,
+    /// Sequence number, also encoding relative lock times.
+    pub sequence: u32,
+    /// Amount of the spent output in atoms.
+    pub value_in: i64,
+    /// Height of the block holding the spent output.
+    pub block_height: u32,
+    /// Index of the transaction holding the spent output in its block.
+    pub block_index: u32,
+    /// Script satisfying the spent output's script.
+    pub signature_script: Vec<u8>,
+}
+
+impl TxIn {
+    /// Returns an input spending previous_out_point with the given signature script, a final
+    /// sequence number and no committed value, block height or block index.
+    pub fn new(previous_out_point: OutPoint, value_in: i64, signature_script: Vec<u8>) -> Self {
+        TxIn {
+            previous_out_point,
+            sequence: MAX_TX_IN_SEQUENCE_NUM,
+            value_in,
+            block_height: 0,
+            block_index: NULL_BLOCK_INDEX,
+            signature_script,
+        }
+    }
+}
+
+/// TxOut is a transaction output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    /// Amount of the output in atoms.
+    pub value: i64,
+    /// Version of the output script.
+    pub version: u16,
+    /// Script spenders of the output must satisfy.
+    pub pk_script: Vec<u8>,
+}
+
+/// MsgTx is a transaction, mirroring dcrd's `wire.MsgTx`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgTx {
+    /// Transaction version.
+    pub version: u16,
+    /// Inputs spending previous outputs.
+    pub tx_in: Vec<TxIn>,
+    /// Outputs created by the transaction.
+    pub tx_out: Vec<TxOut>,
+    /// Block height or time before which the transaction can't be mined.
+    pub lock_time: u32,
+    /// Block height from which the transaction can no longer be mined, or zero for none.
+    pub expiry: u32,
+}
+
+impl Default for MsgTx {
+    fn default() -> Self {
+        MsgTx::new()
+    }
+}
+
+impl MsgTx {
+    /// Returns an empty transaction of the current version.
+    pub fn new() -> Self {
+        MsgTx {
+            version: TX_VERSION,
+            tx_in: Vec::new(),
+            tx_out: Vec::new(),
+            lock_time: 0,
+            expiry: 0,
+        }
+    }
+
+    /// serialize returns the full serialization of the transaction, its prefix followed by its
+    /// witness, as accepted by `send_raw_transaction`.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = self.version_bytes(TX_SERIALIZE_FULL);
+        self.write_prefix(&mut bytes);
+        self.write_witness(&mut bytes);
+
+        bytes
+    }
+
+    /// deserialize decodes a fully serialized transaction, or a transaction serialized without
+    /// its witness, whose inputs are then left without value in, block height, block index or
+    /// signature script. Bytes following the transaction are an error.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, BlockError> {
+        let mut reader = Reader::new(bytes);
+
+        let version = reader.u32()?;
+        let ser_type = (version >> 16) as u16;
+
+        if ser_type != TX_SERIALIZE_FULL && ser_type != TX_SERIALIZE_NO_WITNESS {
+            return Err(BlockError::InvalidSerializationType(ser_type));
+        }
+
+        let mut tx = MsgTx {
+            version: version as u16,
+            ..MsgTx::new()
+        };
+
+        let inputs = reader.var_int()?;
+        for _ in 0..inputs {
+            let mut hash = [0; HASH_SIZE];
+            hash.copy_from_slice(reader.take(HASH_SIZE)?);
+
+            let previous_out_point = OutPoint {
+                hash: Hash::from(hash),
+                index: reader.u32()?,
+                tree: reader.u8()? as i8,
+            };
+
+            tx.tx_in.push(TxIn {
+                sequence: reader.u32()?,
+                ..TxIn::new(previous_out_point, 0, Vec::new())
+            });
+        }
+
+        let outputs = reader.var_int()?;
+        for _ in 0..outputs {
+            tx.tx_out.push(TxOut {
+                value: reader.u64()? as i64,
+                version: reader.u16()?,
+                pk_script: reader.var_bytes()?.to_vec(),
+            });
+        }
+
+        tx.lock_time = reader.u32()?;
+        tx.expiry = reader.u32()?;
+
+        if ser_type == TX_SERIALIZE_FULL {
+            let witnesses = reader.var_int()?;
+
+            if witnesses != tx.tx_in.len() {
+                return Err(BlockError::WitnessCountMismatch {
+                    inputs: tx.tx_in.len(),
+                    witnesses,
+                });
+            }
+
+            for tx_in in tx.tx_in.iter_mut() {
+                tx_in.value_in = reader.u64()? as i64;
+                tx_in.block_height = reader.u32()?;
+                tx_in.block_index = reader.u32()?;
+                tx_in.signature_script = reader.var_bytes()?.to_vec();
+            }
+        }
+
+        if reader.position() != bytes.len() {
+            return Err(BlockError::TrailingBytes(bytes.len() - reader.position()));
+        }
+
+        Ok(tx)
+    }
+
+    /// tx_hash returns the hash identifying the transaction, the BLAKE-256 hash of its prefix
+    /// serialization. Signature scripts are not committed to, so signing doesn't change it.
+    pub fn tx_hash(&self) -> Hash {
+        hash_h(&self.prefix())
+    }
+
+    /// tx_hash_full returns the BLAKE-256 hash of the concatenation of the prefix and witness
+    /// hashes, committing to the signature scripts as well.
+    pub fn tx_hash_full(&self) -> Hash {
+        let mut witness = self.version_bytes(TX_SERIALIZE_ONLY_WITNESS);
+        self.write_witness(&mut witness);
+
+        let mut hashes = [0; HASH_SIZE * 2];
+        hashes[..HASH_SIZE].copy_from_slice(&hash_b(&self.prefix()));
+        hashes[HASH_SIZE..].copy_from_slice(&hash_b(&witness));
+
+        hash_h(&hashes)
+    }
+
+    /// Returns the prefix serialization of the transaction.
+    fn prefix(&self) -> Vec<u8> {
+        let mut bytes = self.version_bytes(TX_SERIALIZE_NO_WITNESS);
+        self.write_prefix(&mut bytes);
+
+        bytes
+    }
+
+    /// Returns the serialized version, with the serialization type in its upper 16 bits.
+    fn version_bytes(&self, ser_type: u16) -> Vec<u8> {
+        (u32::from(self.version) | u32::from(ser_type) << 16)
+            .to_le_bytes()
+            .to_vec()
+    }
+
+    fn write_prefix(&self, bytes: &mut Vec<u8>) {
+        write_var_int(bytes, self.tx_in.len());
+        for tx_in in &self.tx_in {
+            let out_point = &tx_in.previous_out_point;
+
+            bytes.extend_from_slice(out_point.hash.bytes());
+            bytes.extend_from_slice(&out_point.index.to_le_bytes());
+            bytes.push(out_point.tree as u8);
+            bytes.extend_from_slice(&tx_in.sequence.to_le_bytes());
+        }
+
+        write_var_int(bytes, self.tx_out.len());
+        for tx_out in &self.tx_out {
+            bytes.extend_from_slice(&tx_out.value.to_le_bytes());
+            bytes.extend_from_slice(&tx_out.version.to_le_bytes());
+            write_var_bytes(bytes, &tx_out.pk_script);
+        }
+
+        bytes.extend_from_slice(&self.lock_time.to_le_bytes());
+        bytes.extend_from_slice(&self.expiry.to_le_bytes());
+    }
+
+    fn write_witness(&self, bytes: &mut Vec<u8>) {
+        write_var_int(bytes, self.tx_in.len());
+        for tx_in in &self.tx_in {
+            bytes.extend_from_slice(&tx_in.value_in.to_le_bytes());
+            bytes.extend_from_slice(&tx_in.block_height.to_le_bytes());
+            bytes.extend_from_slice(&tx_in.block_index.to_le_bytes());
+            write_var_bytes(bytes, &tx_in.signature_script);
+        }
+    }
+}
+
+/// Writes a variable length integer in its shortest encoding.
+fn write_var_int(bytes: &mut Vec<u8>, value: usize) {
+    let value = value as u64;
+
+    match value {
+        0..=0xfc => bytes.push(value as u8),
+
+        0xfd..=0xffff => {
+            bytes.push(0xfd);
+            bytes.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+
+        0x1_0000..=0xffff_ffff => {
+            bytes.push(0xfe);
+            bytes.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+
+        _ => {
+            bytes.push(0xff);
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// Writes a variable length byte array.
+fn write_var_bytes(bytes: &mut Vec<u8>, data: &[u8]) {
+    write_var_int(bytes, data.len());
+    bytes.extend_from_slice(data);
+}
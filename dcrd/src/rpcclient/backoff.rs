@@ -0,0 +1,95 @@
+//! Reconnection backoff.
+//! Strategies deciding how long to wait between websocket reconnection attempts.
+
+use {
+    super::constants,
+    std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+};
+
+/// Decides how long to wait after a failed websocket reconnection attempt.
+pub trait BackoffStrategy: std::fmt::Debug + Send {
+    /// Returns the delay before the next reconnection attempt, given the number of consecutive
+    /// failed attempts so far, starting at 1.
+    fn next_delay(&mut self, attempt: u32) -> Duration;
+}
+
+/// Waits the same delay after every failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constant(pub Duration);
+
+impl BackoffStrategy for Constant {
+    fn next_delay(&mut self, _attempt: u32) -> Duration {
+        self.0
+    }
+}
+
+/// Doubles the delay after every failed attempt, starting at `initial` and never exceeding `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exponential {
+    pub initial: Duration,
+    pub max: Duration,
+}
+
+impl BackoffStrategy for Exponential {
+    fn next_delay(&mut self, attempt: u32) -> Duration {
+        2u32.checked_pow(attempt.saturating_sub(1))
+            .and_then(|factor| self.initial.checked_mul(factor))
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+}
+
+impl Default for Exponential {
+    fn default() -> Self {
+        Exponential {
+            initial: constants::CONNECTION_RETRY_INTERVAL_SECS,
+            max: constants::MAX_CONNECTION_RETRY_INTERVAL,
+        }
+    }
+}
+
+/// Waits a random delay between half and all of the `Exponential` delay, so clients
+/// disconnected together do not all reconnect at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExponentialWithJitter(pub Exponential);
+
+impl BackoffStrategy for ExponentialWithJitter {
+    fn next_delay(&mut self, attempt: u32) -> Duration {
+        let delay = self.0.next_delay(attempt);
+
+        let random = RandomState::new().build_hasher().finish();
+        let jitter = delay / 2;
+
+        delay - jitter + jitter.mul_f64(random as f64 / u64::MAX as f64)
+    }
+}
+
+/// Backoff strategy shared by a connection configuration and its reconnection handler.
+/// Defaults to `Exponential` backoff from 10 seconds up to 5 minutes.
+#[derive(Debug, Clone)]
+pub struct Backoff(Arc<Mutex<dyn BackoffStrategy>>);
+
+impl Backoff {
+    /// Wraps a backoff strategy, which may be user defined.
+    pub fn new(strategy: impl BackoffStrategy + 'static) -> Self {
+        Backoff(Arc::new(Mutex::new(strategy)))
+    }
+
+    /// Returns the delay before the next reconnection attempt.
+    pub(super) fn next_delay(&self, attempt: u32) -> Duration {
+        match self.0.lock() {
+            Ok(mut strategy) => strategy.next_delay(attempt),
+            Err(e) => e.into_inner().next_delay(attempt),
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::new(Exponential::default())
+    }
+}
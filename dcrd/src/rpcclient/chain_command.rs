@@ -5,7 +5,8 @@ use {
     super::{
         check_config, client::Client, connection::RPCConn, error::RpcClientError, future_type,
     },
-    crate::dcrjson::commands,
+    crate::{chaincfg::chainhash::Hash, dcrjson::commands, dcrutil::amount},
+    std::collections::HashMap,
 };
 
 /// Generates clients command
@@ -20,10 +21,15 @@ macro_rules! command_generator {
             // Error if user is not on HTTP mode and websocket is disconnected.
             check_config!(self);
 
+            // Time spent waiting to be sent counts towards the request timeout.
+            let deadline = self.request_deadline($command);
+
             let cmd_result = self.send_custom_command($command, $json_params).await;
 
             match cmd_result {
-                Ok(e) => Ok(<$output_type>::new(e.1)),
+                Ok(e) => Ok(<$output_type>::new(e.1)
+                    .with_policy(self.conn.deserialization_policy())
+                    .with_deadline(deadline)),
 
                 Err(e) => Err(e),
             }
@@ -40,6 +46,78 @@ impl<C: 'static + RPCConn> Client<C> {
         &[],
     );
 
+    command_generator!(
+        "get_best_block returns the hash and height of the block in the longest (best) chain,
+        saving the separate `get_best_block_hash` and `get_block_count` round trips.",
+        get_best_block,
+        future_type::GetBestBlockFuture,
+        commands::METHOD_GET_BEST_BLOCK,
+        &[],
+    );
+
+    command_generator!(
+        "get_best_block_hash returns the hash of the block in the longest (best) chain.",
+        get_best_block_hash,
+        future_type::GetBestBlockHashFuture,
+        commands::METHOD_GET_BEST_BLOCK_HASH,
+        &[],
+    );
+
+    command_generator!(
+        "get_stake_difficulty returns the ticket price of the next block and of the next ticket
+        price window. Unlike the `stakedifficulty` notification, it can be polled.",
+        get_stake_difficulty,
+        future_type::GetStakeDifficultyFuture,
+        commands::METHOD_GET_STAKE_DIFFICULTY,
+        &[],
+    );
+
+    command_generator!(
+        "estimate_stake_diff returns the minimum, maximum and expected stake difficulty of the next
+        ticket price window. If tickets is set, the stake difficulty is also estimated for that
+        many tickets being purchased in the rest of the window.",
+        estimate_stake_diff,
+        future_type::EstimateStakeDiffFuture,
+        commands::METHOD_ESTIMATE_STAKE_DIFF,
+        &[serde_json::json!(tickets)],
+        tickets: Option<u32>
+    );
+
+    command_generator!(
+        "get_current_net returns the network magic of the network the server is on, matching the
+        `net` of one of the networks in `chaincfg::params`.",
+        get_current_net,
+        future_type::GetCurrentNetFuture,
+        commands::METHOD_GET_CURRENT_NET,
+        &[],
+    );
+
+    command_generator!(
+        "get_raw_mempool returns the hashes of all transactions in the memory pool.",
+        get_raw_mempool,
+        future_type::GetRawMempoolFuture,
+        commands::METHOD_GET_RAW_MEMPOOL,
+        &[serde_json::json!(false)],
+    );
+
+    command_generator!(
+        "get_raw_mempool_verbose returns data about each transaction in the memory pool, keyed
+        by transaction hash. `MempoolFeeRates` sorts the transactions by fee rate.",
+        get_raw_mempool_verbose,
+        future_type::GetRawMempoolVerboseFuture,
+        commands::METHOD_GET_RAW_MEMPOOL,
+        &[serde_json::json!(true)],
+    );
+
+    command_generator!(
+        "get_mempool_info returns the number of transactions in the memory pool and their total
+        size in bytes.",
+        get_mempool_info,
+        future_type::GetMempoolInfoFuture,
+        commands::METHOD_GET_MEMPOOL_INFO,
+        &[],
+    );
+
     command_generator!(
         "get_block_count returns the number of blocks in the longest block chain.",
         get_block_count,
@@ -57,6 +135,54 @@ impl<C: 'static + RPCConn> Client<C> {
         block_height: i64
     );
 
+    command_generator!(
+        "get_block_subsidy returns the subsidy paid at the given block height to proof-of-work,
+        proof-of-stake and the treasury, given the number of voters included in the block.",
+        get_block_subsidy,
+        future_type::GetBlockSubsidyFuture,
+        commands::METHOD_GET_BLOCK_SUBSIDY,
+        &[serde_json::json!(height), serde_json::json!(voters)],
+        height: i64,
+        voters: u16
+    );
+
+    command_generator!(
+        "get_block returns the serialized block given its hash.",
+        get_block,
+        future_type::GetBlockFuture,
+        commands::METHOD_GET_BLOCK,
+        &[serde_json::json!(block_hash), serde_json::json!(false)],
+        block_hash: String
+    );
+
+    command_generator!(
+        "get_block_header returns the serialized header of a block given its hash.",
+        get_block_header,
+        future_type::GetBlockHeaderFuture,
+        commands::METHOD_GET_BLOCK_HEADER,
+        &[serde_json::json!(block_hash), serde_json::json!(false)],
+        block_hash: String
+    );
+
+    command_generator!(
+        "get_block_header_verbose returns a data structure describing the header of a block
+        given its hash.",
+        get_block_header_verbose,
+        future_type::GetBlockHeaderVerboseFuture,
+        commands::METHOD_GET_BLOCK_HEADER,
+        &[serde_json::json!(block_hash), serde_json::json!(true)],
+        block_hash: String
+    );
+
+    command_generator!(
+        "get_block_hex returns the hex-encoded serialized block given its hash.",
+        get_block_hex,
+        future_type::GetBlockHexFuture,
+        commands::METHOD_GET_BLOCK,
+        &[serde_json::json!(block_hash), serde_json::json!(false)],
+        block_hash: String
+    );
+
     command_generator!(
         "get_block_verbose returns a data structure from the server with information
         about a block given its hash.",
@@ -72,29 +198,316 @@ impl<C: 'static + RPCConn> Client<C> {
         verbose_tx: bool
     );
 
+    /// exists_addresses returns whether each of the passed addresses exists in the blockchain.
+    /// The result pairs each address with its existence flag in the order they were passed.
+    pub async fn exists_addresses(
+        &mut self,
+        addresses: &[&str],
+    ) -> Result<future_type::ExistsAddressesFuture, RpcClientError> {
+        check_config!(self);
+
+        let deadline = self.request_deadline(commands::METHOD_EXISTS_ADDRESSES);
+
+        let (_, message) = self
+            .send_custom_command(
+                commands::METHOD_EXISTS_ADDRESSES,
+                &[serde_json::json!(addresses)],
+            )
+            .await?;
+
+        Ok(future_type::ExistsAddressesFuture {
+            message,
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+            deadline: None,
+        }
+        .with_deadline(deadline))
+    }
+
+    /// get_headers returns the headers of the main chain blocks following the first block of
+    /// `block_locators` found in the main chain, up to and including `hash_stop` or up to the
+    /// server's limit when `hash_stop` is not found. Block locators can be built with
+    /// `dcrutil::build_block_locator`.
+    pub async fn get_headers(
+        &mut self,
+        block_locators: &[Hash],
+        hash_stop: &Hash,
+    ) -> Result<future_type::GetHeadersFuture, RpcClientError> {
+        check_config!(self);
+
+        let block_locators = block_locators
+            .iter()
+            .map(|hash| hash.string())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RpcClientError::ChainHash)?;
+        let hash_stop = hash_stop.string().map_err(RpcClientError::ChainHash)?;
+
+        let deadline = self.request_deadline(commands::METHOD_GET_HEADERS);
+
+        let (_, message) = self
+            .send_custom_command(
+                commands::METHOD_GET_HEADERS,
+                &[
+                    serde_json::json!(block_locators),
+                    serde_json::json!(hash_stop),
+                ],
+            )
+            .await?;
+
+        Ok(future_type::GetHeadersFuture::new(message).with_deadline(deadline))
+    }
+
+    /// create_raw_transaction returns an unsigned transaction spending `inputs` and paying each
+    /// address of `amounts` its amount in DCR. The transaction's lock time and expiry are set
+    /// when given.
+    ///
+    /// Amounts are rounded to the nearest Atom before being sent, so they reach the server with
+    /// exactly 8 decimals. Negative or non-finite amounts fail with an InvalidParameter error
+    /// without sending the request.
+    pub async fn create_raw_transaction(
+        &mut self,
+        inputs: Vec<cmd_types::TransactionInput>,
+        amounts: HashMap<String, f64>,
+        lock_time: Option<i64>,
+        expiry: Option<i64>,
+    ) -> Result<future_type::CreateRawTransactionFuture, RpcClientError> {
+        if let Some(input) = inputs.iter().find(|input| i64::from(input.amount) < 0) {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "negative amount {} spent from {}:{}",
+                input.amount, input.txid, input.vout
+            )));
+        }
+
+        let mut outputs = HashMap::with_capacity(amounts.len());
+        for (address, amount) in amounts {
+            let atoms = match amount::new(amount) {
+                Ok(atoms) if i64::from(atoms) >= 0 => atoms,
+
+                _ => {
+                    return Err(RpcClientError::InvalidParameter(format!(
+                        "invalid amount {} paid to {}",
+                        amount, address
+                    )))
+                }
+            };
+
+            outputs.insert(address, atoms);
+        }
+
+        check_config!(self);
+
+        let mut params = vec![serde_json::json!(inputs), serde_json::json!(outputs)];
+
+        // The lock time is sent, possibly as null, whenever the expiry follows it.
+        if lock_time.is_some() || expiry.is_some() {
+            params.push(serde_json::json!(lock_time));
+        }
+
+        if let Some(expiry) = expiry {
+            params.push(serde_json::json!(expiry));
+        }
+
+        let deadline = self.request_deadline(commands::METHOD_CREATE_RAW_TRANSACTION);
+
+        let (_, message) = self
+            .send_custom_command(commands::METHOD_CREATE_RAW_TRANSACTION, &params)
+            .await?;
+
+        Ok(future_type::CreateRawTransactionFuture::new(message)
+            .with_policy(self.conn.deserialization_policy())
+            .with_deadline(deadline))
+    }
+
+    /// get_network_hashps returns the estimated network hashes per second over the `blocks`
+    /// blocks ending at `height`. dcrd estimates over 120 blocks at the best block when called
+    /// with `(120, -1)`.
+    ///
+    /// A `blocks` of -1 or 0 estimates since the last difficulty change and a `height` of -1 uses
+    /// the best block; these sentinels are sent unchanged. Any other negative value fails with an
+    /// InvalidParameter error without sending the request.
+    pub async fn get_network_hashps(
+        &mut self,
+        blocks: i64,
+        height: i64,
+    ) -> Result<future_type::GetNetworkHashPsFuture, RpcClientError> {
+        if blocks < -1 || height < -1 {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "invalid network hash rate window of {} blocks at height {}",
+                blocks, height
+            )));
+        }
+
+        check_config!(self);
+
+        let deadline = self.request_deadline(commands::METHOD_GET_NETWORK_HASH_PS);
+
+        let (_, message) = self
+            .send_custom_command(
+                commands::METHOD_GET_NETWORK_HASH_PS,
+                &[serde_json::json!(blocks), serde_json::json!(height)],
+            )
+            .await?;
+
+        Ok(future_type::GetNetworkHashPsFuture::new(message)
+            .with_policy(self.conn.deserialization_policy())
+            .with_deadline(deadline))
+    }
+
     command_generator!(
-        "decode_raw_transaction returns information about a transaction given its serialized bytes.",
+        "decode_raw_transaction returns information about a transaction given its serialized bytes,
+        such as a transaction built locally before it is sent. Outputs of stake transactions carry
+        their ticket commitment amounts.",
         decode_raw_transaction,
         future_type::DecodeRawTransactionFuture,
         commands::METHOD_DECODE_RAW_TRANSACTION,
-        &[serde_json::json!(serialized_tx)],
+        &[serde_json::json!(hex::encode(serialized_tx))],
         serialized_tx: &[u8]
-     );
+    );
 
     command_generator!(
-        "estimate_smart_fee returns an estimation of a transaction fee rate (in dcr/KB) 
+        "estimate_fee returns the fee rate, in DCR/kB, transactions should pay to be mined.
+        dcrd ignores `num_blocks` and returns its minimum relay fee rate.",
+        estimate_fee,
+        future_type::EstimateFeeFuture,
+        commands::METHOD_ESTIMATE_FEE,
+        &[serde_json::json!(num_blocks)],
+        num_blocks: i64
+    );
+
+    command_generator!(
+        "estimate_smart_fee returns an estimation of a transaction fee rate (in dcr/KB)
         that new transactions should pay if they desire to be mined in up to 
         'confirmations' blocks and the block number where the estimate was found.
         \nThe mode parameter (roughly) selects the different thresholds for accepting 
         an estimation as reasonable, allowing users to select different trade-offs 
         between probability of the transaction being mined in the given target 
         confirmation range and minimization of fees paid.
-        \nAs of 2019-01, only the default conservative mode is supported by dcrd.",
+        \nAs of 2019-01, only the default conservative mode is supported by dcrd. An Unset
+        mode is left out of the request.
+        \nThe future fails with a NoFeeEstimate error holding the server's reasons when no
+        estimate is available, such as shortly after the server started.",
         estimate_smart_fee,
         future_type::EstimateSmartFeeFuture,
         commands::METHOD_ESTIMATE_SMART_FEE,
-        &[serde_json::json!(confirmations), serde_json::json!(mode),],
+        &std::iter::once(serde_json::json!(confirmations))
+            .chain(mode.param())
+            .collect::<Vec<_>>(),
         confirmations: i64,
-        mode: cmd_types::EstimateSmartFeeMode
+        mode: cmd_types::EstimateMode
+    );
+
+    command_generator!(
+        "get_stake_version_info returns stake version statistics for the last `count`
+        stake version intervals, most recent first.",
+        get_stake_version_info,
+        future_type::GetStakeVersionInfoFuture,
+        commands::METHOD_GET_STAKE_VERSION_INFO,
+        &[serde_json::json!(count)],
+        count: i32
+    );
+
+    command_generator!(
+        "get_vote_info returns the vote tally and the agendas being voted on for
+        the given vote version.",
+        get_vote_info,
+        future_type::GetVoteInfoFuture,
+        commands::METHOD_GET_VOTE_INFO,
+        &[serde_json::json!(version)],
+        version: u32
+    );
+
+    command_generator!(
+        "get_raw_transaction returns the serialized transaction given its hash. It can be
+        decoded with `dcrutil::tx::MsgTx::deserialize`.",
+        get_raw_transaction,
+        future_type::GetRawTransactionFuture,
+        commands::METHOD_GET_RAW_TRANSACTION,
+        &[serde_json::json!(tx_hash), serde_json::json!(0)],
+        tx_hash: String
+    );
+
+    command_generator!(
+        "get_raw_transaction_verbose returns information about a transaction given
+        its hash, including the block it was mined in and its number of confirmations.",
+        get_raw_transaction_verbose,
+        future_type::GetRawTransactionVerboseFuture,
+        commands::METHOD_GET_RAW_TRANSACTION,
+        &[serde_json::json!(tx_hash), serde_json::json!(1)],
+        tx_hash: String
+    );
+
+    command_generator!(
+        "get_raw_transaction_hex returns the hex-encoded serialized transaction given its hash.",
+        get_raw_transaction_hex,
+        future_type::GetRawTransactionHexFuture,
+        commands::METHOD_GET_RAW_TRANSACTION,
+        &[serde_json::json!(tx_hash), serde_json::json!(0)],
+        tx_hash: String
+    );
+
+    command_generator!(
+        "get_tx_out returns information about an unspent transaction output given its
+        transaction hash, output index and transaction tree. If include_mempool is set, outputs
+        created and spent by mempool transactions are taken into account. None is returned when
+        the output is spent or doesn't exist.",
+        get_tx_out,
+        future_type::GetTxOutFuture,
+        commands::METHOD_GET_TX_OUT,
+        &[
+            serde_json::json!(tx_hash),
+            serde_json::json!(vout),
+            serde_json::json!(tree),
+            serde_json::json!(include_mempool)
+        ],
+        tx_hash: String,
+        vout: u32,
+        tree: i8,
+        include_mempool: bool
+    );
+
+    command_generator!(
+        "get_peer_info returns data about each peer connected to the server.
+        \nOnly clients authenticated with the server's admin credentials may call getpeerinfo.
+        Limited users fail with an RpcServer error with the InvalidParams code and the message
+        \"limited user not authorized for this method\".",
+        get_peer_info,
+        future_type::GetPeerInfoFuture,
+        commands::METHOD_GET_PEER_INFO,
+        &[],
+    );
+
+    command_generator!(
+        "get_net_totals returns the total bytes received and sent over all network connections
+        along with the server time the totals were taken at.",
+        get_net_totals,
+        future_type::GetNetTotalsFuture,
+        commands::METHOD_GET_NET_TOTALS,
+        &[],
+    );
+
+    command_generator!(
+        "get_mining_info returns mining related information such as the current difficulty,
+        the estimated network hash rate and the contents of the last block template, which pairs
+        with the on_work notification for miners.",
+        get_mining_info,
+        future_type::GetMiningInfoFuture,
+        commands::METHOD_GET_MINING_INFO,
+        &[],
+    );
+
+    command_generator!(
+        "search_raw_transactions_verbose returns up to `count` transactions involving the given
+        address, after skipping the first `skip`, including the block they were mined in.",
+        search_raw_transactions_verbose,
+        future_type::SearchRawTransactionsVerboseFuture,
+        commands::METHOD_SEARCH_RAW_TRANSACTIONS,
+        &[
+            serde_json::json!(address),
+            serde_json::json!(1),
+            serde_json::json!(skip),
+            serde_json::json!(count)
+        ],
+        address: String,
+        skip: i32,
+        count: i32
     );
 }
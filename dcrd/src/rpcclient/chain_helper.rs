@@ -0,0 +1,1533 @@
+//! Chain Helper Commands.
+//! Contains helpers composed from one or more chain commands to RPC server.
+
+use {
+    super::{
+        backoff::{BackoffStrategy, Exponential},
+        chain_notification::{self, HelperRegistration},
+        check_config,
+        client::Client,
+        connection::RPCConn,
+        constants,
+        error::RpcClientError,
+        future_type, logging,
+    },
+    crate::{
+        chaincfg::{
+            chainhash::Hash,
+            params::{self, Network},
+        },
+        dcrjson::{
+            cmd_types::EstimateMode,
+            commands,
+            result_types::{
+                Agenda, GetBlockHeaderVerboseResult, GetBlockSubsidyResult, GetBlockVerboseResult,
+                JsonResponse, MempoolAcceptResult, RpcError, TxRawResult,
+            },
+            RpcErrorCode, RpcServerError,
+        },
+        dcrutil::{address, amount::Amount, Block},
+    },
+    futures_util::{
+        future::{Either, FutureExt},
+        stream::{self, Stream},
+    },
+    log::{trace, warn},
+    std::{
+        collections::{HashSet, VecDeque},
+        time::Duration,
+    },
+    tokio::{sync::mpsc, time},
+};
+
+/// VerifiedBlock is a block whose hash and merkle roots were checked against its header by
+/// `get_verified_block`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedBlock {
+    hash: Hash,
+    block: Block,
+}
+
+impl VerifiedBlock {
+    /// Returns the block hash.
+    pub fn hash(&self) -> &Hash {
+        &self.hash
+    }
+
+    /// Returns the verified block.
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    /// Returns the verified block, consuming the VerifiedBlock.
+    pub fn into_block(self) -> Block {
+        self.block
+    }
+}
+
+/// Status of an agenda whose voting window has begun.
+const AGENDA_STATUS_STARTED: &str = "started";
+
+/// CoinSupplyBreakdown splits the coins issued up to a block height into the
+/// portions paid to proof-of-work miners, proof-of-stake voters and the treasury.
+/// All values are in atoms.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoinSupplyBreakdown {
+    /// Height the supply was computed at.
+    pub height: i64,
+    /// Coins paid to proof-of-work miners, including the block one premine.
+    pub pow: i64,
+    /// Coins paid to proof-of-stake voters.
+    pub pos: i64,
+    /// Coins paid to the treasury.
+    pub treasury: i64,
+    /// Sum of all components.
+    pub total: i64,
+}
+
+/// LatencySummary summarizes the ping round trip times of the peers connected to a server.
+/// Times are zero when no peer has answered a ping.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Number of peers whose ping time was summarized.
+    pub peers: usize,
+    /// Number of connected peers that have not answered a ping yet.
+    pub unmeasured: usize,
+    /// Lowest ping time.
+    pub min: Duration,
+    /// Median ping time.
+    pub median: Duration,
+    /// 95th percentile ping time, the lowest time at least 95% of the peers are within.
+    pub p95: Duration,
+    /// Highest ping time.
+    pub max: Duration,
+}
+
+/// StakeDifficultyUpdate is a stake difficulty change announced when a block is connected
+/// to the main chain.
+#[derive(Debug, Clone)]
+pub struct StakeDifficultyUpdate {
+    /// Hash of the block the stake difficulty was calculated at.
+    pub hash: Hash,
+    /// Height of the block the stake difficulty was calculated at.
+    pub height: i64,
+    /// Price of a ticket in the next stake difficulty window.
+    pub stake_difficulty: Amount,
+}
+
+/// Position of a block_stream in the chain.
+struct BlockStreamState {
+    /// Height of the next block to stream.
+    next_height: i64,
+    /// Hashes of the most recently streamed blocks, the last being the parent of the next block.
+    recent: VecDeque<String>,
+    /// Block connected notifications, if registered.
+    blocks: Option<mpsc::UnboundedReceiver<JsonResponse>>,
+    /// Block notification registration, unregistered once the stream is dropped.
+    registration: Option<HelperRegistration>,
+    /// Whether registering for block notifications was attempted.
+    registered: bool,
+}
+
+/// WinningTicketsEvent lists the tickets chosen to vote on a block connected to the main chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WinningTicketsEvent {
+    /// Hash of the block the tickets vote on.
+    pub hash: Hash,
+    /// Height of the block the tickets vote on.
+    pub height: i64,
+    /// Hashes of the winning tickets, in the order they were selected.
+    pub tickets: Vec<Hash>,
+}
+
+/// MempoolDelta lists the transactions added to and removed from the memory pool since the
+/// previous delta.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MempoolDelta {
+    /// Hashes of transactions accepted to the memory pool.
+    pub added: Vec<Hash>,
+    /// Hashes of transactions that left the memory pool, whether mined, expired or evicted.
+    pub removed: Vec<Hash>,
+}
+
+/// MempoolPressure summarizes how full the memory pool is and the fee rates, in DCR/kB, needed
+/// to get a transaction mined soon.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MempoolPressure {
+    /// Number of transactions in the memory pool.
+    pub size: i64,
+    /// Total size in bytes of the transactions in the memory pool.
+    pub bytes: i64,
+    /// Fee rate estimated to get a transaction into the next block.
+    pub next_block_fee_rate: f64,
+    /// Fee rate estimated to get a transaction mined within 6 blocks.
+    pub six_block_fee_rate: f64,
+}
+
+impl MempoolPressure {
+    /// Returns whether getting into the next block costs more than the default minimum relay
+    /// fee rate of 0.0001 DCR/kB, meaning transactions paying the minimum are left waiting.
+    pub fn is_congested(&self) -> bool {
+        self.next_block_fee_rate > constants::DEFAULT_MIN_RELAY_FEE_RATE
+    }
+}
+
+/// Returns whether a server error code returned by `sendrawtransaction` means the transaction
+/// itself was refused. dcrd reports mempool rule violations with the general Misc code.
+fn is_rejection(code: RpcErrorCode) -> bool {
+    matches!(
+        code,
+        RpcErrorCode::Misc
+            | RpcErrorCode::Deserialization
+            | RpcErrorCode::Verify
+            | RpcErrorCode::DuplicateTx
+    )
+}
+
+impl<C: 'static + RPCConn> Client<C> {
+    /// get_coin_supply_breakdown returns the coin supply at the given height, or at the best block
+    /// if height is None, split into its proof-of-work, proof-of-stake and treasury components.
+    ///
+    /// The block subsidy is constant between reductions, so rather than requesting every block,
+    /// `getblocksubsidy` is sampled once for each run of blocks sharing the same subsidy: block one
+    /// (the premine), the blocks before stake validation height and each subsidy reduction interval
+    /// afterwards. Each sample is then multiplied by the number of blocks in its run.
+    ///
+    /// Subsidies are requested assuming every block from stake validation height includes all
+    /// five votes. Blocks with missed votes pay out less, so the returned values are an upper
+    /// bound on the issued supply and `total` can slightly exceed what `getcoinsupply` reports.
+    ///
+    /// Runs are split using the subsidy reduction interval and stake validation height of the
+    /// network detected with `detect_and_set_network`, or of `chaincfg::params::MAINNET` when the
+    /// network has not been detected.
+    pub async fn get_coin_supply_breakdown(
+        &mut self,
+        height: Option<i64>,
+    ) -> Result<CoinSupplyBreakdown, RpcClientError> {
+        let height = match height {
+            Some(height) => height,
+
+            None => self.get_block_count().await?.await?,
+        };
+
+        let mut breakdown = CoinSupplyBreakdown {
+            height,
+            ..Default::default()
+        };
+
+        let params = self.network.clone().unwrap_or(params::MAINNET);
+        let params = &params;
+
+        for (start, end) in subsidy_segments(height, params) {
+            let voters = if start >= params.stake_validation_height {
+                params.tickets_per_block
+            } else {
+                0
+            };
+
+            let blocks = end - start + 1;
+            let subsidy = self.get_block_subsidy(start, voters).await?.await?;
+            trace!("Received block subsidy sample for {} blocks", blocks);
+
+            breakdown.pow += subsidy.pow * blocks;
+            breakdown.pos += subsidy.pos * blocks;
+            breakdown.treasury += subsidy.developer * blocks;
+        }
+
+        breakdown.total = breakdown.pow + breakdown.pos + breakdown.treasury;
+
+        Ok(breakdown)
+    }
+
+    /// mempool_pressure returns the size of the memory pool together with the fee rates
+    /// estimated by `estimatesmartfee` to get a transaction mined in the next block and within
+    /// 6 blocks, which wallets can use to warn of high fee conditions.
+    ///
+    /// The requests are all sent before any result is awaited. A NoFeeEstimate error is returned
+    /// when the server has no fee estimate yet.
+    pub async fn mempool_pressure(&mut self) -> Result<MempoolPressure, RpcClientError> {
+        let info = self.get_mempool_info().await?;
+        let next_block = self
+            .estimate_smart_fee(1, EstimateMode::Conservative)
+            .await?;
+        let six_blocks = self
+            .estimate_smart_fee(6, EstimateMode::Conservative)
+            .await?;
+
+        let info = info.await?;
+
+        Ok(MempoolPressure {
+            size: info.size,
+            bytes: info.bytes,
+            next_block_fee_rate: next_block.await?.feerate,
+            six_block_fee_rate: six_blocks.await?.feerate,
+        })
+    }
+
+    /// reward_schedule returns the block subsidy every `step` blocks from `from_height` up to
+    /// `to_height`, paired with the height it was sampled at, showing how rewards decay over the
+    /// range.
+    ///
+    /// Like get_coin_supply_breakdown, subsidies from stake validation height assume every vote
+    /// is included. Every `getblocksubsidy` request is sent before any result is awaited, so a
+    /// schedule may hold at most 1000 samples.
+    pub async fn reward_schedule(
+        &mut self,
+        from_height: i64,
+        to_height: i64,
+        step: i64,
+    ) -> Result<Vec<(i64, GetBlockSubsidyResult)>, RpcClientError> {
+        if from_height < 0 || from_height > to_height {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "invalid block height range [{}, {}]",
+                from_height, to_height
+            )));
+        }
+
+        if step <= 0 {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "invalid step {}",
+                step
+            )));
+        }
+
+        // Both heights are non-negative so the difference can't overflow.
+        let samples = (to_height - from_height) / step + 1;
+        if samples > constants::MAX_REWARD_SCHEDULE_SAMPLES {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "{} samples exceed {}",
+                samples,
+                constants::MAX_REWARD_SCHEDULE_SAMPLES
+            )));
+        }
+
+        let params = self.network.clone().unwrap_or(params::MAINNET);
+
+        let mut futures = Vec::with_capacity(samples as usize);
+        for height in (0..samples).map(|sample| from_height + sample * step) {
+            let voters = if height >= params.stake_validation_height {
+                params.tickets_per_block
+            } else {
+                0
+            };
+
+            futures.push((height, self.get_block_subsidy(height, voters).await?));
+        }
+
+        let mut schedule = Vec::with_capacity(futures.len());
+        for (height, future) in futures {
+            schedule.push((height, future.await?));
+        }
+
+        Ok(schedule)
+    }
+
+    /// active_agendas returns the agendas currently being voted on.
+    ///
+    /// The current vote version is resolved from `getstakeversioninfo` as the version cast by
+    /// most votes in the latest stake version interval, and `getvoteinfo` is then queried for
+    /// that version. Only agendas with a "started" status are returned.
+    pub async fn active_agendas(&mut self) -> Result<Vec<Agenda>, RpcClientError> {
+        let stake_version_info = self.get_stake_version_info(1).await?.await?;
+
+        let version = stake_version_info
+            .intervals
+            .first()
+            .and_then(|interval| {
+                interval
+                    .vote_versions
+                    .iter()
+                    .max_by_key(|version| (version.count, version.version))
+            })
+            .map(|version| version.version);
+
+        let version = match version {
+            Some(version) => version,
+
+            None => {
+                trace!("No vote versions in latest stake version interval");
+                return Ok(Vec::new());
+            }
+        };
+
+        let vote_info = self.get_vote_info(version).await?.await?;
+
+        Ok(vote_info
+            .agendas
+            .into_iter()
+            .filter(|agenda| agenda.status == AGENDA_STATUS_STARTED)
+            .collect())
+    }
+
+    /// stake_version_adoption returns the percentage, from 0 to 100, of the votes cast in the
+    /// latest stake version interval, as reported by `getstakeversioninfo`, that are at or above
+    /// target_version. Zero is returned when no votes were cast in the interval.
+    pub async fn stake_version_adoption(
+        &mut self,
+        target_version: u32,
+    ) -> Result<f64, RpcClientError> {
+        let stake_version_info = self.get_stake_version_info(1).await?.await?;
+
+        let (adopted, total) = stake_version_info
+            .intervals
+            .first()
+            .map(|interval| interval.vote_versions.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .fold((0u64, 0u64), |(adopted, total), version| {
+                let count = u64::from(version.count);
+
+                if version.version >= target_version {
+                    (adopted + count, total + count)
+                } else {
+                    (adopted, total + count)
+                }
+            });
+
+        if total == 0 {
+            trace!("No votes in latest stake version interval");
+            return Ok(0.0);
+        }
+
+        Ok(adopted as f64 / total as f64 * 100.0)
+    }
+
+    /// confirmations returns the number of confirmations of the transaction with the given
+    /// hash. None is returned if the transaction is unconfirmed in the mempool or unknown to
+    /// the server.
+    ///
+    /// Confirmations are counted by the server against its current best chain. When the block
+    /// a transaction was mined in is reorganized out, the transaction is either returned to the
+    /// mempool, yielding None until it is mined again and its count restarts from the new block,
+    /// or dropped when it conflicts with the new chain, yielding None as an unknown transaction.
+    /// Transactions not in the mempool are only known to servers running with a transaction index.
+    pub async fn confirmations(&mut self, txid: &Hash) -> Result<Option<i64>, RpcClientError> {
+        let tx_hash = txid.string().map_err(RpcClientError::ChainHash)?;
+
+        let tx = match self.get_raw_transaction_verbose(tx_hash).await?.await {
+            Ok(tx) => tx,
+
+            Err(RpcServerError::ServerError(e)) if e.code == constants::ERR_RPC_NO_TX_INFO => {
+                trace!(
+                    "Server has no information on transaction, error: {}",
+                    e.message
+                );
+                return Ok(None);
+            }
+
+            Err(e) => return Err(e.into()),
+        };
+
+        if tx.block_hash.is_empty() || tx.confirmations <= 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(tx.confirmations))
+    }
+
+    /// is_in_main_chain returns whether the block with the given hash is part of the server's
+    /// current best chain, letting reorg-aware services tell whether a block seen earlier is
+    /// still canonical.
+    ///
+    /// dcrd reports -1 confirmations for blocks it knows about that are not in the best chain,
+    /// such as side chain blocks or blocks reorganized out, and counts confirmations from one
+    /// for best chain blocks. Blocks unknown to the server return the server's error.
+    pub async fn is_in_main_chain(&mut self, hash: &Hash) -> Result<bool, RpcClientError> {
+        let block_hash = hash.string().map_err(RpcClientError::ChainHash)?;
+
+        let header = self.get_block_header_verbose(block_hash).await?.await?;
+
+        Ok(header.confirmations >= 0)
+    }
+
+    /// walk_headers_back returns up to `count` block headers starting at the block with the given
+    /// hash and following each header's previous block hash, ordered newest first.
+    ///
+    /// Each header names its parent, so the headers are requested one round trip at a time. The
+    /// walk stops early at the genesis block, returning fewer than `count` headers.
+    pub async fn walk_headers_back(
+        &mut self,
+        from: &Hash,
+        count: usize,
+    ) -> Result<Vec<GetBlockHeaderVerboseResult>, RpcClientError> {
+        let mut block_hash = from.string().map_err(RpcClientError::ChainHash)?;
+        let mut headers = Vec::with_capacity(count);
+
+        while headers.len() < count {
+            let header = self.get_block_header_verbose(block_hash).await?.await?;
+            block_hash = header.previous_hash.clone();
+
+            let is_genesis = header.height == 0;
+            headers.push(header);
+
+            if is_genesis {
+                break;
+            }
+        }
+
+        Ok(headers)
+    }
+
+    /// get_block_hash_range returns the hashes of the blocks in the best block chain from height
+    /// `start` to `end` inclusive, ordered by height.
+    ///
+    /// A `getblockhash` request is sent for every height before any result is awaited so the
+    /// requests are pipelined over the connection rather than issued one round trip at a time.
+    /// As every request is in flight at once, a range may span at most 1000 blocks; larger ranges
+    /// should be requested in chunks.
+    pub async fn get_block_hash_range(
+        &mut self,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<Hash>, RpcClientError> {
+        if start < 0 || end < 0 || start > end {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "invalid block height range [{}, {}]",
+                start, end
+            )));
+        }
+
+        // Both heights are non-negative so the difference can't overflow.
+        if end - start >= constants::MAX_BLOCK_HASH_RANGE {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "block height range [{}, {}] exceeds {} blocks",
+                start,
+                end,
+                constants::MAX_BLOCK_HASH_RANGE
+            )));
+        }
+
+        let mut futures = Vec::new();
+        for height in start..=end {
+            futures.push(self.get_block_hash(height).await?);
+        }
+
+        let mut hashes = Vec::with_capacity(futures.len());
+        for future in futures {
+            hashes.push(future.await?);
+        }
+
+        Ok(hashes)
+    }
+
+    /// hashrate_trend returns the estimated network hashes per second at the best block over
+    /// each of the given block windows, paired with their window.
+    ///
+    /// Like get_block_hash_range, every `getnetworkhashps` request is sent before any result is
+    /// awaited so the windows are estimated concurrently by the server.
+    pub async fn hashrate_trend(
+        &mut self,
+        windows: &[i64],
+    ) -> Result<Vec<(i64, i64)>, RpcClientError> {
+        let mut futures = Vec::with_capacity(windows.len());
+        for window in windows {
+            futures.push((*window, self.get_network_hashps(*window, -1).await?));
+        }
+
+        let mut trend = Vec::with_capacity(futures.len());
+        for (window, future) in futures {
+            trend.push((window, future.await?));
+        }
+
+        Ok(trend)
+    }
+
+    /// get_raw_transactions returns the transactions with the given hashes in the order they
+    /// were passed, each with its own result so a transaction the server has no information on
+    /// doesn't fail the others.
+    ///
+    /// When verbose is false only the `hex` and `tx_id` of each result are set. Requests are
+    /// pipelined up to 100 at a time, sending the next requests once every result of the
+    /// previous ones is received.
+    pub async fn get_raw_transactions(
+        &mut self,
+        txids: &[Hash],
+        verbose: bool,
+    ) -> Result<Vec<Result<TxRawResult, RpcServerError>>, RpcClientError> {
+        let mut transactions = Vec::with_capacity(txids.len());
+
+        for chunk in txids.chunks(constants::MAX_RAW_TRANSACTIONS_IN_FLIGHT) {
+            let mut futures = Vec::with_capacity(chunk.len());
+
+            for txid in chunk {
+                let tx_hash = txid.string().map_err(RpcClientError::ChainHash)?;
+
+                let future =
+                    if verbose {
+                        Either::Left(self.get_raw_transaction_verbose(tx_hash.clone()).await?)
+                    } else {
+                        Either::Right(self.get_raw_transaction_hex(tx_hash.clone()).await?.map(
+                            |hex| {
+                                hex.map(|hex| TxRawResult {
+                                    hex: Some(hex),
+                                    ..Default::default()
+                                })
+                            },
+                        ))
+                    };
+
+                futures.push((tx_hash, future));
+            }
+
+            for (tx_hash, future) in futures {
+                transactions.push(future.await.map(|mut tx| {
+                    tx.tx_id.get_or_insert(tx_hash);
+                    tx
+                }));
+            }
+        }
+
+        Ok(transactions)
+    }
+
+    /// send_raw_transaction submits the serialized transaction to the server's mempool to be
+    /// relayed, returning its hash. Transactions paying a fee above the server's maximum are
+    /// rejected unless allow_high_fees is set.
+    ///
+    /// Transactions the server refuses, such as transactions already in the mempool or chain,
+    /// failing to decode or breaking a mempool rule like spending missing outputs, fail with a
+    /// TransactionRejected error holding the server's error. Other server errors, such as the
+    /// server warming up or the user not being authorized, are returned unchanged.
+    pub async fn send_raw_transaction(
+        &mut self,
+        tx: &[u8],
+        allow_high_fees: bool,
+    ) -> Result<Hash, RpcClientError> {
+        check_config!(self);
+
+        let deadline = self.request_deadline(commands::METHOD_SEND_RAW_TRANSACTION);
+
+        let (_, message) = self
+            .send_custom_command(
+                commands::METHOD_SEND_RAW_TRANSACTION,
+                &[
+                    serde_json::json!(hex::encode(tx)),
+                    serde_json::json!(allow_high_fees),
+                ],
+            )
+            .await?;
+
+        let sent = future_type::SendRawTransactionFuture { message };
+
+        let sent = match deadline {
+            Some(deadline) => time::timeout_at(deadline, sent)
+                .await
+                .unwrap_or(Err(RpcServerError::Timeout)),
+
+            None => sent.await,
+        };
+
+        match sent {
+            Ok(hash) => Ok(hash),
+
+            Err(RpcServerError::ServerError(e)) if is_rejection(e.code) => {
+                Err(RpcClientError::TransactionRejected(e))
+            }
+
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// test_mempool_accept returns whether each of the serialized transactions would be accepted
+    /// to the server's mempool, without relaying any of them. Results are in the order the
+    /// transactions were passed.
+    ///
+    /// Servers without `testmempoolaccept` are handled by decoding each transaction with
+    /// `decoderawtransaction` and checking it against the policy rules that don't need the
+    /// chain state: it must decode, have inputs and outputs, not be a coinbase and not spend
+    /// more than its inputs. The simulation can't tell whether the inputs exist or are unspent,
+    /// or whether their scripts are satisfied, so an allowed transaction may still be rejected
+    /// when sent. The transaction fee is computed from the input amounts committed to by the
+    /// transaction.
+    pub async fn test_mempool_accept(
+        &mut self,
+        txs: &[&[u8]],
+    ) -> Result<Vec<MempoolAcceptResult>, RpcClientError> {
+        check_config!(self);
+
+        let serialized_txs: Vec<String> = txs.iter().map(hex::encode).collect();
+
+        let deadline = self.request_deadline(commands::METHOD_TEST_MEMPOOL_ACCEPT);
+
+        let (_, message) = self
+            .send_custom_command(
+                commands::METHOD_TEST_MEMPOOL_ACCEPT,
+                &[serde_json::json!(serialized_txs)],
+            )
+            .await?;
+
+        let accepted = future_type::TestMempoolAcceptFuture::new(message)
+            .with_policy(self.conn.deserialization_policy())
+            .with_deadline(deadline)
+            .await;
+
+        match accepted {
+            Err(e) if e.code() == Some(RpcErrorCode::MethodNotFound) => {
+                trace!("Server has no testmempoolaccept, simulating mempool acceptance");
+            }
+
+            result => return Ok(result?),
+        }
+
+        let mut futures = Vec::with_capacity(serialized_txs.len());
+        for serialized_tx in serialized_txs {
+            let deadline = self.request_deadline(commands::METHOD_DECODE_RAW_TRANSACTION);
+
+            let (_, message) = self
+                .send_custom_command(
+                    commands::METHOD_DECODE_RAW_TRANSACTION,
+                    &[serde_json::json!(serialized_tx)],
+                )
+                .await?;
+
+            futures.push(
+                future_type::DecodeRawTransactionFuture::new(message)
+                    .with_policy(self.conn.deserialization_policy())
+                    .with_deadline(deadline),
+            );
+        }
+
+        let mut results = Vec::with_capacity(futures.len());
+        for future in futures {
+            results.push(simulate_mempool_accept(future.await)?);
+        }
+
+        Ok(results)
+    }
+
+    /// get_transaction_any returns information about the transaction with the given hash,
+    /// falling back to searching the transactions of `addresses` when the server can't find it.
+    ///
+    /// Servers running without a transaction index only look up mempool transactions with
+    /// `getrawtransaction`. When the transaction is not found, `searchrawtransactions` is
+    /// queried for each of the passed addresses, such as the addresses the transaction is
+    /// known to pay, until the transaction is found. Searching requires the server to run with
+    /// an address index and pages through every transaction of an address, so it is only
+    /// practical for addresses with few transactions.
+    ///
+    /// If the transaction is not found, or no addresses are passed, a server error advising to
+    /// run the server with `--txindex` is returned.
+    pub async fn get_transaction_any(
+        &mut self,
+        txid: &Hash,
+        addresses: &[&str],
+    ) -> Result<TxRawResult, RpcClientError> {
+        let tx_hash = txid.string().map_err(RpcClientError::ChainHash)?;
+
+        match self
+            .get_raw_transaction_verbose(tx_hash.clone())
+            .await?
+            .await
+        {
+            Ok(tx) => return Ok(tx),
+
+            Err(RpcServerError::ServerError(e)) if e.code == constants::ERR_RPC_NO_TX_INFO => {
+                trace!(
+                    "Transaction not found, searching address transactions, error: {}",
+                    e.message
+                );
+            }
+
+            Err(e) => return Err(e.into()),
+        };
+
+        for address in addresses {
+            let mut skip = 0;
+
+            loop {
+                let txs = match self
+                    .search_raw_transactions_verbose(
+                        address.to_string(),
+                        skip,
+                        constants::SEARCH_RAW_TRANSACTIONS_COUNT,
+                    )
+                    .await?
+                    .await
+                {
+                    Ok(txs) => txs,
+
+                    // Reported for addresses without transactions and for pages past the last
+                    // transaction of an address.
+                    Err(RpcServerError::ServerError(e))
+                        if e.code == constants::ERR_RPC_NO_TX_INFO =>
+                    {
+                        trace!(
+                            "No more transactions for address {}, error: {}",
+                            address,
+                            e.message
+                        );
+                        break;
+                    }
+
+                    Err(e) => return Err(e.into()),
+                };
+
+                let page_len = txs.len() as i32;
+
+                if let Some(tx) = txs
+                    .into_iter()
+                    .find(|tx| tx.tx_id.as_deref() == Some(tx_hash.as_str()))
+                {
+                    return Ok(tx);
+                }
+
+                if page_len < constants::SEARCH_RAW_TRANSACTIONS_COUNT {
+                    break;
+                }
+
+                skip += page_len;
+            }
+        }
+
+        Err(RpcServerError::ServerError(RpcError {
+            code: constants::ERR_RPC_NO_TX_INFO,
+            message: format!(
+                "transaction {} is not in the mempool and was not found in the transactions of \
+                 the given addresses, run the server with --txindex to look up confirmed transactions",
+                tx_hash
+            ),
+        })
+        .into())
+    }
+
+    /// wait_for_confirmation waits until the transaction with the given hash has at least
+    /// `confirmations` confirmations and returns its confirmation count.
+    ///
+    /// The client registers for block notifications and checks the confirmations of the
+    /// transaction each time a block is connected. If the server refuses the registration, or
+    /// notifications end, confirmations are polled instead. Block notifications not registered
+    /// by the user are unregistered once the wait ends.
+    ///
+    /// Once the transaction has been mined, it no longer being in a main chain block means it
+    /// was reorganized out, and `TransactionReorganized` is returned even if it could be mined
+    /// again. `ConfirmationTimeout` is returned if the transaction isn't confirmed in time.
+    pub async fn wait_for_confirmation(
+        &mut self,
+        txid: &Hash,
+        confirmations: i64,
+        timeout: Duration,
+    ) -> Result<i64, RpcClientError> {
+        check_config!(self);
+
+        let tx_hash = txid.string().map_err(RpcClientError::ChainHash)?;
+
+        // Subscribe before registering so no block connected after registration is missed.
+        let mut blocks = Some(
+            self.subscribe_notifications(commands::NOTIFICATION_METHOD_BLOCK_CONNECTED)
+                .await,
+        );
+
+        // Held until the wait ends.
+        let _registration = match self
+            .register_for_helper(
+                commands::METHOD_NOTIFY_BLOCKS,
+                commands::METHOD_STOP_NOTIFY_BLOCKS,
+                &[],
+            )
+            .await
+        {
+            Ok(registration) => Some(registration),
+
+            Err(RpcClientError::RpcServer(e)) => {
+                warn!(
+                    "Block notifications unavailable, polling for confirmations, error: {}",
+                    e
+                );
+                blocks = None;
+                None
+            }
+
+            Err(e) => return Err(e),
+        };
+
+        let wait = async {
+            let mut mined = false;
+
+            loop {
+                match self.confirmations(txid).await? {
+                    Some(count) if count >= confirmations => return Ok(count),
+
+                    Some(_) => mined = true,
+
+                    None if mined => {
+                        return Err(RpcClientError::TransactionReorganized(tx_hash.clone()))
+                    }
+
+                    None => {}
+                }
+
+                match blocks.as_mut() {
+                    Some(notifications) => {
+                        if notifications.recv().await.is_none() {
+                            trace!("Block notifications ended, polling for confirmations");
+                            blocks = None;
+                        }
+                    }
+
+                    None => tokio::time::sleep(constants::CONFIRMATION_POLL_INTERVAL).await,
+                }
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(result) => result,
+
+            Err(_) => Err(RpcClientError::ConfirmationTimeout(tx_hash)),
+        }
+    }
+
+    /// wait_until_ready waits until the server has finished its initial block download, as
+    /// reported by `getblockchaininfo`, so queries aren't answered from a chain still syncing.
+    /// The server is polled at an exponentially increasing interval, from 100 milliseconds up
+    /// to 10 seconds. ReadinessTimeout is returned if the server is still syncing after timeout.
+    pub async fn wait_until_ready(&mut self, timeout: Duration) -> Result<(), RpcClientError> {
+        let mut backoff = Exponential {
+            initial: constants::READINESS_POLL_INTERVAL,
+            max: constants::MAX_READINESS_POLL_INTERVAL,
+        };
+
+        let wait = async {
+            let mut polls = 0;
+
+            loop {
+                let info = self.get_blockchain_info().await?.await?;
+
+                if !info.initial_block_download {
+                    return Ok(());
+                }
+
+                polls += 1;
+
+                trace!("Server is in initial block download, polling again");
+                tokio::time::sleep(backoff.next_delay(polls)).await;
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(result) => result,
+
+            Err(_) => Err(RpcClientError::ReadinessTimeout),
+        }
+    }
+
+    /// peer_latency_summary returns the minimum, median, 95th percentile and maximum ping round
+    /// trip times of the peers connected to the server, as reported by `getpeerinfo`. Peers
+    /// that have not answered a ping, reporting no or a zero ping time, are only counted.
+    pub async fn peer_latency_summary(&mut self) -> Result<LatencySummary, RpcClientError> {
+        let peers = self.get_peer_info().await?.await?;
+
+        let mut ping_times: Vec<Duration> = peers
+            .iter()
+            .filter(|peer| peer.ping_time.is_finite() && peer.ping_time > 0.0)
+            .map(|peer| Duration::from_secs_f64(peer.ping_time / 1_000_000.0))
+            .collect();
+        ping_times.sort_unstable();
+
+        let unmeasured = peers.len() - ping_times.len();
+
+        if ping_times.is_empty() {
+            return Ok(LatencySummary {
+                unmeasured,
+                ..Default::default()
+            });
+        }
+
+        let count = ping_times.len();
+        let median = if count.is_multiple_of(2) {
+            (ping_times[count / 2 - 1] + ping_times[count / 2]) / 2
+        } else {
+            ping_times[count / 2]
+        };
+
+        // Nearest rank percentile, rounding the rank up.
+        let p95 = ping_times[(count * 95).div_ceil(100) - 1];
+
+        Ok(LatencySummary {
+            peers: count,
+            unmeasured,
+            min: ping_times[0],
+            median,
+            p95,
+            max: ping_times[count - 1],
+        })
+    }
+
+    /// tip returns the hash and height of the best block.
+    ///
+    /// Requesting the hash and height separately can pair the hash of one block with the height
+    /// of the next when a block is connected in between, so `getbestblock`, which returns both
+    /// together, is used. Servers without `getbestblock` fall back to `getblockcount` followed
+    /// by `getblockhash` at the returned height. The pair is then always consistent, though
+    /// the returned block may already have been superseded by a new tip.
+    pub async fn tip(&mut self) -> Result<(Hash, i64), RpcClientError> {
+        match self.get_best_block().await?.await {
+            Ok(best_block) => return Ok((best_block.hash, best_block.height)),
+
+            Err(e) if e.code() == Some(RpcErrorCode::MethodNotFound) => {
+                trace!("Server has no getbestblock, requesting block count and hash");
+            }
+
+            Err(e) => return Err(e.into()),
+        }
+
+        let height = self.get_block_count().await?.await?;
+        let hash = self.get_block_hash(height).await?.await?;
+
+        Ok((hash, height))
+    }
+
+    /// blocks_until_stake_change returns the number of blocks until the stake difficulty changes,
+    /// including the block starting the next ticket price window, together with the expected
+    /// stake difficulty of that window as estimated by `estimatestakediff`.
+    ///
+    /// Windows are sized by the network detected with `detect_and_set_network`, or by
+    /// `chaincfg::params::MAINNET` when the network has not been detected.
+    pub async fn blocks_until_stake_change(&mut self) -> Result<(i64, Amount), RpcClientError> {
+        let window_size = self
+            .network
+            .as_ref()
+            .unwrap_or(&params::MAINNET)
+            .stake_diff_window_size;
+
+        let block_count = self.get_block_count().await?;
+        let estimate = self.estimate_stake_diff(None).await?;
+
+        let height = block_count.await?;
+        let estimate = estimate.await?;
+
+        Ok((window_size - height % window_size, estimate.expected))
+    }
+
+    /// is_output_spendable returns whether the output of transaction txid at index vout in the
+    /// given transaction tree is unspent, counting outputs spent by mempool transactions as
+    /// spent. Outputs that don't exist are reported as not spendable.
+    pub async fn is_output_spendable(
+        &mut self,
+        txid: &Hash,
+        vout: u32,
+        tree: i8,
+    ) -> Result<bool, RpcClientError> {
+        let txid = txid.string().map_err(RpcClientError::ChainHash)?;
+
+        let tx_out = self.get_tx_out(txid, vout, tree, true).await?.await?;
+
+        Ok(tx_out.is_some())
+    }
+
+    /// get_verified_block fetches the serialized block of the given hash and checks it against
+    /// its header, so a node serving a tampered block is detected. The hash of the block header
+    /// must be the requested hash and the transactions must match the header's merkle roots, as
+    /// checked by `Block::verify`. A Block error is returned when verification fails.
+    pub async fn get_verified_block(
+        &mut self,
+        hash: &Hash,
+    ) -> Result<VerifiedBlock, RpcClientError> {
+        let block_hash = hash.string().map_err(RpcClientError::ChainHash)?;
+
+        let block = self.get_block(block_hash).await?.await?;
+
+        let block = Block::deserialize(&block).map_err(RpcClientError::Block)?;
+        block.verify(hash).map_err(RpcClientError::Block)?;
+
+        Ok(VerifiedBlock {
+            hash: hash.clone(),
+            block,
+        })
+    }
+
+    /// detect_and_set_network requests the network the server is on with `getcurrentnet`, stores
+    /// it on the client and returns it. Helpers depending on network parameters, such as
+    /// `decode_address` and `get_coin_supply_breakdown`, use the stored network from then on.
+    /// An UnknownNetwork error is returned for networks not in `chaincfg::params::NETWORKS`.
+    pub async fn detect_and_set_network(&mut self) -> Result<Network, RpcClientError> {
+        let net = self.get_current_net().await?.await?;
+
+        let network = params::NETWORKS
+            .iter()
+            .find(|network| network.net == net)
+            .cloned()
+            .ok_or(RpcClientError::UnknownNetwork(net))?;
+
+        self.network = Some(network.clone());
+
+        Ok(network)
+    }
+
+    /// Returns the network detected with `detect_and_set_network`, if any.
+    pub fn network(&self) -> Option<&Network> {
+        self.network.as_ref()
+    }
+
+    /// decode_address decodes an address of the network detected with `detect_and_set_network`,
+    /// failing with an address error for addresses of other networks. A NetworkNotDetected error
+    /// is returned if the network has not been detected.
+    pub fn decode_address(&self, addr: &str) -> Result<address::DecodedAddress, RpcClientError> {
+        let network = self
+            .network
+            .as_ref()
+            .ok_or(RpcClientError::NetworkNotDetected)?;
+
+        address::decode(addr, network).map_err(RpcClientError::Address)
+    }
+
+    /// get_genesis_block returns the decoded genesis block of the server's chain. Transactions
+    /// are returned as hashes only.
+    pub async fn get_genesis_block(&mut self) -> Result<GetBlockVerboseResult, RpcClientError> {
+        let genesis_hash = self.get_block_hash(0).await?.await?;
+        let genesis_hash = genesis_hash.string().map_err(RpcClientError::ChainHash)?;
+
+        Ok(self.get_block_verbose(genesis_hash, false).await?.await?)
+    }
+
+    /// verify_genesis checks that the server's genesis block hash matches `expected`, which is
+    /// usually the `genesis_hash` of a network in `chaincfg::params`. A GenesisMismatch error is
+    /// returned when the server is on a different chain.
+    ///
+    /// Calling this once after connecting guards against issuing writes to the wrong network.
+    pub async fn verify_genesis(&mut self, expected: &Hash) -> Result<(), RpcClientError> {
+        let genesis_hash = self.get_block_hash(0).await?.await?;
+
+        if genesis_hash != *expected {
+            return Err(RpcClientError::GenesisMismatch {
+                expected: expected.string().map_err(RpcClientError::ChainHash)?,
+                found: genesis_hash.string().map_err(RpcClientError::ChainHash)?,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// mempool_updates returns a stream of changes to the server's memory pool. The first delta
+    /// adds every transaction in the memory pool when called, and later deltas are computed by
+    /// diffing `getrawmempool` against the previous snapshot every poll_interval.
+    ///
+    /// On websocket connections the client is also registered for non-verbose `txaccepted`
+    /// notifications, so additions are delivered as soon as they are notified rather than on the
+    /// next poll. Removals are only detected by polling. Failed polls are logged and retried on
+    /// the next interval, and the stream ends once the client is disconnected. Transaction
+    /// notifications not registered by the user are unregistered once the stream is dropped, and
+    /// verbose notifications registered by the user leave additions to be detected by polling.
+    pub async fn mempool_updates(
+        &mut self,
+        poll_interval: Duration,
+    ) -> Result<impl Stream<Item = MempoolDelta> + '_, RpcClientError> {
+        check_config!(self);
+
+        let mut accepted = None;
+
+        if !self.conn.is_http_mode() {
+            // Subscribe before registering so no transaction accepted after registration is missed.
+            let notifications = self
+                .subscribe_notifications(commands::NOTIFICATION_METHOD_TX_ACCEPTED)
+                .await;
+
+            match self
+                .register_for_helper(
+                    commands::METHOD_NOTIFY_NEW_TX,
+                    commands::METHOD_STOP_NOTIFY_NEW_TX,
+                    &[serde_json::json!(false)],
+                )
+                .await
+            {
+                Ok(registration) => accepted = Some((notifications, registration)),
+
+                Err(RpcClientError::RpcServer(e)) => warn!(
+                    "Transaction notifications unavailable, polling for additions, error: {}",
+                    e
+                ),
+
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mempool: HashSet<Hash> = self.get_raw_mempool().await?.await?.into_iter().collect();
+
+        let first = MempoolDelta {
+            added: mempool.iter().cloned().collect(),
+            removed: Vec::new(),
+        };
+
+        Ok(stream::unfold(
+            (self, mempool, accepted, Some(first)),
+            move |(client, mut mempool, mut accepted, first)| async move {
+                if let Some(first) = first {
+                    return Some((first, (client, mempool, accepted, None)));
+                }
+
+                let poll = tokio::time::sleep(poll_interval);
+                tokio::pin!(poll);
+
+                loop {
+                    let notification = async {
+                        match accepted.as_mut() {
+                            Some((notifications, _)) => notifications.recv().await,
+
+                            None => std::future::pending().await,
+                        }
+                    };
+
+                    tokio::select! {
+                        notification = notification => {
+                            let notification = match notification {
+                                Some(notification) => notification,
+
+                                None => {
+                                    trace!("Transaction notifications ended, polling for additions");
+                                    accepted = None;
+                                    continue;
+                                }
+                            };
+
+                            if let Some((txid, _)) =
+                                chain_notification::parse_tx_accepted(&notification.params)
+                            {
+                                if mempool.insert(txid.clone()) {
+                                    let delta = MempoolDelta {
+                                        added: vec![txid],
+                                        removed: Vec::new(),
+                                    };
+
+                                    return Some((delta, (client, mempool, accepted, None)));
+                                }
+                            }
+                        }
+
+                        _ = &mut poll => {
+                            poll.as_mut()
+                                .reset(tokio::time::Instant::now() + poll_interval);
+
+                            let current = match client.get_raw_mempool().await {
+                                Ok(future) => future.await,
+
+                                Err(e) => {
+                                    warn!("Mempool updates ended, error: {}", e);
+                                    return None;
+                                }
+                            };
+
+                            let current: HashSet<Hash> = match current {
+                                Ok(current) => current.into_iter().collect(),
+
+                                Err(e) => {
+                                    warn!("Error polling mempool, error: {}", e);
+                                    continue;
+                                }
+                            };
+
+                            let delta = diff_mempool(&mempool, &current);
+                            mempool = current;
+
+                            if delta != MempoolDelta::default() {
+                                return Some((delta, (client, mempool, accepted, None)));
+                            }
+                        }
+                    }
+                }
+            },
+        ))
+    }
+
+    /// block_stream returns a stream of the main chain blocks from start_height to the tip, with
+    /// transactions returned as hashes only. Once the tip is reached, the stream waits for new
+    /// blocks if block notifications could be registered, and ends otherwise.
+    ///
+    /// A block not extending the previously streamed block means the chain was reorganized.
+    /// The stream then steps back to the fork point and streams the new main chain blocks from
+    /// there, so a block is emitted again at each height it was replaced at. Reorganizations
+    /// deeper than the last 256 streamed blocks are not detected.
+    ///
+    /// Errors are streamed without advancing, so polling again retries the failed request. Block
+    /// notifications not registered by the user are unregistered once the stream is dropped.
+    pub fn block_stream(
+        &mut self,
+        start_height: i64,
+    ) -> impl Stream<Item = Result<GetBlockVerboseResult, RpcClientError>> + '_ {
+        let state = BlockStreamState {
+            next_height: start_height,
+            recent: VecDeque::new(),
+            blocks: None,
+            registration: None,
+            registered: false,
+        };
+
+        stream::unfold((self, state), |(client, mut state)| async move {
+            let block = client.next_streamed_block(&mut state).await.transpose()?;
+
+            Some((block, (client, state)))
+        })
+    }
+
+    /// Returns the next block of a block_stream, or None once the stream ends.
+    async fn next_streamed_block(
+        &mut self,
+        state: &mut BlockStreamState,
+    ) -> Result<Option<GetBlockVerboseResult>, RpcClientError> {
+        if !state.registered && !self.conn.is_http_mode() {
+            // Subscribe before registering so no block connected after registration is missed.
+            let notifications = self
+                .subscribe_notifications(commands::NOTIFICATION_METHOD_BLOCK_CONNECTED)
+                .await;
+
+            match self
+                .register_for_helper(
+                    commands::METHOD_NOTIFY_BLOCKS,
+                    commands::METHOD_STOP_NOTIFY_BLOCKS,
+                    &[],
+                )
+                .await
+            {
+                Ok(registration) => {
+                    state.blocks = Some(notifications);
+                    state.registration = Some(registration);
+                }
+
+                Err(RpcClientError::RpcServer(e)) => warn!(
+                    "Block notifications unavailable, block stream ends at the tip, error: {}",
+                    e
+                ),
+
+                Err(e) => return Err(e),
+            }
+        }
+
+        state.registered = true;
+
+        loop {
+            let tip = self.get_block_count().await?.await?;
+
+            if state.next_height > tip {
+                match state.blocks.as_mut() {
+                    Some(notifications) => match notifications.recv().await {
+                        Some(_) => continue,
+
+                        None => {
+                            trace!("Block notifications ended, ending block stream");
+                            return Ok(None);
+                        }
+                    },
+
+                    None => return Ok(None),
+                }
+            }
+
+            let hash = self.get_block_hash(state.next_height).await?.await?;
+            let hash = hash.string().map_err(RpcClientError::ChainHash)?;
+
+            let block = self.get_block_verbose(hash, false).await?.await?;
+
+            if let Some(parent) = state.recent.back() {
+                if block.previous_hash != *parent {
+                    trace!(
+                        "Block {} does not extend streamed block {}, stepping back",
+                        block.hash,
+                        parent
+                    );
+
+                    state.recent.pop_back();
+                    state.next_height -= 1;
+                    continue;
+                }
+            }
+
+            state.recent.push_back(block.hash.clone());
+            if state.recent.len() > constants::BLOCK_STREAM_REORG_DEPTH {
+                state.recent.pop_front();
+            }
+
+            state.next_height += 1;
+
+            return Ok(Some(block));
+        }
+    }
+
+    /// stake_difficulty_changes registers the client for `stakedifficulty` notifications and
+    /// returns a stream of the updates. The registration is renewed on reconnection and the
+    /// stream ends once the client's notification handler exits.
+    ///
+    /// Updates are delivered whether or not an on_stake_difficulty callback is set, so no
+    /// notification handler is required.
+    pub async fn stake_difficulty_changes(
+        &mut self,
+    ) -> Result<impl Stream<Item = StakeDifficultyUpdate>, RpcClientError> {
+        check_config!(self);
+
+        // Subscribe before registering so no update sent after registration is missed.
+        let notifications = self
+            .subscribe_notifications(commands::NOTIFICATION_METHOD_STAKE_DIFFICULTY)
+            .await;
+
+        self.create_notification(commands::METHOD_NOTIFY_STAKE_DIFFICULTY, &[])
+            .await?
+            .await?;
+
+        Ok(stream::unfold(
+            notifications,
+            |mut notifications| async move {
+                loop {
+                    let notification = notifications.recv().await?;
+
+                    if let Some((hash, height, stake_diff)) =
+                        chain_notification::parse_stake_difficulty(&notification.params)
+                    {
+                        let update = StakeDifficultyUpdate {
+                            hash,
+                            height,
+                            stake_difficulty: Amount::from(stake_diff),
+                        };
+
+                        return Some((update, notifications));
+                    }
+                }
+            },
+        ))
+    }
+
+    /// winning_tickets_channel registers the client for `winningtickets` notifications and
+    /// returns a channel receiving the tickets chosen to vote on each connected block. The
+    /// registration is renewed on reconnection and the channel closes once the client's
+    /// notification handler exits. Malformed notifications are skipped.
+    ///
+    /// Events are delivered whether or not an on_winning_tickets callback is set, so no
+    /// notification handler is required. Dropping the receiver ends the subscription.
+    pub async fn winning_tickets_channel(
+        &mut self,
+    ) -> Result<mpsc::Receiver<WinningTicketsEvent>, RpcClientError> {
+        check_config!(self);
+
+        // Subscribe before registering so no event sent after registration is missed.
+        let mut notifications = self
+            .subscribe_notifications(commands::NOTIFICATION_METHOD_WINNING_TICKETS)
+            .await;
+
+        self.create_notification(commands::METHOD_NOTIFY_WINNING_TICKETS, &[])
+            .await?
+            .await?;
+
+        let (sender, receiver) = mpsc::channel(constants::WINNING_TICKETS_CHANNEL_SIZE);
+
+        logging::spawn(async move {
+            while let Some(notification) = notifications.recv().await {
+                let (hash, height, tickets) =
+                    match chain_notification::parse_winning_tickets(&notification.params) {
+                        Some(e) => e,
+
+                        None => continue,
+                    };
+
+                let event = WinningTicketsEvent {
+                    hash,
+                    height,
+                    tickets,
+                };
+
+                if sender.send(event).await.is_err() {
+                    trace!("Winning tickets receiver dropped");
+                    return;
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+}
+
+/// Returns the transactions added to and removed from the previous memory pool snapshot.
+fn diff_mempool(previous: &HashSet<Hash>, current: &HashSet<Hash>) -> MempoolDelta {
+    MempoolDelta {
+        added: current.difference(previous).cloned().collect(),
+        removed: previous.difference(current).cloned().collect(),
+    }
+}
+
+/// Checks a decoded transaction against the mempool policy rules that don't require the chain
+/// state. Transactions the server failed to decode are rejected and other errors are returned.
+fn simulate_mempool_accept(
+    tx: Result<TxRawResult, RpcServerError>,
+) -> Result<MempoolAcceptResult, RpcClientError> {
+    let tx = match tx {
+        Ok(tx) => tx,
+
+        Err(RpcServerError::ServerError(e)) if e.code == RpcErrorCode::Deserialization => {
+            return Ok(MempoolAcceptResult {
+                reject_reason: Some(e.message),
+                ..Default::default()
+            });
+        }
+
+        Err(e) => return Err(e.into()),
+    };
+
+    let reject = |reason: &str| MempoolAcceptResult {
+        txid: tx.tx_id.clone().unwrap_or_default(),
+        reject_reason: Some(reason.to_string()),
+        ..Default::default()
+    };
+
+    if tx.vin.is_empty() {
+        return Ok(reject("transaction has no inputs"));
+    }
+
+    if tx.vout.is_empty() {
+        return Ok(reject("transaction has no outputs"));
+    }
+
+    if tx.vin.iter().any(|vin| vin.is_coin_base()) {
+        return Ok(reject(
+            "coinbase transactions are not accepted to the mempool",
+        ));
+    }
+
+    let amount_in = tx
+        .vin
+        .iter()
+        .try_fold(0i64, |total, vin| total.checked_add(vin.amount_in.into()));
+    let amount_out = tx.vout.iter().try_fold(0i64, |total, vout| {
+        let value: i64 = vout.value.into();
+        if value < 0 {
+            return None;
+        }
+
+        total.checked_add(value)
+    });
+
+    let fee = match (amount_in, amount_out) {
+        (Some(amount_in), Some(amount_out)) if amount_in >= amount_out => amount_in - amount_out,
+
+        (_, None) => return Ok(reject("transaction output values are out of range")),
+
+        _ => return Ok(reject("transaction outputs exceed its inputs")),
+    };
+
+    Ok(MempoolAcceptResult {
+        txid: tx.tx_id.unwrap_or_default(),
+        allowed: true,
+        fee: Some(Amount::from(fee)),
+        ..Default::default()
+    })
+}
+
+/// Returns inclusive height ranges up to `height` over which the block subsidy is constant.
+/// The genesis block pays no subsidy and is skipped.
+fn subsidy_segments(height: i64, params: &Network) -> Vec<(i64, i64)> {
+    let mut segments = Vec::new();
+
+    if height >= 1 {
+        segments.push((1, 1));
+    }
+
+    let mut start = 2;
+    while start <= height {
+        let mut end =
+            (start / params.subsidy_reduction_interval + 1) * params.subsidy_reduction_interval - 1;
+
+        if start < params.stake_validation_height {
+            end = end.min(params.stake_validation_height - 1);
+        }
+
+        end = end.min(height);
+        segments.push((start, end));
+        start = end + 1;
+    }
+
+    segments
+}
@@ -7,13 +7,24 @@ use std::collections::HashMap;
 use super::connection::RPCConn;
 
 use {
-    super::{check_config, error::RpcClientError, future_type::NotificationsFuture},
+    super::{
+        check_config,
+        error::RpcClientError,
+        future_type::NotificationsFuture,
+        infrastructure::{Command, HelperRegistrations, NotificationState},
+        logging,
+    },
     crate::{
         chaincfg::chainhash::Hash,
-        dcrjson::{commands, marshal_to_hash, parse_hex_parameters, result_types},
+        dcrjson::{cmd_types, commands, marshal_to_hash, parse_hex_parameters, result_types},
         rpcclient::client::Client,
     },
     log::{trace, warn},
+    std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    tokio::sync::mpsc,
 };
 
 macro_rules! notification_generator {
@@ -64,6 +75,82 @@ macro_rules! callback_check {
     };
 }
 
+/// NotificationKinds is a set of notifications registered together by `notify_all`. Kinds
+/// are combined with `|`, as in `NotificationKinds::BLOCKS | NotificationKinds::WORK`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NotificationKinds(u8);
+
+impl NotificationKinds {
+    /// Block connected and disconnected notifications, registered with notify_blocks.
+    pub const BLOCKS: Self = Self(1);
+    /// New block template notifications, registered with notify_work.
+    pub const WORK: Self = Self(1 << 1);
+    /// Matured ticket notifications, registered with notify_new_tickets.
+    pub const NEW_TICKETS: Self = Self(1 << 2);
+    /// Spent and missed ticket notifications, registered with notify_spent_and_missed_tickets.
+    pub const SPENT_AND_MISSED_TICKETS: Self = Self(1 << 3);
+    /// Stake difficulty notifications, registered with notify_stake_difficulty.
+    pub const STAKE_DIFFICULTY: Self = Self(1 << 4);
+    /// Non-verbose transaction accepted notifications, registered with notify_new_transactions.
+    pub const NEW_TRANSACTIONS: Self = Self(1 << 5);
+    /// Winning ticket notifications, registered with notify_winning_tickets.
+    pub const WINNING_TICKETS: Self = Self(1 << 6);
+
+    /// Every kind with its registration command, in registration order.
+    const KINDS: [(Self, &'static str); 7] = [
+        (Self::BLOCKS, commands::METHOD_NOTIFY_BLOCKS),
+        (Self::WORK, commands::METHOD_NOTIFIY_NEW_WORK),
+        (Self::NEW_TICKETS, commands::METHOD_NOTIFY_NEW_TICKETS),
+        (
+            Self::SPENT_AND_MISSED_TICKETS,
+            commands::METHOD_NOTIFY_SPEND_AND_MISSED_TICKETS,
+        ),
+        (
+            Self::STAKE_DIFFICULTY,
+            commands::METHOD_NOTIFY_STAKE_DIFFICULTY,
+        ),
+        (Self::NEW_TRANSACTIONS, commands::METHOD_NOTIFY_NEW_TX),
+        (
+            Self::WINNING_TICKETS,
+            commands::METHOD_NOTIFY_WINNING_TICKETS,
+        ),
+    ];
+
+    /// Returns the empty set.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the set of every kind.
+    pub const fn all() -> Self {
+        Self(0b111_1111)
+    }
+
+    /// Returns whether every kind in other is in the set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns whether the set is empty.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl std::ops::BitOr for NotificationKinds {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl std::ops::BitOrAssign for NotificationKinds {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
 macro_rules! create_notif_future {
     ($self: ident, $command: expr, $param: expr) => {{
         let notif_future = $self.create_notification($command, $param).await;
@@ -86,7 +173,7 @@ impl<C: 'static + RPCConn> Client<C> {
         NotificationsFuture,
         commands::METHOD_NOTIFY_BLOCKS,
         &[],
-        all_defined(on_block_connected, on_block_disconnected),
+        either_defined(on_block_connected, on_block_disconnected, on_block_disconnected_header),
         ()
     );
 
@@ -152,24 +239,389 @@ impl<C: 'static + RPCConn> Client<C> {
         ()
     );
 
-    async fn create_notification(
+    notification_generator!(
+        "notify_stake_difficulty registers the client to receive notifications when
+        blocks are connected to the main chain and the stake difficulty is updated.
+        The notifications are delivered to the notification handlers associated with the client.
+        Calling this function has no effect if there are no notification handlers and will result in
+        an error if the client is configured to run in HTTP POST mode.
+        \nThe notifications delivered as a result of this call will be those from OnStakeDifficulty.
+        \n**NOTE: This is a dcrd extension and requires a websocket connection.**",
+        notify_stake_difficulty,
+        NotificationsFuture,
+        commands::METHOD_NOTIFY_STAKE_DIFFICULTY,
+        &[],
+        all_defined(on_stake_difficulty),
+        ()
+    );
+
+    notification_generator!(
+        "notify_winning_tickets registers the client to receive notifications when
+        blocks are connected to the main chain and tickets are chosen to vote on them.
+        The notifications are delivered to the notification handlers associated with the client.
+        Calling this function has no effect if there are no notification handlers and will result in
+        an error if the client is configured to run in HTTP POST mode.
+        \nThe notifications delivered as a result of this call will be those from OnWinningTickets.
+        \n**NOTE: This is a dcrd extension and requires a websocket connection.**",
+        notify_winning_tickets,
+        NotificationsFuture,
+        commands::METHOD_NOTIFY_WINNING_TICKETS,
+        &[],
+        all_defined(on_winning_tickets),
+        ()
+    );
+
+    /// notify_all registers every notification kind in which, sending all registrations before
+    /// waiting on the server's replies. Every kind is attempted even if another fails to
+    /// register, and a NotificationRegistration error lists each failed registration command
+    /// with its error. Kinds without a notification handler set fail as with their individual
+    /// registration method.
+    pub async fn notify_all(&mut self, which: NotificationKinds) -> Result<(), RpcClientError> {
+        check_config!(self);
+
+        let mut registrations = Vec::new();
+        let mut failures = Vec::new();
+
+        for (kind, method) in NotificationKinds::KINDS {
+            if !which.contains(kind) {
+                continue;
+            }
+
+            let registration = match kind {
+                NotificationKinds::BLOCKS => self.notify_blocks().await,
+                NotificationKinds::WORK => self.notify_work().await,
+                NotificationKinds::NEW_TICKETS => self.notify_new_tickets().await,
+                NotificationKinds::SPENT_AND_MISSED_TICKETS => {
+                    self.notify_spent_and_missed_tickets().await
+                }
+                NotificationKinds::STAKE_DIFFICULTY => self.notify_stake_difficulty().await,
+                NotificationKinds::WINNING_TICKETS => self.notify_winning_tickets().await,
+                _ => self.notify_new_transactions(false).await,
+            };
+
+            match registration {
+                Ok(registration) => registrations.push((method, registration)),
+
+                Err(e) => failures.push((method.to_string(), e)),
+            }
+        }
+
+        for (method, registration) in registrations {
+            if let Err(e) = registration.await {
+                failures.push((method.to_string(), e.into()));
+            }
+        }
+
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        Err(RpcClientError::NotificationRegistration(failures))
+    }
+
+    /// available_notifications returns the notification registration commands, such as
+    /// `notifyblocks`, this client can register and dispatch notifications for.
+    pub fn available_notifications(&self) -> &[&str] {
+        commands::NOTIFICATION_REGISTRATION_METHODS
+    }
+
+    /// registered_notifications returns the notification registration commands sent by this
+    /// client, sorted by name. These are registered again on reconnection.
+    pub async fn registered_notifications(&self) -> Vec<String> {
+        let mut registered: Vec<String> = self
+            .notification_state
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect();
+        registered.sort();
+
+        registered
+    }
+
+    /// load_tx_filter loads the transaction filter relevant transaction notifications are matched
+    /// against, replacing the loaded filter if reload is true and adding to it otherwise.
+    /// Unmined transactions paying to one of the addresses, or spending one of the outpoints,
+    /// are delivered to on_relevant_tx_accepted.
+    ///
+    /// The resulting filter is loaded again on reconnection, before notifications are received
+    /// from the new connection, so no relevant transaction is missed by a reconnection.
+    ///
+    /// **NOTE: This is a dcrd extension and requires a websocket connection.**
+    pub async fn load_tx_filter(
+        &mut self,
+        reload: bool,
+        addresses: &[&str],
+        outpoints: &[cmd_types::OutPoint],
+    ) -> Result<NotificationsFuture, RpcClientError> {
+        check_config!(self);
+        callback_check!(
+            self,
+            commands::METHOD_LOAD_TX_FILTER,
+            all_defined(on_relevant_tx_accepted)
+        );
+
+        let params = [
+            serde_json::json!(reload),
+            serde_json::json!(addresses),
+            serde_json::json!(outpoints),
+        ];
+
+        let deadline = self.request_deadline(commands::METHOD_LOAD_TX_FILTER);
+
+        let (id, result_receiver) = self
+            .send_custom_command(commands::METHOD_LOAD_TX_FILTER, &params)
+            .await?;
+
+        // Filters added without reloading are merged into the previous filter, so the whole
+        // filter is reloaded on reconnection.
+        let mut notification_state = self.notification_state.write().await;
+
+        let mut filter_addresses = Vec::new();
+        let mut filter_outpoints = Vec::new();
+
+        if !reload {
+            if let Some((_, previous)) = notification_state.get(commands::METHOD_LOAD_TX_FILTER) {
+                if let Some(previous) = previous[1].as_array() {
+                    filter_addresses.extend(previous.iter().cloned());
+                }
+
+                if let Some(previous) = previous[2].as_array() {
+                    filter_outpoints.extend(previous.iter().cloned());
+                }
+            }
+        }
+
+        filter_addresses.extend(params[1].as_array().into_iter().flatten().cloned());
+        filter_outpoints.extend(params[2].as_array().into_iter().flatten().cloned());
+
+        notification_state.insert(
+            commands::METHOD_LOAD_TX_FILTER.to_string(),
+            (
+                id,
+                vec![
+                    serde_json::json!(true),
+                    serde_json::Value::Array(filter_addresses),
+                    serde_json::Value::Array(filter_outpoints),
+                ],
+            ),
+        );
+
+        Ok(NotificationsFuture::new(result_receiver).with_deadline(deadline))
+    }
+
+    pub(super) async fn create_notification(
         &mut self,
         method: &str,
         params: &[serde_json::Value],
     ) -> Result<NotificationsFuture, RpcClientError> {
+        let deadline = self.request_deadline(method);
+
         let (id, result_receiver) = match self.send_custom_command(method, params).await {
             Ok(e) => e,
 
             Err(e) => return Err(e),
         };
 
+        // The user takes over registrations made by helpers, which are then left registered.
+        let mut helper_registrations = self.helper_registrations.lock().await;
+        helper_registrations.remove(method);
+
         // Register notification command to active notifications for reconnection.
         let mut notification_state = self.notification_state.write().await;
-        notification_state.insert(method.to_string(), id);
+        notification_state.insert(method.to_string(), (id, params.to_vec()));
 
-        Ok(NotificationsFuture {
-            message: result_receiver,
-        })
+        Ok(NotificationsFuture::new(result_receiver).with_deadline(deadline))
+    }
+
+    /// Registers notifications for a helper such as `block_stream`, returning a guard that
+    /// unregisters them with stop_method once every helper using them dropped its guard.
+    /// Notifications already registered by the user are used as they are and left registered.
+    pub(super) async fn register_for_helper(
+        &mut self,
+        method: &'static str,
+        stop_method: &'static str,
+        params: &[serde_json::Value],
+    ) -> Result<HelperRegistration, RpcClientError> {
+        let mut registration = HelperRegistration {
+            method,
+            stop_method,
+            id: None,
+            helper_registrations: self.helper_registrations.clone(),
+            notification_state: self.notification_state.clone(),
+            user_command: self.ws_user_command.clone(),
+            next_id: self.id.clone(),
+            string_request_ids: self.conn.string_request_ids(),
+        };
+
+        let registrations = self.helper_registrations.clone();
+        let mut helper_registrations = registrations.lock().await;
+
+        if let Some((id, count)) = helper_registrations.get_mut(method) {
+            *count += 1;
+            registration.id = Some(*id);
+
+            return Ok(registration);
+        }
+
+        if self.notification_state.read().await.contains_key(method) {
+            trace!("Using {} notifications registered by the user", method);
+            return Ok(registration);
+        }
+
+        let deadline = self.request_deadline(method);
+        let (id, result_receiver) = self.send_custom_command(method, params).await?;
+
+        helper_registrations.insert(method.to_string(), (id, 1));
+        self.notification_state
+            .write()
+            .await
+            .insert(method.to_string(), (id, params.to_vec()));
+        drop(helper_registrations);
+
+        // Dropping the guard of a refused registration removes it again.
+        registration.id = Some(id);
+
+        NotificationsFuture::new(result_receiver)
+            .with_deadline(deadline)
+            .await?;
+
+        Ok(registration)
+    }
+}
+
+/// Returns the registration command notifications of method are sent for.
+pub(super) fn registration_method(method: &str) -> Option<&'static str> {
+    match method {
+        commands::NOTIFICATION_METHOD_BLOCK_CONNECTED
+        | commands::NOTIFICATION_METHOD_BLOCK_DISCONNECTED
+        | commands::NOTIFICATION_METHOD_REORGANIZATION => Some(commands::METHOD_NOTIFY_BLOCKS),
+
+        commands::NOTIFICATION_METHOD_TX_ACCEPTED
+        | commands::NOTIFICATION_METHOD_TX_ACCEPTED_VERBOSE => Some(commands::METHOD_NOTIFY_NEW_TX),
+
+        commands::NOTIFICATION_METHOD_WORK => Some(commands::METHOD_NOTIFIY_NEW_WORK),
+        commands::NOTIFICATION_METHOD_NEW_TICKETS => Some(commands::METHOD_NOTIFY_NEW_TICKETS),
+        commands::NOTIFICATION_METHOD_STAKE_DIFFICULTY => {
+            Some(commands::METHOD_NOTIFY_STAKE_DIFFICULTY)
+        }
+        commands::NOTIFICATION_METHOD_SPENT_AND_MISSING_TICKETS => {
+            Some(commands::METHOD_NOTIFY_SPEND_AND_MISSED_TICKETS)
+        }
+        commands::NOTIFICATION_METHOD_WINNING_TICKETS => {
+            Some(commands::METHOD_NOTIFY_WINNING_TICKETS)
+        }
+        commands::NOTIFICATION_METHOD_RELEVANT_TX_ACCEPTED => Some(commands::METHOD_LOAD_TX_FILTER),
+
+        _ => None,
+    }
+}
+
+/// Notification registration used by a helper, returned by `register_for_helper`. Once the
+/// last helper using a registration drops its guard, the notifications are unregistered and no
+/// longer registered again on reconnection.
+pub(super) struct HelperRegistration {
+    method: &'static str,
+    stop_method: &'static str,
+    /// ID of the registration made for helpers, None if the user registered the notifications.
+    id: Option<u64>,
+    helper_registrations: HelperRegistrations,
+    notification_state: NotificationState,
+    user_command: mpsc::Sender<Command>,
+    next_id: Arc<AtomicU64>,
+    string_request_ids: bool,
+}
+
+impl HelperRegistration {
+    /// Releases the registration, unregistering it if no other helper uses it.
+    async fn release(mut self) {
+        let id = match self.id.take() {
+            Some(id) => id,
+
+            None => return,
+        };
+
+        let mut helper_registrations = self.helper_registrations.lock().await;
+
+        match helper_registrations.get_mut(self.method) {
+            Some((registered, count)) if *registered == id => {
+                *count -= 1;
+                if *count > 0 {
+                    return;
+                }
+
+                helper_registrations.remove(self.method);
+            }
+
+            // The user registered the notifications since, taking over the registration.
+            _ => return,
+        }
+
+        self.notification_state.write().await.remove(self.method);
+
+        let stop_id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let rpc_message = serde_json::to_vec(&result_types::JsonRequest {
+            jsonrpc: "1.0",
+            id: result_types::request_id(stop_id, self.string_request_ids),
+            method: self.stop_method,
+            params: &[],
+        });
+
+        let rpc_message = match rpc_message {
+            Ok(rpc_message) => rpc_message,
+
+            Err(e) => {
+                warn!("Error marshalling {}, error: {}", self.stop_method, e);
+                return;
+            }
+        };
+
+        let (user_channel, mut receiver) = mpsc::channel(1);
+
+        // Sent before another helper can register again, so the registration is not undone.
+        let sent = self
+            .user_command
+            .send(Command {
+                id: stop_id,
+                user_channel,
+                rpc_message,
+            })
+            .await;
+        drop(helper_registrations);
+
+        if sent.is_err() {
+            trace!("Client disconnected, {} not sent", self.stop_method);
+            return;
+        }
+
+        if let Some(response) = receiver.recv().await {
+            if !response.error.is_null() {
+                warn!(
+                    "Error unregistering {} notifications, error: {}",
+                    self.method, response.error
+                );
+            }
+        }
+    }
+}
+
+impl Drop for HelperRegistration {
+    fn drop(&mut self) {
+        if self.id.is_none() || tokio::runtime::Handle::try_current().is_err() {
+            return;
+        }
+
+        let registration = HelperRegistration {
+            id: self.id.take(),
+            helper_registrations: self.helper_registrations.clone(),
+            notification_state: self.notification_state.clone(),
+            user_command: self.user_command.clone(),
+            next_id: self.next_id.clone(),
+            ..*self
+        };
+
+        logging::spawn(registration.release());
     }
 }
 
@@ -254,6 +706,36 @@ pub(super) fn on_block_disconnected(
     on_block_disconnected(block_header);
 }
 
+pub(super) fn on_block_disconnected_header(
+    params: &[serde_json::Value],
+    on_block_disconnected_header: fn(block_header: result_types::GetBlockHeaderVerboseResult),
+) {
+    trace!("Received on block disconnected notification");
+
+    if params.len() != 1 {
+        warn!("Server sent wrong number of parameters on block disconnected notification handler");
+        return;
+    }
+
+    let block_header = match parse_hex_parameters(&params[0]) {
+        Some(e) => e,
+
+        None => {
+            warn!("Error parsing hex value on block disconnection notification");
+            return;
+        }
+    };
+
+    match result_types::GetBlockHeaderVerboseResult::from_bytes(&block_header) {
+        Ok(header) => on_block_disconnected_header(header),
+
+        Err(e) => warn!(
+            "Error decoding block header on block disconnected notification: {}",
+            e
+        ),
+    }
+}
+
 pub(super) fn on_reorganization(
     params: &[serde_json::Value],
     on_reorganization_callback: fn(
@@ -439,11 +921,20 @@ pub(super) fn on_tx_accepted(
 ) {
     trace!("Received transaction accepted notification");
 
+    if let Some((hash, amount)) = parse_tx_accepted(params) {
+        on_tx_callback(hash, amount)
+    }
+}
+
+/// Parses the transaction hash and amount of a transaction accepted notification.
+pub(super) fn parse_tx_accepted(
+    params: &[serde_json::Value],
+) -> Option<(Hash, crate::dcrutil::amount::Amount)> {
     if params.len() != 2 {
         warn!(
             "Server sent wrong number of parameters on transaction accepted notification handler"
         );
-        return;
+        return None;
     }
 
     let hash = match crate::dcrjson::marshal_to_hash(params[0].clone()) {
@@ -451,35 +942,23 @@ pub(super) fn on_tx_accepted(
 
         None => {
             warn!("Error marshalling value to hash in on transaction accepted");
-            return;
+            return None;
         }
     };
 
-    let amount_unit: f64 = match serde_json::from_value(params[1].clone()) {
+    let amount: crate::dcrutil::amount::Amount = match serde_json::from_value(params[1].clone()) {
         Ok(e) => e,
 
         Err(e) => {
             warn!(
-                "Error marshalling amount unit in on transaction accepted notification, error: {}",
+                "Error marshalling amount in on transaction accepted notification, error: {}",
                 e
             );
-            return;
-        }
-    };
-
-    let amount = match crate::dcrutil::amount::new(amount_unit) {
-        Ok(e) => e,
-
-        Err(e) => {
-            warn!(
-                "Error converting marshalled number to Amount type, error: {}",
-                e
-            );
-            return;
+            return None;
         }
     };
 
-    on_tx_callback(hash, amount)
+    Some((hash, amount))
 }
 
 pub(super) fn on_tx_accepted_verbose(
@@ -575,3 +1054,160 @@ pub(super) fn on_spent_and_missed_tickets(
 
     on_spent_and_missed_tickets_callback(hash, height, stake_diff, tickets);
 }
+
+/// Parses the block hash, block height and stake difficulty of a stake difficulty notification.
+pub(super) fn parse_stake_difficulty(params: &[serde_json::Value]) -> Option<(Hash, i64, i64)> {
+    if params.len() != 3 {
+        warn!("Server sent wrong number of parameters on stake difficulty notification handler");
+        return None;
+    }
+
+    let hash = match marshal_to_hash(params[0].clone()) {
+        Some(e) => e,
+
+        None => {
+            warn!("Error marshalling to hash in on stake difficulty notification.");
+            return None;
+        }
+    };
+
+    let block_height: i64 = match serde_json::from_value(params[1].clone()) {
+        Ok(e) => e,
+
+        Err(e) => {
+            warn!(
+                "Error marshalling block height in on stake difficulty notification, error: {}",
+                e
+            );
+            return None;
+        }
+    };
+
+    let stake_diff: i64 = match serde_json::from_value(params[2].clone()) {
+        Ok(e) => e,
+
+        Err(e) => {
+            warn!(
+                "Error marshalling stake diff in on stake difficulty notification, error: {}",
+                e
+            );
+            return None;
+        }
+    };
+
+    Some((hash, block_height, stake_diff))
+}
+
+pub(super) fn on_stake_difficulty(
+    params: &[serde_json::Value],
+    stake_difficulty_callback: fn(hash: Hash, height: i64, stake_diff: i64),
+) {
+    trace!("Received on stake difficulty notification");
+
+    if let Some((hash, block_height, stake_diff)) = parse_stake_difficulty(params) {
+        stake_difficulty_callback(hash, block_height, stake_diff)
+    }
+}
+
+/// Parses the block hash, block height and ticket hashes of a winning tickets notification.
+/// The server sends the tickets keyed by their index, so they are returned in index order.
+pub(super) fn parse_winning_tickets(
+    params: &[serde_json::Value],
+) -> Option<(Hash, i64, Vec<Hash>)> {
+    if params.len() != 3 {
+        warn!("Server sent wrong number of parameters on winning tickets notification handler");
+        return None;
+    }
+
+    let hash = match marshal_to_hash(params[0].clone()) {
+        Some(e) => e,
+
+        None => {
+            warn!("Error marshalling to hash in on winning tickets notification.");
+            return None;
+        }
+    };
+
+    let block_height: i64 = match serde_json::from_value(params[1].clone()) {
+        Ok(e) => e,
+
+        Err(e) => {
+            warn!(
+                "Error marshalling block height in on winning tickets notification, error: {}",
+                e
+            );
+            return None;
+        }
+    };
+
+    let tickets_str: HashMap<String, String> = match serde_json::from_value(params[2].clone()) {
+        Ok(e) => e,
+
+        Err(e) => {
+            warn!(
+                "Error marshalling tickets in on winning tickets notification, error: {}",
+                e
+            );
+            return None;
+        }
+    };
+
+    let mut tickets: Vec<Option<Hash>> = vec![None; tickets_str.len()];
+
+    for (index, ticket_str) in tickets_str.iter() {
+        let slot = match index.parse::<usize>() {
+            Ok(index) if index < tickets.len() => &mut tickets[index],
+
+            _ => {
+                warn!(
+                    "Invalid ticket index {} in winning tickets notification.",
+                    index
+                );
+                return None;
+            }
+        };
+
+        match Hash::new_from_str(ticket_str) {
+            Ok(e) => *slot = Some(e),
+
+            Err(e) => {
+                warn!("Error converting tickets string to hash, error: {}", e);
+                return None;
+            }
+        }
+    }
+
+    // Indexes are distinct and below the ticket count, so every slot is filled.
+    let tickets = tickets.into_iter().flatten().collect();
+
+    Some((hash, block_height, tickets))
+}
+
+pub(super) fn on_winning_tickets(
+    params: &[serde_json::Value],
+    winning_tickets_callback: fn(block_hash: Hash, block_height: i64, tickets: Vec<Hash>),
+) {
+    trace!("Received on winning tickets notification");
+
+    if let Some((hash, block_height, tickets)) = parse_winning_tickets(params) {
+        winning_tickets_callback(hash, block_height, tickets)
+    }
+}
+
+pub(super) fn on_relevant_tx_accepted(
+    params: &[serde_json::Value],
+    on_relevant_tx_accepted: fn(transaction: Vec<u8>),
+) {
+    trace!("Received relevant transaction accepted notification");
+
+    if params.len() != 1 {
+        warn!("Server sent wrong number of parameters on relevant transaction accepted notification handler");
+        return;
+    }
+
+    match parse_hex_parameters(&params[0]) {
+        Some(transaction) => on_relevant_tx_accepted(transaction),
+
+        None => warn!("Error parsing hex value on relevant transaction accepted notification."),
+    }
+}
@@ -7,12 +7,17 @@ use {
         connection::{RPCConn, Websocket},
         constants,
         error::RpcClientError,
-        infrastructure, notify,
+        infrastructure, logging,
+        logging::{debug, error, info, warn},
+        notify,
+        rate_limit::RateLimiter,
+    },
+    crate::{
+        chaincfg::params::Network,
+        dcrjson::{commands, result_types, result_types::JsonResponse},
     },
-    crate::dcrjson::{result_types, result_types::JsonResponse},
     futures_util::stream::SplitSink,
     futures_util::stream::SplitStream,
-    log::{info, warn},
     std::sync::Arc,
     std::{
         collections::{HashMap, VecDeque},
@@ -39,7 +44,7 @@ use {
 /// All field in `Client` are async safe.
 pub struct Client<C> {
     /// tracks asynchronous requests and is to be updated at realtime.
-    pub(crate) id: AtomicU64,
+    pub(crate) id: Arc<AtomicU64>,
 
     /// A websocket channel that tunnels converted users messages to websocket write middleman to be consumed by websocket writer.
     pub(crate) ws_user_command: mpsc::Sender<infrastructure::Command>,
@@ -65,7 +70,13 @@ pub struct Client<C> {
     /// On notification registration, message sent to the RPC server is copied and stored. This is so that on reconnection
     /// same message can be sent to the server and server can reply to recently registered command channel which calls the callback
     /// function.
-    pub(crate) notification_state: Arc<RwLock<HashMap<String, u64>>>,
+    pub(crate) notification_state: infrastructure::NotificationState,
+
+    /// Channels notifications are forwarded to, keyed by notification method.
+    notification_subscribers: infrastructure::NotificationSubscribers,
+
+    /// Notification registrations made by helpers, unregistered once no helper uses them.
+    pub(crate) helper_registrations: infrastructure::HelperRegistrations,
 
     /// Stores all requests to be be sent to the RPC server.
     requests_queue_container: Arc<Mutex<VecDeque<Vec<u8>>>>,
@@ -76,6 +87,24 @@ pub struct Client<C> {
 
     /// Indicates whether the client is disconnected from the server.
     is_ws_disconnected: Arc<RwLock<bool>>,
+
+    /// Indicates whether the websocket reconnect handler is reconnecting the client.
+    is_ws_reconnecting: Arc<RwLock<bool>>,
+
+    /// Indicates whether the client only receives notifications, refusing requests.
+    listen_only: bool,
+
+    /// Network the server is on, once detected with `detect_and_set_network`.
+    pub(crate) network: Option<Network>,
+
+    /// Paces requests when `requests_per_second` is configured.
+    rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// Signals the websocket reconnect handler of the current connection to reconnect.
+    signal_ws_reconnect: Option<mpsc::Sender<notify::ReconnectReason>>,
+
+    /// Why the websocket connection was last lost.
+    last_disconnect_reason: Arc<std::sync::Mutex<Option<notify::DisconnectReason>>>,
 }
 
 /// Creates a new RPC client based on the provided connection configuration
@@ -83,9 +112,95 @@ pub struct Client<C> {
 /// interested in receiving notifications and will be ignored if the
 /// configuration is set to run in HTTP POST mode.
 pub async fn new<C: 'static + connection::RPCConn>(
+    conn: C,
+    notif_handler: notify::NotificationHandlers,
+) -> Result<Client<C>, RpcClientError> {
+    new_client(
+        conn,
+        notif_handler,
+        false,
+        notify::NotificationStateSnapshot::default(),
+    )
+    .await
+}
+
+/// Creates a new RPC client registering the notifications of a snapshot returned by
+/// `export_notification_state` on connection and reconnection, so a replacement client can take
+/// over the subscriptions of the client it replaces. Notifications are registered again with
+/// the parameters they were exported with.
+pub async fn with_notification_state<C: 'static + connection::RPCConn>(
+    conn: C,
+    notif_handler: notify::NotificationHandlers,
+    snapshot: notify::NotificationStateSnapshot,
+) -> Result<Client<C>, RpcClientError> {
+    check_registration_methods(snapshot.registrations.keys().map(String::as_str))?;
+
+    new_client(conn, notif_handler, false, snapshot).await
+}
+
+/// Creates a new RPC client that only receives notifications, registering the given
+/// notification registration commands, such as `notifyblocks`, on connection and reconnection.
+/// Notifications are delivered to the notification handlers.
+///
+/// A listen only client keeps no request queue and its request methods, including notification
+/// registration methods, return a `ListenOnly` error. Registrations are sent without parameters,
+/// so `notifynewtransactions` delivers non-verbose notifications. Listen only clients require a
+/// websocket connection.
+pub async fn listen_only<C: 'static + connection::RPCConn>(
+    conn: C,
+    notif_handler: notify::NotificationHandlers,
+    notifications: &[&str],
+) -> Result<Client<C>, RpcClientError> {
+    if conn.is_http_mode() {
+        return Err(RpcClientError::InvalidParameter(
+            "listen only clients require a websocket connection".to_string(),
+        ));
+    }
+
+    check_registration_methods(notifications.iter().copied())?;
+
+    let snapshot = notify::NotificationStateSnapshot {
+        registrations: notifications
+            .iter()
+            .map(|method| (method.to_string(), Vec::new()))
+            .collect(),
+    };
+
+    new_client(conn, notif_handler, true, snapshot).await
+}
+
+/// Errors with an InvalidParameter error if one of methods is not a notification registration
+/// command.
+fn check_registration_methods<'a>(
+    mut methods: impl Iterator<Item = &'a str>,
+) -> Result<(), RpcClientError> {
+    match methods.find(|method| !commands::NOTIFICATION_REGISTRATION_METHODS.contains(method)) {
+        Some(unknown) => Err(RpcClientError::InvalidParameter(format!(
+            "unknown notification registration command {}",
+            unknown
+        ))),
+
+        None => Ok(()),
+    }
+}
+
+async fn new_client<C: 'static + connection::RPCConn>(
     mut conn: C,
     notif_handler: notify::NotificationHandlers,
+    listen_only: bool,
+    snapshot: notify::NotificationStateSnapshot,
 ) -> Result<Client<C>, RpcClientError> {
+    let rate_limiter = match conn.requests_per_second() {
+        Some(rate) if !(rate.is_finite() && rate > 0.0) => {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "requests per second must be positive, got {}",
+                rate
+            )))
+        }
+
+        rate => rate.map(|rate| Arc::new(RateLimiter::new(rate, conn.burst()))),
+    };
+
     let websocket_channel = mpsc::channel(constants::SEND_BUFFER_SIZE);
     let http_channel = mpsc::channel(constants::SEND_BUFFER_SIZE);
 
@@ -93,13 +208,16 @@ pub async fn new<C: 'static + connection::RPCConn>(
     let ws_disconnect_acknowledgement = mpsc::channel(1);
 
     let mut client = Client {
-        id: AtomicU64::new(1),
+        id: Arc::new(AtomicU64::new(1)),
         disconnect_ws: disconnect_ws_channel.0,
         conn: conn.clone(),
 
         is_ws_disconnected: Arc::new(RwLock::new(true)),
+        is_ws_reconnecting: Arc::new(RwLock::new(false)),
         notification_handler: Arc::new(notif_handler),
         notification_state: Arc::new(RwLock::new(HashMap::new())),
+        notification_subscribers: Arc::new(Mutex::new(HashMap::new())),
+        helper_registrations: Arc::new(Mutex::new(HashMap::new())),
         receiver_channel_id_mapper: Arc::new(Mutex::new(HashMap::new())),
         requests_queue_container: Arc::new(Mutex::new(VecDeque::new())),
 
@@ -107,21 +225,35 @@ pub async fn new<C: 'static + connection::RPCConn>(
         http_user_command: http_channel.0,
 
         ws_disconnected_acknowledgement: ws_disconnect_acknowledgement.1,
+        listen_only,
+        network: None,
+        rate_limiter,
+        signal_ws_reconnect: None,
+        last_disconnect_reason: Arc::new(std::sync::Mutex::new(None)),
     };
 
+    {
+        let mut notification_state = client.notification_state.write().await;
+        for (method, params) in snapshot.registrations {
+            notification_state.insert(method, (client.next_id(), params));
+        }
+    }
+
     if !conn.disable_connect_on_new() && !conn.is_http_mode() {
         info!("Establishing websocket connection");
 
-        match conn.ws_split_stream().await {
+        match connect_on_new(&mut conn).await {
             Ok(ws) => {
-                client
-                    .ws_handler(
+                logging::scope(
+                    conn.connection_name().map(Arc::from),
+                    client.ws_handler(
                         websocket_channel.1,
                         disconnect_ws_channel.1,
                         ws_disconnect_acknowledgement.0,
                         ws,
-                    )
-                    .await;
+                    ),
+                )
+                .await;
 
                 *client.is_ws_disconnected.write().await = false;
             }
@@ -131,10 +263,10 @@ pub async fn new<C: 'static + connection::RPCConn>(
     } else if conn.is_http_mode() {
         let conn = conn.clone();
 
-        tokio::spawn(async move {
+        logging::spawn_named(conn.connection_name().map(Arc::from), async move {
             let http_mode_future = conn.handle_post_methods(http_channel.1);
             if let Err(e) = http_mode_future.await {
-                log::error!("http connection error: {}", e)
+                error!("http connection error: {}", e)
             }
         });
     }
@@ -142,6 +274,49 @@ pub async fn new<C: 'static + connection::RPCConn>(
     Ok(client)
 }
 
+/// Establishes the initial websocket connection of a new client. Failed attempts are retried
+/// with the reconnection backoff when `connect_retry_on_new` is set, until
+/// `max_reconnect_attempts` attempts or `max_auth_failures` consecutive rejected attempts fail.
+async fn connect_on_new<C: connection::RPCConn>(
+    conn: &mut C,
+) -> Result<(SplitStream<Websocket>, SplitSink<Websocket, Message>), RpcClientError> {
+    if !conn.connect_retry_on_new() {
+        return conn.ws_split_stream().await;
+    }
+
+    let backoff = conn.backoff();
+    let mut auth_failures = 0;
+    let mut attempts = 0;
+
+    loop {
+        let e = match conn.ws_split_stream().await {
+            Ok(ws) => return Ok(ws),
+
+            Err(e) => e,
+        };
+
+        attempts += 1;
+
+        if let RpcClientError::AuthenticationFailed = e {
+            auth_failures += 1;
+
+            if auth_failures == conn.max_auth_failures() {
+                return Err(e);
+            }
+        } else {
+            auth_failures = 0;
+        }
+
+        if infrastructure::reconnect_attempts_exhausted(conn, attempts) {
+            return Err(e);
+        }
+
+        warn!("unable to connect websocket, error: {}. Retrying.", e);
+
+        tokio::time::sleep(backoff.next_delay(attempts)).await;
+    }
+}
+
 // TODO: Do we need a waitgroup???
 impl<C: 'static + RPCConn> Client<C> {
     /// Handles websocket connection to server by calling selective function to handle websocket send, write and reconnect.
@@ -165,7 +340,7 @@ impl<C: 'static + RPCConn> Client<C> {
     ) {
         let queue_command = mpsc::channel(1);
 
-        let msg_acknowledgement = mpsc::channel(1);
+        let msg_acknowledgement = mpsc::unbounded_channel();
 
         let request_queue_update = mpsc::channel(1);
 
@@ -174,7 +349,23 @@ impl<C: 'static + RPCConn> Client<C> {
         let new_ws_sink = mpsc::channel(1);
         let ws_sink = mpsc::channel(1);
 
-        infrastructure::get_ws_sink(ws_sink.1, stream.1, msg_acknowledgement.0.clone()).await;
+        let signal_ws_reconnect = mpsc::channel(1);
+        self.signal_ws_reconnect = Some(signal_ws_reconnect.0.clone());
+
+        let last_received = Arc::new(RwLock::new(tokio::time::Instant::now()));
+        let server_close = Arc::new(RwLock::new(None));
+
+        infrastructure::get_ws_sink(
+            ws_sink.1,
+            stream.1,
+            msg_acknowledgement.0.clone(),
+            signal_ws_reconnect.0.clone(),
+            constants::WRITE_TIMEOUT_SECS,
+            self.notification_handler.on_raw_message,
+        )
+        .await;
+
+        let registration_sink = ws_sink.0.clone();
 
         let websocket_out = infrastructure::handle_websocket_out(
             ws_sink.0,
@@ -183,65 +374,172 @@ impl<C: 'static + RPCConn> Client<C> {
             msg_acknowledgement.0.clone(),
             request_queue_update.1,
             disconnect_ws_cmd_rcv,
+            self.conn.idle_timeout(),
+            last_received.clone(),
+            signal_ws_reconnect.0.clone(),
+            self.conn.keep_alive(),
+            self.conn.string_request_ids(),
         );
 
         let handle_rcvd_msg = mpsc::unbounded_channel();
 
         let new_ws_reader = mpsc::channel(1);
 
-        let signal_ws_reconnect = mpsc::channel(1);
-
         let websocket_in = infrastructure::handle_websocket_in(
-            handle_rcvd_msg.0,
+            handle_rcvd_msg.0.clone(),
             stream.0,
             new_ws_reader.1,
-            signal_ws_reconnect.0,
+            signal_ws_reconnect.0.clone(),
+            last_received,
+            server_close.clone(),
+            self.notification_handler.on_raw_message,
+            self.receiver_channel_id_mapper.clone(),
+            self.requests_queue_container.clone(),
         );
 
         let rcvd_msg_handler = infrastructure::handle_received_message(
             handle_rcvd_msg.1,
             notification_handler.0,
             ws_disconnect_acknowledgement,
+            self.is_ws_disconnected.clone(),
             self.receiver_channel_id_mapper.clone(),
+            self.conn.max_response_size(),
+            self.notification_handler.on_rpc_error,
         );
 
-        let ws_write_middleman = infrastructure::ws_write_middleman(
-            user_command,
-            request_queue_update.0,
-            msg_acknowledgement.1,
-            queue_command.0,
-            self.requests_queue_container.clone(),
-            self.receiver_channel_id_mapper.clone(),
-        );
+        // Registered notifications, such as those of listen only clients or imported with
+        // `with_notification_state`, are registered on connection.
+        for (method, (id, params)) in self.notification_state.read().await.iter() {
+            debug!("Registering {} notification on connection.", method);
+
+            let data = infrastructure::registration_message(
+                method,
+                *id,
+                params,
+                self.conn.string_request_ids(),
+            );
+
+            if let Err(e) = registration_sink.send(Message::Text(data)).await {
+                warn!("Error registering notification on connection, error: {}", e);
+            }
+        }
+
+        if self.listen_only {
+            logging::spawn(infrastructure::listen_only_middleman(
+                user_command,
+                msg_acknowledgement.1,
+                request_queue_update.0,
+                queue_command.0,
+            ));
+        } else {
+            logging::spawn(infrastructure::ws_write_middleman(
+                user_command,
+                request_queue_update.0,
+                msg_acknowledgement.1,
+                queue_command.0,
+                self.requests_queue_container.clone(),
+                self.receiver_channel_id_mapper.clone(),
+            ));
+        }
 
         let on_client_connected = self
             .notification_handler
             .on_client_connected
             .unwrap_or(|| {});
 
+        let on_reconnected = self.notification_handler.on_reconnected.unwrap_or(|| {});
+
+        let on_client_disconnected = self
+            .notification_handler
+            .on_client_disconnected
+            .unwrap_or(|| {});
+
+        let on_reconnecting = self.notification_handler.on_reconnecting.unwrap_or(|_| {});
+
+        let on_reconnect_giveup = self
+            .notification_handler
+            .on_reconnect_giveup
+            .unwrap_or(|| {});
+
+        // Blocks connected while reconnecting are recovered from the last notified block.
+        let last_block_height = match self.notification_handler.on_block_connected {
+            Some(on_block_connected) if self.conn.recover_missed_blocks() && !self.listen_only => {
+                Some((
+                    infrastructure::LastBlockHeight::default(),
+                    on_block_connected,
+                ))
+            }
+
+            _ => None,
+        };
+
+        let on_reconnect = {
+            let user_command = self.ws_user_command.clone();
+            let id = self.id.clone();
+            let string_request_ids = self.conn.string_request_ids();
+            let recovery = last_block_height.clone();
+
+            move || {
+                on_client_connected();
+                on_reconnected();
+
+                if let Some((last_block_height, on_block_connected)) = recovery.clone() {
+                    let recovery = infrastructure::recover_missed_blocks(
+                        user_command.clone(),
+                        id.clone(),
+                        string_request_ids,
+                        last_block_height,
+                        on_block_connected,
+                    );
+
+                    logging::spawn(async move {
+                        if let Err(e) = recovery.await {
+                            warn!(
+                                "Error recovering blocks missed while reconnecting, error: {}",
+                                e
+                            );
+                        }
+                    });
+                }
+            }
+        };
+
         let reconnect_handler = infrastructure::ws_reconnect_handler(
             self.conn.clone(),
             self.is_ws_disconnected.clone(),
+            self.is_ws_reconnecting.clone(),
+            self.requests_queue_container.clone(),
             signal_ws_reconnect.1,
+            server_close,
+            self.last_disconnect_reason.clone(),
             new_ws_reader.0,
             new_ws_sink.0,
             self.notification_state.clone(),
+            handle_rcvd_msg.0,
             msg_acknowledgement.0,
-            on_client_connected,
+            signal_ws_reconnect.0,
+            on_reconnect,
+            on_reconnecting,
+            on_client_disconnected,
+            on_reconnect_giveup,
+            self.notification_handler.on_raw_message,
         );
 
         let notification_handler = infrastructure::handle_notification(
             notification_handler.1,
             self.notification_handler.clone(),
+            self.conn.notification_workers(),
+            self.notification_subscribers.clone(),
+            last_block_height.map(|(last_block_height, _)| last_block_height),
+            self.helper_registrations.clone(),
         );
 
         // Separately spawn asynchronous thread for each instances.
-        tokio::spawn(websocket_out);
-        tokio::spawn(websocket_in);
-        tokio::spawn(rcvd_msg_handler);
-        tokio::spawn(ws_write_middleman);
-        tokio::spawn(reconnect_handler);
-        tokio::spawn(notification_handler);
+        logging::spawn(websocket_out);
+        logging::spawn(websocket_in);
+        logging::spawn(rcvd_msg_handler);
+        logging::spawn(reconnect_handler);
+        logging::spawn(notification_handler);
 
         on_client_connected();
     }
@@ -255,6 +553,40 @@ impl<C: 'static + RPCConn> Client<C> {
         self.id.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Sets the ID used by the next request, with following requests counting up from it. This
+    /// allows predictable IDs in tests and keeps clear of IDs reserved by a server or used by
+    /// another client sharing the same ID space.
+    ///
+    /// IDs are not checked for reuse. The ID must not be one of a request still awaiting its
+    /// response, see `pending_requests`, nor of a registered notification, whose ID is reused
+    /// to register it again on reconnection. ID 0 is used by keep-alive requests.
+    pub fn set_next_id(&self, id: u64) {
+        self.id.store(id, Ordering::SeqCst)
+    }
+
+    /// Returns the ID the next request will be sent with, without consuming it.
+    pub fn peek_next_id(&self) -> u64 {
+        self.id.load(Ordering::SeqCst)
+    }
+
+    /// Returns a channel receiving every notification of method, such as `stakedifficulty`, from
+    /// now on. The subscription ends when the receiver is dropped.
+    pub(crate) async fn subscribe_notifications(
+        &self,
+        method: &str,
+    ) -> mpsc::UnboundedReceiver<JsonResponse> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        self.notification_subscribers
+            .lock()
+            .await
+            .entry(method.to_string())
+            .or_default()
+            .push(sender);
+
+        receiver
+    }
+
     /// Establishes the initial websocket connection.  This is necessary when a client was
     /// created after setting the DisableConnectOnNew field of the Config struct.
     ///
@@ -290,11 +622,14 @@ impl<C: 'static + RPCConn> Client<C> {
             *is_ws_disconnected = false;
         }
 
-        self.ws_handler(
-            user_command_channel.1,
-            disconnect_ws_channel.1,
-            ws_disconnect_acknowledgement.0,
-            ws,
+        logging::scope(
+            self.conn.connection_name().map(Arc::from),
+            self.ws_handler(
+                user_command_channel.1,
+                disconnect_ws_channel.1,
+                ws_disconnect_acknowledgement.0,
+                ws,
+            ),
         )
         .await;
 
@@ -308,6 +643,10 @@ impl<C: 'static + RPCConn> Client<C> {
         method: &str,
         params: &[serde_json::Value],
     ) -> Result<(u64, mpsc::Receiver<JsonResponse>), RpcClientError> {
+        if self.listen_only {
+            return Err(RpcClientError::ListenOnly);
+        }
+
         let (id, msg) = self.marshal_command(method, params);
 
         let msg = match msg {
@@ -319,11 +658,94 @@ impl<C: 'static + RPCConn> Client<C> {
             }
         };
 
+        let deadline = self.request_deadline(method);
+
+        Ok((id, self.send_command(id, msg, deadline).await?))
+    }
+
+    /// Sends a serialized JSON-RPC request as is, returning a receiving channel that receives
+    /// the result returned by server. This allows proxying requests serialized elsewhere
+    /// verbatim.
+    ///
+    /// The body must be a JSON object with an unsigned integer `id`, which the response is
+    /// mapped to. An ID must not be reused while its request is pending, see `pending_requests`,
+    /// and should be kept clear of the IDs the client generates, see `set_next_id`.
+    pub async fn send_raw_request(
+        &mut self,
+        body: Vec<u8>,
+    ) -> Result<mpsc::Receiver<JsonResponse>, RpcClientError> {
+        if self.listen_only {
+            return Err(RpcClientError::ListenOnly);
+        }
+
+        let request: serde_json::Value = match serde_json::from_slice(&body) {
+            Ok(request) => request,
+
+            Err(e) => {
+                return Err(RpcClientError::InvalidParameter(format!(
+                    "raw request is not valid JSON, error: {}",
+                    e
+                )))
+            }
+        };
+
+        let id = match request.get("id").and_then(serde_json::Value::as_u64) {
+            Some(id) => id,
+
+            None => {
+                return Err(RpcClientError::InvalidParameter(
+                    "raw request has no unsigned integer id".to_string(),
+                ))
+            }
+        };
+
+        if self
+            .receiver_channel_id_mapper
+            .lock()
+            .await
+            .contains_key(&id)
+        {
+            return Err(RpcClientError::InvalidParameter(format!(
+                "raw request id {} is already pending",
+                id
+            )));
+        }
+
+        let method = request
+            .get("method")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let deadline = self.request_deadline(method);
+
+        self.send_command(id, body, deadline).await
+    }
+
+    /// Sends a serialized request with the given ID to the server, returning a receiving
+    /// channel that receives its result. Requests waiting on the rate limiter fail once
+    /// `deadline` passes.
+    async fn send_command(
+        &self,
+        id: u64,
+        rpc_message: Vec<u8>,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<mpsc::Receiver<JsonResponse>, RpcClientError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if !rate_limiter.acquire(deadline).await {
+                return Err(RpcClientError::RequestTimeout);
+            }
+        }
+
+        if self.conn.queue_while_disconnected() && !self.conn.is_http_mode() {
+            if let Some(receiver) = self.queue_request(id, &rpc_message).await? {
+                return Ok(receiver);
+            }
+        }
+
         let channel = mpsc::channel(1);
 
         let cmd = super::infrastructure::Command {
             id,
-            rpc_message: msg,
+            rpc_message,
             user_channel: channel.0,
         };
 
@@ -334,7 +756,7 @@ impl<C: 'static + RPCConn> Client<C> {
         };
 
         match server_channel.send(cmd).await {
-            Ok(_) => Ok((id, channel.1)),
+            Ok(_) => Ok(channel.1),
 
             Err(e) => {
                 warn!("error sending custom command to server, error: {}", e);
@@ -344,6 +766,59 @@ impl<C: 'static + RPCConn> Client<C> {
         }
     }
 
+    /// Queues a request made while the websocket is disconnected or reconnecting, to be sent once
+    /// `connect` or the websocket reconnect handler reconnects the client, returning a receiving
+    /// channel that receives its result. The request is dropped from the queue and answered with
+    /// an error if still queued once `queued_request_timeout` passes. None is returned if the
+    /// client is connected, leaving the request to be sent as usual.
+    async fn queue_request(
+        &self,
+        id: u64,
+        rpc_message: &[u8],
+    ) -> Result<Option<mpsc::Receiver<JsonResponse>>, RpcClientError> {
+        let max_queued_requests = self.conn.max_queued_requests();
+
+        // The reconnect handler reads the queue under the same lock once reconnected, so a
+        // request queued while reconnecting is never left behind.
+        let mut queue = self.requests_queue_container.lock().await;
+        if !self.is_disconnected().await && !*self.is_ws_reconnecting.read().await {
+            return Ok(None);
+        }
+
+        if queue.len() >= max_queued_requests {
+            return Err(RpcClientError::RequestQueueFull(max_queued_requests));
+        }
+
+        let channel = mpsc::channel(1);
+
+        self.receiver_channel_id_mapper
+            .lock()
+            .await
+            .insert(id, channel.0);
+        queue.push_back(rpc_message.to_vec());
+        drop(queue);
+
+        debug!("Queued request ID {} until the client reconnects", id);
+
+        logging::spawn(infrastructure::expire_queued_request(
+            id,
+            rpc_message.to_vec(),
+            self.conn.queued_request_timeout(),
+            self.requests_queue_container.clone(),
+            self.receiver_channel_id_mapper.clone(),
+        ));
+
+        Ok(Some(channel.1))
+    }
+
+    /// Returns when a request of the given method made now times out, if `request_timeout` or
+    /// a timeout for the method is configured.
+    pub(crate) fn request_deadline(&self, method: &str) -> Option<tokio::time::Instant> {
+        self.conn
+            .method_timeout(method)
+            .map(|timeout| tokio::time::Instant::now() + timeout)
+    }
+
     /// Marshals clients methods and parameters to a valid JSON RPC command also returning command ID for mapping.
     pub(super) fn marshal_command(
         &self,
@@ -354,7 +829,7 @@ impl<C: 'static + RPCConn> Client<C> {
 
         let request = result_types::JsonRequest {
             jsonrpc: "1.0",
-            id,
+            id: result_types::request_id(id, self.conn.string_request_ids()),
             method,
             params,
         };
@@ -374,6 +849,11 @@ impl<C: 'static + RPCConn> Client<C> {
             *is_ws_disconnected = true;
         }
 
+        infrastructure::set_disconnect_reason(
+            &self.last_disconnect_reason,
+            notify::DisconnectReason::Shutdown,
+        );
+
         if self.disconnect_ws.send(()).await.is_err() {
             warn!("error sending disconnect command to webserver, disconnect_ws closed.");
             return;
@@ -388,14 +868,92 @@ impl<C: 'static + RPCConn> Client<C> {
     }
 
     async fn unregister_notification_state(&mut self) {
+        self.helper_registrations.lock().await.clear();
         self.notification_state.write().await.clear()
     }
 
+    /// Drops the websocket connection and reconnects, as if the server had dropped the
+    /// connection. The reconnection goes through the usual reconnect path: `on_reconnecting` is
+    /// called with a Forced reason, notifications are registered again on the new connection
+    /// and `on_client_connected` and `on_reconnected` are called once reconnected. This is meant
+    /// for testing how applications handle reconnections. Nothing is done if a reconnection is
+    /// already pending.
+    pub async fn force_reconnect(&mut self) -> Result<(), RpcClientError> {
+        if self.conn.is_http_mode() || self.is_disconnected().await {
+            return Err(RpcClientError::RpcDisconnected);
+        }
+
+        let signal_ws_reconnect = match &self.signal_ws_reconnect {
+            Some(signal_ws_reconnect) => signal_ws_reconnect,
+
+            None => return Err(RpcClientError::RpcDisconnected),
+        };
+
+        match signal_ws_reconnect.try_send(notify::ReconnectReason::Forced) {
+            Ok(_) | Err(mpsc::error::TrySendError::Full(_)) => Ok(()),
+
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(RpcClientError::RpcDisconnected),
+        }
+    }
+
     /// Return websocket disconnected state to webserver.
     pub async fn is_disconnected(&self) -> bool {
         *self.is_ws_disconnected.read().await
     }
 
+    /// Returns why the websocket connection was last lost, or None if it never was. The reason is
+    /// kept once the client reconnects, and is Shutdown once the client disconnects with
+    /// `disconnect` or `shutdown`.
+    pub fn last_disconnect_reason(&self) -> Option<notify::DisconnectReason> {
+        match self.last_disconnect_reason.lock() {
+            Ok(last_disconnect_reason) => last_disconnect_reason.clone(),
+
+            Err(e) => {
+                warn!("Error reading disconnect reason, error: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Returns the IDs of requests awaiting a response from the server, sorted in ascending
+    /// order. The list is a snapshot taken under a short-lived lock.
+    pub async fn pending_requests(&self) -> Vec<u64> {
+        let mut ids: Vec<u64> = self
+            .receiver_channel_id_mapper
+            .lock()
+            .await
+            .keys()
+            .copied()
+            .collect();
+        ids.sort_unstable();
+
+        ids
+    }
+
+    /// Returns the notifications registered by this client with the parameters they were last
+    /// registered with, for a replacement client created with `with_notification_state` to
+    /// register on connection. Registrations made by helpers such as `block_stream` are left
+    /// out, as they end with the helper.
+    pub async fn export_notification_state(&self) -> notify::NotificationStateSnapshot {
+        let helper_registrations = self.helper_registrations.lock().await;
+
+        notify::NotificationStateSnapshot {
+            registrations: self
+                .notification_state
+                .read()
+                .await
+                .iter()
+                .filter(|(method, _)| !helper_registrations.contains_key(*method))
+                .map(|(method, (_, params))| (method.clone(), params.clone()))
+                .collect(),
+        }
+    }
+
+    /// Returns the number of requests queued but not yet written to the websocket.
+    pub async fn queued_message_count(&self) -> usize {
+        self.requests_queue_container.lock().await.len()
+    }
+
     /// Clear queue, error commands channels and close websocket connection normally.
     /// Shutdown broadcasts a disconnect command to websocket continuosly and waits for waitgroup block to be
     /// closed before exiting.
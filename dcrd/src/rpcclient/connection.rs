@@ -1,28 +1,38 @@
 //! Client connection.
 //! Consists all websocket cofigurations.
 
-use crate::dcrjson::result_types::JsonResponse;
+use crate::{
+    chaincfg::params::Network,
+    dcrjson::{result_types::JsonResponse, DeserializationPolicy},
+};
 
 use super::infrastructure::Command;
 
 use {
-    super::error::RpcClientError,
+    super::{
+        backoff::Backoff,
+        constants,
+        error::RpcClientError,
+        logging::{info, warn},
+    },
     async_trait::async_trait,
     futures_util::stream::SplitSink,
     futures_util::stream::{SplitStream, StreamExt},
     httparse::Status,
-    log::info,
-    log::warn,
+    percent_encoding::percent_decode_str,
     tokio::{
-        io::{AsyncReadExt, AsyncWriteExt},
+        io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
         net::TcpStream,
         sync::mpsc,
     },
     tokio_native_tls::native_tls,
     tokio_tungstenite::{
-        tungstenite::{handshake::headers, http::Request, Message},
+        tungstenite::{
+            self, handshake::headers, http::Request, protocol::WebSocketConfig, Message,
+        },
         MaybeTlsStream, WebSocketStream,
     },
+    url::Url,
 };
 
 /// RPC connection trait.
@@ -41,6 +51,180 @@ pub trait RPCConn: Sized + Send + Sync + Clone {
     fn is_http_mode(&self) -> bool;
     fn disable_connect_on_new(&self) -> bool;
     fn disable_auto_reconnect(&self) -> bool;
+
+    /// Whether the initial connection made by `client::new` is retried like a reconnection.
+    /// Disabled by default.
+    fn connect_retry_on_new(&self) -> bool {
+        false
+    }
+
+    /// Number of consecutive rejected reconnections after which the client stops reconnecting.
+    /// Zero, the default, retries indefinitely.
+    fn max_auth_failures(&self) -> u32 {
+        0
+    }
+
+    /// Number of consecutive failed reconnection attempts after which the client stops
+    /// reconnecting. Zero gives up after the first failed attempt. None, the default, retries
+    /// indefinitely.
+    fn max_reconnect_attempts(&self) -> Option<u32> {
+        None
+    }
+
+    /// Strategy deciding the delay between failed reconnection attempts. Exponential by default.
+    fn backoff(&self) -> Backoff {
+        Backoff::default()
+    }
+
+    /// Number of worker threads notification callbacks are dispatched to. Zero, the default, calls
+    /// callbacks on the notification handler task.
+    fn notification_workers(&self) -> usize {
+        0
+    }
+
+    /// Time without messages after which the server is pinged. Idle pings are disabled by default.
+    fn idle_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// How the connection is kept alive. Websocket pings by default.
+    fn keep_alive(&self) -> KeepAlive {
+        KeepAlive::default()
+    }
+
+    /// Whether blocks connected while reconnecting are replayed. Disabled by default.
+    fn recover_missed_blocks(&self) -> bool {
+        false
+    }
+
+    /// Size in bytes above which websocket messages are refused. Messages the websocket stream
+    /// does not refuse while reading are dropped once received. No limit by default.
+    fn max_response_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether request IDs are sent as JSON strings. Numeric IDs are sent by default.
+    fn string_request_ids(&self) -> bool {
+        false
+    }
+
+    /// How result fields unknown to their result type are handled. Lenient by default.
+    fn deserialization_policy(&self) -> DeserializationPolicy {
+        DeserializationPolicy::default()
+    }
+
+    /// Name prefixing the log lines emitted by the client's tasks. None by default.
+    fn connection_name(&self) -> Option<String> {
+        None
+    }
+
+    /// Time after which a request not answered by the server fails. No timeout by default.
+    fn request_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Time after which a request of the given method not answered by the server fails.
+    /// Defaults to `request_timeout`.
+    fn method_timeout(&self, _method: &str) -> Option<std::time::Duration> {
+        self.request_timeout()
+    }
+
+    /// Rate requests are sent at, in requests per second. Unlimited by default.
+    fn requests_per_second(&self) -> Option<f64> {
+        None
+    }
+
+    /// Number of requests that can be sent at once before being paced to
+    /// `requests_per_second`. One by default.
+    fn burst(&self) -> u32 {
+        1
+    }
+
+    /// Whether requests made while the websocket is disconnected are queued until the client
+    /// reconnects. Disabled by default.
+    fn queue_while_disconnected(&self) -> bool {
+        false
+    }
+
+    /// Number of requests that can be queued while disconnected. 100 by default.
+    fn max_queued_requests(&self) -> usize {
+        constants::DEFAULT_MAX_QUEUED_REQUESTS
+    }
+
+    /// Time a request queued while disconnected waits for the client to reconnect. One minute
+    /// by default.
+    fn queued_request_timeout(&self) -> std::time::Duration {
+        constants::DEFAULT_QUEUED_REQUEST_TIMEOUT
+    }
+}
+
+/// Converts a websocket handshake error to a client error, distinguishing credentials
+/// rejected by the server from other handshake failures.
+pub(super) fn handshake_error(e: tungstenite::Error) -> RpcClientError {
+    match e {
+        tungstenite::Error::Http(ref response)
+            if response.status() == tungstenite::http::StatusCode::UNAUTHORIZED =>
+        {
+            RpcClientError::AuthenticationFailed
+        }
+
+        e => RpcClientError::RpcHandshake(Box::new(e)),
+    }
+}
+
+/// Reads the body of an HTTP response chunk by chunk, failing once it exceeds `max_size` bytes
+/// rather than buffering it whole. Compressed bodies are decompressed by reqwest as they are
+/// read, so the limit applies to the decompressed size.
+async fn read_body(mut response: reqwest::Response, max_size: usize) -> Result<Vec<u8>, String> {
+    let size_exceeded = || {
+        format!(
+            "response exceeds maximum response size of {} bytes",
+            max_size
+        )
+    };
+
+    if response.content_length().unwrap_or_default() > max_size as u64 {
+        return Err(size_exceeded());
+    }
+
+    let mut body = Vec::new();
+
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if body.len() + chunk.len() > max_size {
+            return Err(size_exceeded());
+        }
+
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Websocket configuration refusing messages and frames over `max_response_size` while they are
+/// read, instead of once fully buffered. Limits above tungstenite's defaults are capped to them.
+pub(super) fn websocket_config(max_response_size: Option<usize>) -> Option<WebSocketConfig> {
+    let max_response_size = max_response_size?;
+    let config = WebSocketConfig::default();
+
+    Some(WebSocketConfig {
+        max_message_size: config
+            .max_message_size
+            .map(|max| max.min(max_response_size)),
+        max_frame_size: config.max_frame_size.map(|max| max.min(max_response_size)),
+        ..config
+    })
+}
+
+/// How the client keeps an idle websocket connection alive.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum KeepAlive {
+    /// Sends websocket protocol pings. This is the default.
+    #[default]
+    Ping,
+    /// Sends the given parameterless RPC method, such as `getblockcount` or `session`, as an
+    /// application-level request. Some proxies and load balancers close connections that only
+    /// exchange control frames, which requests keep open. Responses are discarded.
+    Request(String),
 }
 
 /// Describes the connection configuration parameters for the client.
@@ -66,12 +250,36 @@ pub struct ConnConfig {
     /// Full socks5 proxy url containing `scheme` usually `Socks5`, `host` and `port` if specified.
     pub proxy_host: Option<String>,
 
+    /// User-Agent header sent with the websocket handshake and HTTP POST requests. None, the
+    /// default, sends no User-Agent on websocket handshakes and reqwest's default on HTTP POST
+    /// requests.
+    pub user_agent: Option<String>,
+
+    /// Origin header sent with the websocket handshake, as required by some reverse proxies
+    /// that reject upgrades without one. None, the default, omits the header.
+    pub origin: Option<String>,
+
+    /// Additional headers sent with the websocket handshake and HTTP POST requests, such as
+    /// API keys required by authenticating gateways. Empty by default.
+    pub extra_headers: Vec<(String, String)>,
+
     /// Username to connect to proxy.
     pub proxy_username: String,
 
     /// Password to connect to proxy.
     pub proxy_password: String,
 
+    /// Reads the proxy from the `HTTPS_PROXY`, or `HTTP_PROXY` when TLS is disabled, and
+    /// `ALL_PROXY` environment variables when `proxy_host` is unset. Hosts matching `NO_PROXY`
+    /// are connected to directly. Only HTTP proxies are supported, so connecting fails with
+    /// `RpcClientError::InvalidProxy` when the proxy has another scheme, such as `socks5://`.
+    /// Environment proxies are only sent the credentials embedded in their url, never
+    /// `proxy_username` and `proxy_password`.
+    ///
+    /// Websocket connections ignore environment proxies unless this is set. HTTP POST mode
+    /// otherwise leaves proxy resolution to reqwest, which reads the same variables itself.
+    pub use_env_proxy: bool,
+
     /// Specifies whether transport layer security should be
     /// disabled.  It is recommended to always use TLS if the RPC server
     /// supports it as otherwise your username and password is sent across
@@ -84,9 +292,109 @@ pub struct ConnConfig {
     /// to start the websocket.
     pub disable_connect_on_new: bool,
 
+    /// Retries the initial connection made when creating the client with
+    /// `rpcclient::client::new` with the backoff used to reconnect, rather than failing at
+    /// once, such as when the node is still starting. Attempts are bounded by
+    /// `max_reconnect_attempts` and `max_auth_failures`. Disabled by default.
+    pub connect_retry_on_new: bool,
+
     /// Disable reconnection if websocket fails.
     pub disable_auto_reconnect: bool,
 
+    /// Number of consecutive reconnection attempts rejected for invalid credentials after
+    /// which the client stops reconnecting and is disconnected. Zero retries indefinitely.
+    pub max_auth_failures: u32,
+
+    /// Number of consecutive failed reconnection attempts after which the client stops
+    /// reconnecting, invokes `on_reconnect_giveup` and is disconnected. Zero gives up after the
+    /// first failed attempt, like one. None retries indefinitely.
+    pub max_reconnect_attempts: Option<u32>,
+
+    /// Decides how long to wait after each failed reconnection attempt. Defaults to exponential
+    /// backoff from 10 seconds up to 5 minutes. `Backoff::new` accepts the provided `Constant`,
+    /// `Exponential` and `ExponentialWithJitter` strategies or a user defined `BackoffStrategy`.
+    pub backoff: Backoff,
+
+    /// Number of worker threads notification callbacks are dispatched to, so CPU bound callbacks
+    /// do not hold up messages received from the websocket. Notifications of the same type are
+    /// always handled by the same worker, preserving their order. Zero calls callbacks directly
+    /// on the notification handler task.
+    ///
+    /// Worker queues are unbounded, so callbacks that are persistently slower than the rate
+    /// notifications arrive at grow the queued notifications in memory rather than slowing
+    /// down the websocket.
+    pub notification_workers: usize,
+
+    /// Time without any message sent or received after which the server is pinged. If the ping
+    /// is not answered within the same time the websocket is reconnected. None disables idle pings.
+    pub idle_timeout: Option<std::time::Duration>,
+
+    /// How the connection is kept alive, both periodically and once idle_timeout elapses.
+    /// Defaults to websocket pings.
+    pub keep_alive: KeepAlive,
+
+    /// Replays `on_block_connected` for blocks connected while the websocket was reconnecting.
+    /// After a reconnection, blocks from the last block connected notification received up to
+    /// the best block are fetched and passed to the callback with no transactions, as if
+    /// notified. Requires `notify_blocks`. Disabled by default.
+    pub recover_missed_blocks: bool,
+
+    /// Size in bytes above which websocket responses and notifications are refused while being
+    /// read, so they are never buffered whole. Refusing a message reconnects the websocket, and
+    /// requests awaiting a response on the lost connection fail with `ResponseTooLarge`.
+    /// HTTP responses over the limit, compressed or once decompressed, fail the request. Messages
+    /// and responses over 64 MiB are always refused. None, the default, sets no limit below that.
+    pub max_response_size: Option<usize>,
+
+    /// Sends request IDs as JSON strings, such as `"42"`, for JSON-RPC servers and proxies that
+    /// expect string IDs. Responses are matched to their requests whether the server answers
+    /// with a numeric or a string ID. Disabled by default.
+    pub string_request_ids: bool,
+
+    /// How fields returned by the server that are unknown to a result type, such as fields
+    /// added by a newer server version, are handled. Lenient, the default, keeps them in the
+    /// result's `extra_fields`, Warn also logs them and Strict fails the call.
+    pub deserialization_policy: DeserializationPolicy,
+
+    /// Name included as a `[name]` prefix in the log lines emitted by the client's tasks, telling
+    /// apart the logs of clients connected to different nodes. None, the default, adds no prefix.
+    pub connection_name: Option<String>,
+
+    /// Time after which a request not answered by the server fails, including any time spent
+    /// waiting to be sent by the rate limiter. None, the default, waits indefinitely.
+    pub request_timeout: Option<std::time::Duration>,
+
+    /// Timeouts of requests of the given methods, keyed by method name, overriding
+    /// `request_timeout` for slow methods such as `searchrawtransactions` and `verifychain` or
+    /// methods expected to be answered quickly. Empty by default.
+    pub method_timeouts: std::collections::HashMap<String, std::time::Duration>,
+
+    /// Rate requests are sent at, in requests per second, for public servers limiting the rate
+    /// of requests they accept. Requests over the rate wait to be sent, in order, and fail with a
+    /// RequestTimeout error if they can't be sent before `request_timeout`. None, the default,
+    /// sends requests as they are made.
+    pub requests_per_second: Option<f64>,
+
+    /// Number of requests that can be sent at once, after requests have not been made for a
+    /// while, before being paced to `requests_per_second`. Defaults to one.
+    pub burst: u32,
+
+    /// Queues requests made while the websocket is disconnected, such as after `disconnect` or
+    /// once reconnecting was given up, rather than failing them with an RpcDisconnected error.
+    /// Requests made while the client is reconnecting automatically are queued too. Queued
+    /// requests are sent once `connect` or the automatic reconnection reconnects the client,
+    /// after notifications are registered again. Disabled by default.
+    pub queue_while_disconnected: bool,
+
+    /// Number of requests that can be queued while disconnected, past which requests fail with
+    /// a RequestQueueFull error. Defaults to 100.
+    pub max_queued_requests: usize,
+
+    /// Time a request queued while disconnected waits for the client to reconnect, after which
+    /// it is dropped from the queue and fails with an invalid response error. Defaults to one
+    /// minute.
+    pub queued_request_timeout: std::time::Duration,
+
     /// Instructs the client to run using multiple independent
     /// connections issuing HTTP POST requests instead of using the default
     /// of websockets.  Websockets are generally preferred as some of the
@@ -94,6 +402,26 @@ pub struct ConnConfig {
     /// however, not all servers support the websocket extensions, so this
     /// flag can be set to true to use basic HTTP POST requests instead.
     pub http_post_mode: bool,
+
+    /// Stops HTTP POST requests from asking for gzip or deflate compressed responses.
+    /// Compressed responses are decompressed transparently, reducing the bandwidth used by
+    /// large results at the cost of some CPU time.
+    ///
+    /// Websocket connections are always uncompressed. permessage-deflate marks compressed
+    /// frames with the RSV1 bit, which the websocket library rejects as a protocol error, so
+    /// the extension is never offered during the handshake.
+    pub disable_http_compression: bool,
+}
+
+impl ConnConfig {
+    /// Returns the default configuration connecting to the RPC server of a local node on the
+    /// default RPC port of the given network.
+    pub fn for_network(network: &Network) -> Self {
+        ConnConfig {
+            host: format!("127.0.0.1:{}", network.rpc_port),
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for ConnConfig {
@@ -101,22 +429,51 @@ impl Default for ConnConfig {
         ConnConfig {
             certificates: String::new(),
             disable_connect_on_new: false,
+            connect_retry_on_new: false,
             disable_tls: false,
             http_post_mode: false,
+            disable_http_compression: false,
             disable_auto_reconnect: false,
+            max_auth_failures: 3,
+            max_reconnect_attempts: None,
+            backoff: Backoff::default(),
+            notification_workers: 0,
+            idle_timeout: None,
+            keep_alive: KeepAlive::default(),
+            recover_missed_blocks: false,
+            max_response_size: None,
+            string_request_ids: false,
+            deserialization_policy: DeserializationPolicy::default(),
+            connection_name: None,
+            request_timeout: None,
+            method_timeouts: std::collections::HashMap::new(),
+            requests_per_second: None,
+            burst: 1,
+            queue_while_disconnected: false,
+            max_queued_requests: constants::DEFAULT_MAX_QUEUED_REQUESTS,
+            queued_request_timeout: constants::DEFAULT_QUEUED_REQUEST_TIMEOUT,
             endpoint: String::from("ws"),
             host: "127.0.0.1:19109".to_string(),
             password: String::new(),
             proxy_host: None,
             proxy_username: String::new(),
             proxy_password: String::new(),
+            use_env_proxy: false,
             user: String::new(),
+            user_agent: None,
+            origin: None,
+            extra_headers: Vec::new(),
         }
     }
 }
 
-/// TLS or TCP Websocket connection connection.
-pub type Websocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+/// Byte stream a websocket runs over, such as a TLS or TCP stream, or an in-memory stream.
+pub trait Transport: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Transport for T {}
+
+/// Websocket connection over a TLS, TCP or in-memory transport.
+pub type Websocket = WebSocketStream<Box<dyn Transport>>;
 
 #[async_trait]
 impl RPCConn for ConnConfig {
@@ -150,6 +507,10 @@ impl RPCConn for ConnConfig {
                 }
             };
 
+        let max_response_size = self
+            .max_response_size
+            .unwrap_or(constants::MAX_HTTP_RESPONSE_SIZE);
+
         while let Some(cmd) = http_user_command.recv().await {
             let url = if self.disable_tls {
                 format!("http://{}", self.host)
@@ -187,7 +548,7 @@ impl RPCConn for ConnConfig {
             };
 
             let response = match client.execute(request).await {
-                Ok(e) => e.bytes().await,
+                Ok(e) => read_body(e, max_response_size).await,
 
                 Err(e) => {
                     warn!("Error sending RPC message to server, error: {}", e);
@@ -212,6 +573,11 @@ impl RPCConn for ConnConfig {
 
                 Err(e) => {
                     warn!("Error retrieving HTTP server response, error: {}", e);
+                    json_response.error = serde_json::Value::String(format!(
+                        "Error retrieving http response, error: {}",
+                        e
+                    ));
+
                     on_error("HTTP response".to_string(), json_response, cmd.user_channel).await;
 
                     continue;
@@ -250,6 +616,10 @@ impl RPCConn for ConnConfig {
         self.disable_connect_on_new
     }
 
+    fn connect_retry_on_new(&self) -> bool {
+        self.connect_retry_on_new
+    }
+
     fn is_http_mode(&self) -> bool {
         self.http_post_mode
     }
@@ -257,19 +627,96 @@ impl RPCConn for ConnConfig {
     fn disable_auto_reconnect(&self) -> bool {
         self.disable_auto_reconnect
     }
+
+    fn max_auth_failures(&self) -> u32 {
+        self.max_auth_failures
+    }
+
+    fn max_reconnect_attempts(&self) -> Option<u32> {
+        self.max_reconnect_attempts
+    }
+
+    fn backoff(&self) -> Backoff {
+        self.backoff.clone()
+    }
+
+    fn notification_workers(&self) -> usize {
+        self.notification_workers
+    }
+
+    fn idle_timeout(&self) -> Option<std::time::Duration> {
+        self.idle_timeout
+    }
+
+    fn keep_alive(&self) -> KeepAlive {
+        self.keep_alive.clone()
+    }
+
+    fn recover_missed_blocks(&self) -> bool {
+        self.recover_missed_blocks
+    }
+
+    fn max_response_size(&self) -> Option<usize> {
+        self.max_response_size
+    }
+
+    fn string_request_ids(&self) -> bool {
+        self.string_request_ids
+    }
+
+    fn deserialization_policy(&self) -> DeserializationPolicy {
+        self.deserialization_policy
+    }
+
+    fn connection_name(&self) -> Option<String> {
+        self.connection_name.clone()
+    }
+
+    fn request_timeout(&self) -> Option<std::time::Duration> {
+        self.request_timeout
+    }
+
+    fn method_timeout(&self, method: &str) -> Option<std::time::Duration> {
+        match self.method_timeouts.get(method) {
+            Some(timeout) => Some(*timeout),
+
+            None => self.request_timeout,
+        }
+    }
+
+    fn requests_per_second(&self) -> Option<f64> {
+        self.requests_per_second
+    }
+
+    fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    fn queue_while_disconnected(&self) -> bool {
+        self.queue_while_disconnected
+    }
+
+    fn max_queued_requests(&self) -> usize {
+        self.max_queued_requests
+    }
+
+    fn queued_request_timeout(&self) -> std::time::Duration {
+        self.queued_request_timeout
+    }
 }
 
 impl ConnConfig {
     /// Invokes a websocket stream to rpcclient using optional TLS and socks proxy.
-    async fn dial_websocket(
-        &mut self,
-    ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, RpcClientError> {
+    async fn dial_websocket(&mut self) -> Result<Websocket, RpcClientError> {
         let mut buffered_header = Vec::<u8>::new();
 
-        let stream = match self.proxy_host.clone() {
+        let proxy = self.proxy()?;
+
+        let stream = match &proxy {
             Some(proxy) => {
-                self.add_proxy_header(&mut buffered_header);
-                self.connect_stream(proxy.as_str()).await
+                let credentials = self.proxy_credentials(proxy);
+                self.add_proxy_header(&mut buffered_header, credentials);
+                self.connect_stream(&proxy_address(proxy)).await
             }
 
             None => self.connect_stream(self.host.clone().as_str()).await,
@@ -277,13 +724,9 @@ impl ConnConfig {
 
         match stream {
             Ok(mut stream) => {
-                if self.proxy_host.is_some() {
-                    if let Err(e) = self
-                        .dial_connection(&mut buffered_header, &mut stream)
-                        .await
-                    {
-                        return Err(e);
-                    }
+                if proxy.is_some() {
+                    self.dial_connection(&mut buffered_header, &mut stream)
+                        .await?;
                 }
 
                 let scheme = if self.disable_tls { "ws" } else { "wss" };
@@ -293,18 +736,36 @@ impl ConnConfig {
                 let enc = base64::encode(login.as_bytes());
                 let form = format!("Basic {}", enc);
 
-                let wrapped_request = Request::builder()
-                    .uri(host)
-                    .header("authorization", form)
-                    .body(());
+                let mut request_builder =
+                    Request::builder().uri(host).header("authorization", form);
+
+                if let Some(user_agent) = &self.user_agent {
+                    request_builder = request_builder.header("user-agent", user_agent);
+                }
+
+                if let Some(origin) = &self.origin {
+                    request_builder = request_builder.header("origin", origin);
+                }
+
+                for (name, value) in &self.extra_headers {
+                    request_builder = request_builder.header(name, value);
+                }
+
+                let wrapped_request = request_builder.body(());
 
                 match wrapped_request {
-                    Ok(request) => match tokio_tungstenite::client_async(request, stream).await {
+                    Ok(request) => match tokio_tungstenite::client_async_with_config(
+                        request,
+                        Box::new(stream) as Box<dyn Transport>,
+                        websocket_config(self.max_response_size),
+                    )
+                    .await
+                    {
                         Ok(websokcet) => Ok(websokcet.0),
 
                         Err(e) => {
                             warn!("Error creating websocket handshake, error: {}", e);
-                            Err(RpcClientError::RpcHandshake(e))
+                            Err(handshake_error(e))
                         }
                     },
 
@@ -379,9 +840,13 @@ impl ConnConfig {
         }
     }
 
-    /// Initiates proxy connection if proxy credentials are specified.
+    /// Initiates proxy connection, authenticating to the proxy if credentials are given.
     /// CONNECT header is sent to proxy server using socks5.
-    fn add_proxy_header(&mut self, buffered_header: &mut Vec<u8>) {
+    fn add_proxy_header(
+        &mut self,
+        buffered_header: &mut Vec<u8>,
+        credentials: Option<(String, String)>,
+    ) {
         buffered_header.extend_from_slice(
             format!(
                 "\
@@ -394,14 +859,16 @@ impl ConnConfig {
         );
 
         // Add Authorization to proxy server passing basic auth credentials to stream header.
-        let login = format!("{}:{}", self.user, self.password);
+        if let Some((username, password)) = credentials {
+            let login = format!("{}:{}", username, password);
 
-        let mut header_string = String::from("Basic ");
-        header_string.push_str(&base64::encode(login.as_str()));
+            let mut header_string = String::from("Basic ");
+            header_string.push_str(&base64::encode(login.as_str()));
 
-        buffered_header.extend_from_slice(
-            format!("{}: {}\r\n", "proxy-authorization", header_string).as_bytes(),
-        );
+            buffered_header.extend_from_slice(
+                format!("{}: {}\r\n", "proxy-authorization", header_string).as_bytes(),
+            );
+        }
 
         // Add trailing empty line
         buffered_header.extend_from_slice(b"\r\n");
@@ -410,7 +877,7 @@ impl ConnConfig {
     /// Dial TCP connection, sending headers.
     async fn dial_connection(
         &self,
-        buffered_header: &mut Vec<u8>,
+        buffered_header: &mut [u8],
         stream: &mut MaybeTlsStream<TcpStream>,
     ) -> Result<(), RpcClientError> {
         match stream.write_all(buffered_header).await {
@@ -465,10 +932,111 @@ impl ConnConfig {
         }
     }
 
+    /// Returns the proxy connections are made through, resolving it from the environment when
+    /// `proxy_host` is unset and `use_env_proxy` is set. Environment proxies other than HTTP
+    /// proxies are refused.
+    pub(super) fn proxy(&self) -> Result<Option<Url>, RpcClientError> {
+        self.resolve_proxy(|name| std::env::var(name).ok())
+    }
+
+    /// Resolves the proxy as `proxy` does, reading environment variables with `var`.
+    pub(super) fn resolve_proxy(
+        &self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Url>, RpcClientError> {
+        // Returns the value of the first set and non-empty variable of names.
+        let env_var = |names: &[&str]| {
+            names
+                .iter()
+                .filter_map(|name| var(name))
+                .find(|value| !value.is_empty())
+        };
+
+        if let Some(proxy_host) = &self.proxy_host {
+            return parse_proxy(proxy_host).map(Some);
+        }
+
+        if !self.use_env_proxy {
+            return Ok(None);
+        }
+
+        let host = self.host.as_str();
+        let hostname = host.rsplit_once(':').map_or(host, |(hostname, _)| hostname);
+
+        let excluded = env_var(&["NO_PROXY", "no_proxy"]).is_some_and(|no_proxy| {
+            no_proxy.split(',').map(str::trim).any(|entry| {
+                let entry = entry.trim_start_matches('.');
+
+                entry == "*"
+                    || entry == host
+                    || entry == hostname
+                    || (!entry.is_empty() && hostname.ends_with(&format!(".{}", entry)))
+            })
+        });
+
+        if excluded {
+            info!("Host {} excluded from environment proxy", host);
+            return Ok(None);
+        }
+
+        let scheme_proxy = if self.disable_tls {
+            ["HTTP_PROXY", "http_proxy"]
+        } else {
+            ["HTTPS_PROXY", "https_proxy"]
+        };
+
+        let proxy = match env_var(&scheme_proxy).or_else(|| env_var(&["ALL_PROXY", "all_proxy"])) {
+            Some(proxy) => parse_proxy(&proxy)?,
+
+            None => return Ok(None),
+        };
+
+        // The websocket dialer can only tunnel through an HTTP proxy with CONNECT.
+        if proxy.scheme() != "http" {
+            warn!(
+                "Environment proxy has unsupported scheme {}, only HTTP proxies are supported",
+                proxy.scheme()
+            );
+
+            return Err(RpcClientError::InvalidProxy(format!(
+                "unsupported scheme {}",
+                proxy.scheme()
+            )));
+        }
+
+        Ok(Some(proxy))
+    }
+
+    /// Returns the credentials sent to the given proxy: those embedded in its url, or else
+    /// `proxy_username` and `proxy_password` when the proxy is set with `proxy_host`. Proxies
+    /// read from the environment are never sent the credentials of the configuration.
+    pub(super) fn proxy_credentials(&self, proxy: &Url) -> Option<(String, String)> {
+        if !proxy.username().is_empty() || proxy.password().is_some() {
+            let decode = |component: &str| {
+                percent_decode_str(component)
+                    .decode_utf8_lossy()
+                    .into_owned()
+            };
+
+            return Some((
+                decode(proxy.username()),
+                decode(proxy.password().unwrap_or_default()),
+            ));
+        }
+
+        if self.proxy_host.is_some()
+            && (!self.proxy_username.is_empty() || !self.proxy_password.is_empty())
+        {
+            return Some((self.proxy_username.clone(), self.proxy_password.clone()));
+        }
+
+        None
+    }
+
     fn create_http_client(&self) -> Result<reqwest::Client, RpcClientError> {
-        let proxy = match self.proxy_host.clone() {
+        let proxy = match self.proxy()? {
             Some(proxy) => {
-                let proxy = reqwest::Proxy::all(proxy);
+                let proxy = reqwest::Proxy::all(proxy.as_str());
 
                 let proxy = match proxy {
                     Ok(e) => e,
@@ -479,7 +1047,10 @@ impl ConnConfig {
                     }
                 };
 
-                let proxy = if !self.proxy_password.is_empty() || !self.proxy_username.is_empty() {
+                // Credentials embedded in the proxy url are sent by reqwest itself.
+                let proxy = if self.proxy_host.is_some()
+                    && (!self.proxy_password.is_empty() || !self.proxy_username.is_empty())
+                {
                     proxy.basic_auth(&self.proxy_username, &self.proxy_password)
                 } else {
                     proxy
@@ -495,22 +1066,38 @@ impl ConnConfig {
         request_builder = match proxy {
             Some(e) => request_builder.proxy(e),
 
+            // Reqwest reads proxy environment variables itself unless told otherwise, which is
+            // left to it unless they were resolved here.
+            None if self.use_env_proxy => request_builder.no_proxy(),
+
             None => request_builder,
         };
 
-        request_builder = match reqwest::Certificate::from_pem(self.certificates.as_bytes()) {
-            Ok(certificate) => {
-                // ToDo: check if host name is an ip before accepting invalid hostname.
-                request_builder
-                    .add_root_certificate(certificate)
-                    .danger_accept_invalid_certs(true)
-            }
+        // Certificates are only used, and so only required, for TLS connections.
+        if !self.disable_tls {
+            request_builder = match reqwest::Certificate::from_pem(self.certificates.as_bytes()) {
+                Ok(certificate) => {
+                    // ToDo: check if host name is an ip before accepting invalid hostname.
+                    request_builder
+                        .add_root_certificate(certificate)
+                        .danger_accept_invalid_certs(true)
+                }
 
-            Err(e) => {
-                warn!("Error parsing tls certificate, error: {}", e);
-                return Err(RpcClientError::HttpTlsCertificate(e));
-            }
-        };
+                Err(e) => {
+                    warn!("Error parsing tls certificate, error: {}", e);
+                    return Err(RpcClientError::HttpTlsCertificate(e));
+                }
+            };
+        }
+
+        // Reqwest asks for gzip and deflate compressed responses and decompresses them itself.
+        if self.disable_http_compression {
+            request_builder = request_builder.no_gzip().no_deflate();
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            request_builder = request_builder.user_agent(user_agent);
+        }
 
         let mut headers = reqwest::header::HeaderMap::new();
 
@@ -527,6 +1114,35 @@ impl ConnConfig {
 
         headers.append(reqwest::header::CONTENT_TYPE, header_value);
 
+        for (name, value) in &self.extra_headers {
+            let header_name = match reqwest::header::HeaderName::from_bytes(name.as_bytes()) {
+                Ok(e) => e,
+                Err(e) => {
+                    warn!(
+                        "Invalid header name {} in HTTP Post mode, error: {}",
+                        name, e
+                    );
+                    return Err(RpcClientError::InvalidParameter(format!(
+                        "invalid header name {}",
+                        name
+                    )));
+                }
+            };
+
+            let header_value = match reqwest::header::HeaderValue::from_str(value) {
+                Ok(e) => e,
+                Err(e) => {
+                    warn!(
+                        "Invalid value for header {} in HTTP Post mode, error: {}",
+                        name, e
+                    );
+                    return Err(RpcClientError::HttpHeader(e));
+                }
+            };
+
+            headers.append(header_name, header_value);
+        }
+
         let request_builder = request_builder.default_headers(headers);
 
         match request_builder.build() {
@@ -542,3 +1158,26 @@ impl ConnConfig {
         }
     }
 }
+
+/// Parses a proxy url. Proxies given without a scheme are HTTP proxies.
+fn parse_proxy(proxy: &str) -> Result<Url, RpcClientError> {
+    let parsed = if proxy.contains("://") {
+        Url::parse(proxy)
+    } else {
+        Url::parse(&format!("http://{}", proxy))
+    };
+
+    parsed.map_err(|e| {
+        warn!("Error parsing proxy url, error: {}", e);
+        RpcClientError::InvalidProxy(e.to_string())
+    })
+}
+
+/// Returns the `host:port` address of a proxy url, dropping its scheme, credentials and path.
+fn proxy_address(proxy: &Url) -> String {
+    format!(
+        "{}:{}",
+        proxy.host_str().unwrap_or_default(),
+        proxy.port_or_known_default().unwrap_or(80)
+    )
+}
@@ -1,8 +1,59 @@
 #![allow(dead_code)]
+use crate::dcrjson::RpcErrorCode;
+
 /// Time required to retry connecting to websocket.
 pub(super) const CONNECTION_RETRY_INTERVAL_SECS: std::time::Duration =
     std::time::Duration::from_secs(10);
+/// Maximum time waited between websocket reconnection attempts by default.
+pub(super) const MAX_CONNECTION_RETRY_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(300);
+/// Time allowed for a websocket write to complete before reconnecting.
+pub(super) const WRITE_TIMEOUT_SECS: std::time::Duration = std::time::Duration::from_secs(30);
+/// Size in bytes above which HTTP responses are refused, once decompressed, if no maximum
+/// response size is set. Matches the size above which websocket messages are refused.
+pub(super) const MAX_HTTP_RESPONSE_SIZE: usize = 64 << 20;
 /// Number of elements the websocket send channel can queue before blocking.
 pub(super) const SEND_BUFFER_SIZE: usize = 50;
 /// The required timeframe to send pings to websocket.
 pub(super) const KEEP_ALIVE: u64 = 10;
+/// ID of keep-alive requests. Client request IDs start at 1, so responses to keep-alive
+/// requests are never mistaken for a pending request's.
+pub(super) const KEEP_ALIVE_REQUEST_ID: u64 = 0;
+/// Error code returned by the server when no information is available about a transaction.
+pub(super) const ERR_RPC_NO_TX_INFO: RpcErrorCode = RpcErrorCode::InvalidAddressOrKey;
+/// Number of requests that can be queued while disconnected by default.
+pub(super) const DEFAULT_MAX_QUEUED_REQUESTS: usize = 100;
+/// Time a request queued while disconnected waits for the client to reconnect by default.
+pub(super) const DEFAULT_QUEUED_REQUEST_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(60);
+/// Maximum number of block hashes requested at once by get_block_hash_range.
+pub(super) const MAX_BLOCK_HASH_RANGE: i64 = 1000;
+/// Maximum number of block subsidies sampled at once by reward_schedule.
+pub(super) const MAX_REWARD_SCHEDULE_SAMPLES: i64 = 1000;
+/// Number of transactions requested per searchrawtransactions page.
+pub(super) const SEARCH_RAW_TRANSACTIONS_COUNT: i32 = 100;
+/// Interval confirmations are polled at when block notifications are unavailable.
+pub(super) const CONFIRMATION_POLL_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(30);
+/// Number of recently streamed block hashes block_stream keeps to find the fork point of a
+/// reorganization.
+pub(super) const BLOCK_STREAM_REORG_DEPTH: usize = 256;
+/// Number of winning tickets events winning_tickets_channel queues before waiting on the receiver.
+pub(super) const WINNING_TICKETS_CHANNEL_SIZE: usize = 16;
+/// Interval wait_until_ready first polls the server's sync state at, doubled after every poll.
+pub(super) const READINESS_POLL_INTERVAL: std::time::Duration =
+    std::time::Duration::from_millis(100);
+/// Maximum interval wait_until_ready polls the server's sync state at.
+pub(super) const MAX_READINESS_POLL_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(10);
+/// Maximum number of getrawtransaction requests get_raw_transactions keeps in flight.
+pub(super) const MAX_RAW_TRANSACTIONS_IN_FLIGHT: usize = 100;
+/// Time the server has to accept every notification registration replayed on reconnection
+/// before the reconnection is retried.
+pub(super) const NOTIFICATION_REPLAY_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(30);
+/// Default minimum fee rate, in DCR/kB, servers relay transactions at.
+pub(super) const DEFAULT_MIN_RELAY_FEE_RATE: f64 = 0.0001;
+/// Websocket close code sent by servers refusing a client by policy, such as when its access
+/// was revoked.
+pub(super) const CLOSE_POLICY_VIOLATION: u16 = 1008;
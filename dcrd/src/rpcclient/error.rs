@@ -1,5 +1,13 @@
 //! Contains all RPC client errors.
-use {thiserror::Error, tokio_native_tls::native_tls};
+use {
+    crate::{
+        chaincfg::chainhash::ChainHashError,
+        dcrjson::{result_types::RpcError, RpcServerError},
+        dcrutil::{address::AddressError, BlockError},
+    },
+    thiserror::Error,
+    tokio_native_tls::native_tls,
+};
 /// RPC client errors
 #[derive(Error, Debug)]
 pub enum RpcClientError {
@@ -13,6 +21,9 @@ pub enum RpcClientError {
     /// Invalid authentication to RPC.
     #[error("rpc authentication error")]
     RpcAuthenticationRequest,
+    /// Credentials were rejected by the RPC server.
+    #[error("rpc server rejected authentication credentials")]
+    AuthenticationFailed,
     /// Invalid tcp connection to RPC server.
     #[error("tcp stream error: {0}")]
     TcpStream(std::io::Error),
@@ -27,10 +38,13 @@ pub enum RpcClientError {
     TlsStream(native_tls::Error),
     /// Invalid rpc open command.
     #[error("rpc handshake error: {0}")]
-    RpcHandshake(tokio_tungstenite::tungstenite::Error),
+    RpcHandshake(Box<tokio_tungstenite::tungstenite::Error>),
     /// Invalid proxy connection
     #[error("invalid proxy connection")]
     ProxyConnection,
+    /// Proxy url is malformed or, for proxies read from the environment, not an HTTP proxy.
+    #[error("invalid proxy: {0}")]
+    InvalidProxy(String),
     /// Failed to set proxy authentication.
     #[error("proxy authentication request error: {0}")]
     ProxyAuthentication(std::io::Error),
@@ -44,6 +58,10 @@ pub enum RpcClientError {
     #[error("rpc client disconnected")]
     RpcDisconnected,
 
+    /// Request sent by a listen only client.
+    #[error("client is listen only and can't send requests")]
+    ListenOnly,
+
     /// Websocket already connected to server.
     #[error("websocket already connected to RPC server")]
     WebsocketAlreadyConnected,
@@ -56,4 +74,69 @@ pub enum RpcClientError {
     /// Invalid http handshake to server.
     #[error("error initiating HTTP Hanshake in HTTP Post mode, error: {0}")]
     HttpHandshake(reqwest::Error),
+
+    /// Invalid parameter passed to a command.
+    #[error("invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    /// Error returned by server while resolving a command.
+    #[error("rpc server error: {0}")]
+    RpcServer(RpcServerError),
+    /// Invalid chain hash.
+    #[error("chain hash error: {0}")]
+    ChainHash(ChainHashError),
+
+    /// Transaction did not reach the requested number of confirmations in time.
+    #[error("timed out waiting for transaction {0} to confirm")]
+    ConfirmationTimeout(String),
+    /// Server did not finish its initial block download in time.
+    #[error("timed out waiting for the server to finish its initial block download")]
+    ReadinessTimeout,
+    /// Confirmed transaction was removed from the main chain by a reorganization.
+    #[error("transaction {0} was reorganized out of the main chain")]
+    TransactionReorganized(String),
+    /// Server's genesis block differs from the expected network's genesis block.
+    #[error("server genesis block {found} does not match expected genesis block {expected}")]
+    GenesisMismatch { expected: String, found: String },
+    /// Server is on a network unknown to `chaincfg::params`.
+    #[error("server is on unknown network {0:#010x}")]
+    UnknownNetwork(u32),
+    /// Network has not been detected with `detect_and_set_network`.
+    #[error("network not detected")]
+    NetworkNotDetected,
+    /// Address is invalid or of another network.
+    #[error("address error: {0}")]
+    Address(AddressError),
+    /// Request could not be sent by the rate limiter before the request timeout.
+    #[error("request timed out waiting to be sent")]
+    RequestTimeout,
+    /// Request made while disconnected could not be queued as the given maximum number of
+    /// requests are already queued.
+    #[error("request queue full, {0} requests are already queued")]
+    RequestQueueFull(usize),
+    /// Transaction was rejected by the server, such as when it already exists or pays too high
+    /// a fee.
+    #[error("transaction rejected by the server: {}", .0.message)]
+    TransactionRejected(RpcError),
+    /// Block is malformed or doesn't match its header or requested hash.
+    #[error("block error: {0}")]
+    Block(BlockError),
+    /// Notifications that failed to register, by registration command.
+    #[error("failed to register notifications: {}", registration_failures(.0))]
+    NotificationRegistration(Vec<(String, RpcClientError)>),
+}
+
+/// Lists failed notification registrations with their errors.
+fn registration_failures(failures: &[(String, RpcClientError)]) -> String {
+    failures
+        .iter()
+        .map(|(method, e)| format!("{} ({})", method, e))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl From<RpcServerError> for RpcClientError {
+    fn from(e: RpcServerError) -> Self {
+        RpcClientError::RpcServer(e)
+    }
 }
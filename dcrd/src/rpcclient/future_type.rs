@@ -3,15 +3,16 @@
 
 use {
     crate::dcrjson::{
-        result_types,
-        result_types::{JsonResponse, RpcError},
-        RpcServerError,
+        decode_result, result_types,
+        result_types::{ClientError, JsonResponse, RpcError},
+        DeserializationPolicy, RpcServerError,
     },
     core::future::Future,
     core::pin::Pin,
     core::task::{Context, Poll},
     log::{trace, warn},
-    tokio::sync::mpsc,
+    std::collections::HashMap,
+    tokio::{sync::mpsc, time},
 };
 
 /// Build a RPC command future type.
@@ -19,11 +20,35 @@ macro_rules! build_future {
     ($struct_name:ident, $output:ty) => {
         pub struct $struct_name {
             pub(crate) message: mpsc::Receiver<JsonResponse>,
+            pub(crate) policy: DeserializationPolicy,
+            pub(crate) deadline: Option<Pin<Box<time::Sleep>>>,
         }
 
         impl $struct_name {
             pub fn new(rcvr: mpsc::Receiver<JsonResponse>) -> $struct_name {
-                Self { message: rcvr }
+                Self {
+                    message: rcvr,
+                    policy: DeserializationPolicy::default(),
+                    deadline: None,
+                }
+            }
+
+            /// Sets the time after which the future fails with a Timeout error if the server
+            /// has not responded.
+            pub(crate) fn with_deadline(mut self, deadline: Option<time::Instant>) -> $struct_name {
+                self.deadline = deadline.map(|deadline| Box::pin(time::sleep_until(deadline)));
+                self
+            }
+
+            /// Sets how fields of the result unknown to its type are handled.
+            pub fn with_policy(mut self, policy: DeserializationPolicy) -> $struct_name {
+                self.policy = policy;
+                self
+            }
+
+            /// Returns how fields of the result unknown to its type are handled.
+            pub fn policy(&self) -> DeserializationPolicy {
+                self.policy
             }
         }
 
@@ -44,7 +69,20 @@ macro_rules! build_future {
                         }
                     },
 
-                    Poll::Pending => Poll::Pending,
+                    Poll::Pending => {
+                        let timed_out = match self.deadline.as_mut() {
+                            Some(deadline) => deadline.as_mut().poll(cx).is_ready(),
+
+                            None => false,
+                        };
+
+                        if timed_out {
+                            warn!("Server did not respond before the request timed out");
+                            return Poll::Ready(Err(RpcServerError::Timeout));
+                        }
+
+                        Poll::Pending
+                    }
                 }
             }
         }
@@ -77,12 +115,12 @@ impl GetBlockchainInfoFuture {
             return Err(get_error_value(message.error));
         }
 
-        let val = match serde_json::from_value(message.result) {
+        let val = match decode_result(message.result, self.policy) {
             Ok(val) => val,
 
             Err(e) => {
                 warn!("error marshalling Get Blockchain Info result");
-                return Err(RpcServerError::Marshaller(e));
+                return Err(e);
             }
         };
 
@@ -146,6 +184,119 @@ impl GetBlockHashFuture {
     }
 }
 
+build_future![GetBestBlockHashFuture, Result<crate::chaincfg::chainhash::Hash, RpcServerError>];
+
+impl GetBestBlockHashFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<crate::chaincfg::chainhash::Hash, RpcServerError> {
+        trace!("server sent a Get Best Block Hash result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match crate::dcrjson::marshal_to_hash(message.result) {
+            Some(hash) => Ok(hash),
+
+            None => Err(RpcServerError::InvalidResponse(
+                "invalid best block hash".to_string(),
+            )),
+        }
+    }
+}
+
+build_future![GetBlockFuture, Result<Vec<u8>, RpcServerError>];
+
+impl GetBlockFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<Vec<u8>, RpcServerError> {
+        trace!("server sent a Get Block result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match crate::dcrjson::parse_hex_parameters(&message.result) {
+            Some(block) => Ok(block),
+
+            None => {
+                warn!("error decoding serialized block");
+                Err(RpcServerError::InvalidResponse(
+                    "invalid serialized block".into(),
+                ))
+            }
+        }
+    }
+}
+
+build_future![GetBlockHeaderFuture, Result<Vec<u8>, RpcServerError>];
+
+impl GetBlockHeaderFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<Vec<u8>, RpcServerError> {
+        trace!("server sent a Get Block Header result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match crate::dcrjson::parse_hex_parameters(&message.result) {
+            Some(header) => Ok(header),
+
+            None => {
+                warn!("error decoding serialized block header");
+                Err(RpcServerError::InvalidResponse(
+                    "invalid serialized block header".into(),
+                ))
+            }
+        }
+    }
+}
+
+build_future![
+    GetBlockHeaderVerboseFuture,
+    Result<result_types::GetBlockHeaderVerboseResult, RpcServerError>
+];
+impl GetBlockHeaderVerboseFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetBlockHeaderVerboseResult, RpcServerError> {
+        trace!("server sent a Get Block Header Verbose result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Block Header Verbose result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetBlockHexFuture, Result<String, RpcServerError>];
+impl GetBlockHexFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<String, RpcServerError> {
+        trace!("server sent a Get Block result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match serde_json::from_value(message.result) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Block result");
+                Err(RpcServerError::Marshaller(e))
+            }
+        }
+    }
+}
+
 build_future![GetBlockVerboseFuture, Result<result_types::GetBlockVerboseResult, RpcServerError>];
 impl GetBlockVerboseFuture {
     fn on_message(
@@ -157,12 +308,12 @@ impl GetBlockVerboseFuture {
             return Err(get_error_value(message.error));
         }
 
-        match serde_json::from_value(message.result) {
+        match decode_result(message.result, self.policy) {
             Ok(val) => Ok(val),
 
             Err(e) => {
                 warn!("error marshalling Get Block Verbose result");
-                Err(RpcServerError::Marshaller(e))
+                Err(e)
             }
         }
     }
@@ -179,12 +330,12 @@ impl DecodeRawTransactionFuture {
             return Err(get_error_value(message.error));
         }
 
-        match serde_json::from_value(message.result) {
+        match decode_result(message.result, self.policy) {
             Ok(val) => Ok(val),
 
             Err(e) => {
                 warn!("error marshalling Get Block Verbose result");
-                Err(RpcServerError::Marshaller(e))
+                Err(e)
             }
         }
     }
@@ -196,7 +347,36 @@ impl EstimateSmartFeeFuture {
         &self,
         message: JsonResponse,
     ) -> Result<result_types::EstimateSmartFeeResult, RpcServerError> {
-        trace!("server sent a Get Block Verbose result");
+        trace!("server sent an Estimate Smart Fee result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        let estimate: result_types::EstimateSmartFeeResult =
+            match decode_result(message.result, self.policy) {
+                Ok(val) => val,
+
+                Err(e) => {
+                    warn!("error marshalling Estimate Smart Fee result");
+                    return Err(e);
+                }
+            };
+
+        // The fee rate is meaningless when the server could not estimate it.
+        if !estimate.errors.is_empty() {
+            return Err(RpcServerError::NoFeeEstimate(estimate.errors));
+        }
+
+        Ok(estimate)
+    }
+}
+
+build_future![EstimateFeeFuture, Result<f64, RpcServerError>];
+
+impl EstimateFeeFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<f64, RpcServerError> {
+        trace!("server sent an Estimate Fee result");
+
         if !message.error.is_null() {
             return Err(get_error_value(message.error));
         }
@@ -205,14 +385,695 @@ impl EstimateSmartFeeFuture {
             Ok(val) => Ok(val),
 
             Err(e) => {
-                warn!("error marshalling Get Block Verbose result");
+                warn!("error marshalling Estimate Fee result");
                 Err(RpcServerError::Marshaller(e))
             }
         }
     }
 }
 
+build_future![
+    GetStakeDifficultyFuture,
+    Result<result_types::GetStakeDifficultyResult, RpcServerError>
+];
+
+impl GetStakeDifficultyFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetStakeDifficultyResult, RpcServerError> {
+        trace!("server sent a Get Stake Difficulty result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Stake Difficulty result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![
+    EstimateStakeDiffFuture,
+    Result<result_types::EstimateStakeDiffResult, RpcServerError>
+];
+
+impl EstimateStakeDiffFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::EstimateStakeDiffResult, RpcServerError> {
+        trace!("server sent an Estimate Stake Diff result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Estimate Stake Diff result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetCurrentNetFuture, Result<u32, RpcServerError>];
+
+impl GetCurrentNetFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<u32, RpcServerError> {
+        trace!("server sent a Get Current Net result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match serde_json::from_value(message.result) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Current Net result");
+                Err(RpcServerError::Marshaller(e))
+            }
+        }
+    }
+}
+
+build_future![
+    GetRawMempoolFuture,
+    Result<Vec<crate::chaincfg::chainhash::Hash>, RpcServerError>
+];
+
+impl GetRawMempoolFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<Vec<crate::chaincfg::chainhash::Hash>, RpcServerError> {
+        trace!("server sent a Get Raw Mempool result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        let hashes: Vec<String> = match serde_json::from_value(message.result) {
+            Ok(val) => val,
+
+            Err(e) => {
+                warn!("error marshalling Get Raw Mempool result");
+                return Err(RpcServerError::Marshaller(e));
+            }
+        };
+
+        hashes
+            .iter()
+            .map(|hash| {
+                crate::chaincfg::chainhash::Hash::new_from_str(hash).map_err(|e| {
+                    warn!("invalid hash bytes from server, error: {}.", e);
+                    RpcServerError::InvalidResponse(format!("{}", e))
+                })
+            })
+            .collect()
+    }
+}
+
+build_future![
+    GetTxOutFuture,
+    Result<Option<result_types::GetTxOutResult>, RpcServerError>
+];
+
+impl GetTxOutFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<Option<result_types::GetTxOutResult>, RpcServerError> {
+        trace!("server sent a Get Tx Out result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        // A null result means the output is spent or never existed.
+        if message.result.is_null() {
+            return Ok(None);
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(Some(val)),
+
+            Err(e) => {
+                warn!("error marshalling Get Tx Out result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetBestBlockFuture, Result<result_types::GetBestBlockResult, RpcServerError>];
+impl GetBestBlockFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetBestBlockResult, RpcServerError> {
+        trace!("server sent a Get Best Block result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Best Block result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetMempoolInfoFuture, Result<result_types::GetMempoolInfoResult, RpcServerError>];
+impl GetMempoolInfoFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetMempoolInfoResult, RpcServerError> {
+        trace!("server sent a Get Mempool Info result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Mempool Info result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetNetTotalsFuture, Result<result_types::GetNetTotalsResult, RpcServerError>];
+impl GetNetTotalsFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetNetTotalsResult, RpcServerError> {
+        trace!("server sent a Get Net Totals result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Net Totals result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetMiningInfoFuture, Result<result_types::GetMiningInfoResult, RpcServerError>];
+impl GetMiningInfoFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetMiningInfoResult, RpcServerError> {
+        trace!("server sent a Get Mining Info result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Mining Info result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetBlockSubsidyFuture, Result<result_types::GetBlockSubsidyResult, RpcServerError>];
+impl GetBlockSubsidyFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetBlockSubsidyResult, RpcServerError> {
+        trace!("server sent a Get Block Subsidy result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Block Subsidy result");
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Future of the hash of a transaction sent by `send_raw_transaction`.
+pub(crate) struct SendRawTransactionFuture {
+    pub(crate) message: mpsc::Receiver<JsonResponse>,
+}
+
+impl Future for SendRawTransactionFuture {
+    type Output = Result<crate::chaincfg::chainhash::Hash, RpcServerError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.message.poll_recv(cx) {
+            Poll::Ready(message) => match message {
+                Some(msg) => Poll::Ready(self.on_message(msg)),
+
+                None => {
+                    warn!("Server sent an empty response");
+                    Poll::Ready(Err(RpcServerError::EmptyResponse))
+                }
+            },
+
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl SendRawTransactionFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<crate::chaincfg::chainhash::Hash, RpcServerError> {
+        trace!("server sent a Send Raw Transaction result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match crate::dcrjson::marshal_to_hash(message.result) {
+            Some(hash) => Ok(hash),
+
+            None => Err(RpcServerError::InvalidResponse(
+                "invalid transaction hash".to_string(),
+            )),
+        }
+    }
+}
+
+/// Future returned by `exists_addresses`. It keeps the requested addresses so that the
+/// returned bitset can be paired with them.
+pub struct ExistsAddressesFuture {
+    pub(crate) message: mpsc::Receiver<JsonResponse>,
+    pub(crate) addresses: Vec<String>,
+    pub(crate) deadline: Option<Pin<Box<time::Sleep>>>,
+}
+
+impl Future for ExistsAddressesFuture {
+    type Output = Result<result_types::ExistsAddressesResult, RpcServerError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.message.poll_recv(cx) {
+            Poll::Ready(message) => match message {
+                Some(msg) => {
+                    let val = self.on_message(msg);
+                    Poll::Ready(val)
+                }
+
+                None => {
+                    warn!("Server sent an empty response");
+                    Poll::Ready(Err(RpcServerError::EmptyResponse))
+                }
+            },
+
+            Poll::Pending => {
+                let timed_out = match self.deadline.as_mut() {
+                    Some(deadline) => deadline.as_mut().poll(cx).is_ready(),
+
+                    None => false,
+                };
+
+                if timed_out {
+                    warn!("Server did not respond before the request timed out");
+                    return Poll::Ready(Err(RpcServerError::Timeout));
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl ExistsAddressesFuture {
+    /// Sets the time after which the future fails with a Timeout error if the server has not
+    /// responded.
+    pub(crate) fn with_deadline(mut self, deadline: Option<time::Instant>) -> Self {
+        self.deadline = deadline.map(|deadline| Box::pin(time::sleep_until(deadline)));
+        self
+    }
+
+    fn on_message(
+        &mut self,
+        message: JsonResponse,
+    ) -> Result<result_types::ExistsAddressesResult, RpcServerError> {
+        trace!("server sent an Exists Addresses result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        let bitset = match crate::dcrjson::parse_hex_parameters(&message.result) {
+            Some(bitset) => bitset,
+
+            None => {
+                warn!("error decoding Exists Addresses bitset");
+                return Err(RpcServerError::InvalidResponse(
+                    "invalid exists addresses bitset".into(),
+                ));
+            }
+        };
+
+        let addresses = std::mem::take(&mut self.addresses);
+
+        result_types::ExistsAddressesResult::from_bitset(addresses, &bitset)
+    }
+}
+
+build_future![
+    GetHeadersFuture,
+    Result<Vec<result_types::GetBlockHeaderVerboseResult>, RpcServerError>
+];
+impl GetHeadersFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<Vec<result_types::GetBlockHeaderVerboseResult>, RpcServerError> {
+        trace!("server sent a Get Headers result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        let result: result_types::GetHeadersResult = match serde_json::from_value(message.result) {
+            Ok(val) => val,
+
+            Err(e) => {
+                warn!("error marshalling Get Headers result");
+                return Err(RpcServerError::Marshaller(e));
+            }
+        };
+
+        result.decode()
+    }
+}
+
+build_future![GetStakeVersionInfoFuture, Result<result_types::GetStakeVersionInfoResult, RpcServerError>];
+impl GetStakeVersionInfoFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetStakeVersionInfoResult, RpcServerError> {
+        trace!("server sent a Get Stake Version Info result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Stake Version Info result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetVoteInfoFuture, Result<result_types::GetVoteInfoResult, RpcServerError>];
+impl GetVoteInfoFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::GetVoteInfoResult, RpcServerError> {
+        trace!("server sent a Get Vote Info result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Vote Info result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetRawTransactionVerboseFuture, Result<result_types::TxRawResult, RpcServerError>];
+impl GetRawTransactionVerboseFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<result_types::TxRawResult, RpcServerError> {
+        trace!("server sent a Get Raw Transaction Verbose result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match decode_result(message.result, self.policy) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Raw Transaction Verbose result");
+                Err(e)
+            }
+        }
+    }
+}
+
+build_future![GetRawTransactionFuture, Result<Vec<u8>, RpcServerError>];
+
+impl GetRawTransactionFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<Vec<u8>, RpcServerError> {
+        trace!("server sent a Get Raw Transaction result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match crate::dcrjson::parse_hex_parameters(&message.result) {
+            Some(tx) => Ok(tx),
+
+            None => {
+                warn!("error decoding serialized transaction");
+                Err(RpcServerError::InvalidResponse(
+                    "invalid serialized transaction".into(),
+                ))
+            }
+        }
+    }
+}
+
+build_future![CreateRawTransactionFuture, Result<Vec<u8>, RpcServerError>];
+
+impl CreateRawTransactionFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<Vec<u8>, RpcServerError> {
+        trace!("server sent a Create Raw Transaction result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match crate::dcrjson::parse_hex_parameters(&message.result) {
+            Some(tx) => Ok(tx),
+
+            None => {
+                warn!("error decoding serialized transaction");
+                Err(RpcServerError::InvalidResponse(
+                    "invalid serialized transaction".into(),
+                ))
+            }
+        }
+    }
+}
+
+build_future![GetRawTransactionHexFuture, Result<String, RpcServerError>];
+impl GetRawTransactionHexFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<String, RpcServerError> {
+        trace!("server sent a Get Raw Transaction result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match serde_json::from_value(message.result) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Raw Transaction result");
+                Err(RpcServerError::Marshaller(e))
+            }
+        }
+    }
+}
+
+build_future![
+    TestMempoolAcceptFuture,
+    Result<Vec<result_types::MempoolAcceptResult>, RpcServerError>
+];
+impl TestMempoolAcceptFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<Vec<result_types::MempoolAcceptResult>, RpcServerError> {
+        trace!("server sent a Test Mempool Accept result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        let results: Vec<serde_json::Value> = match serde_json::from_value(message.result) {
+            Ok(val) => val,
+
+            Err(e) => {
+                warn!("error marshalling Test Mempool Accept result");
+                return Err(RpcServerError::Marshaller(e));
+            }
+        };
+
+        results
+            .into_iter()
+            .map(|result| decode_result(result, self.policy()))
+            .collect()
+    }
+}
+
+build_future![
+    SearchRawTransactionsVerboseFuture,
+    Result<Vec<result_types::TxRawResult>, RpcServerError>
+];
+impl SearchRawTransactionsVerboseFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<Vec<result_types::TxRawResult>, RpcServerError> {
+        trace!("server sent a Search Raw Transactions Verbose result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match serde_json::from_value(message.result) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Search Raw Transactions Verbose result");
+                Err(RpcServerError::Marshaller(e))
+            }
+        }
+    }
+}
+
+build_future![GetNetworkHashPsFuture, Result<i64, RpcServerError>];
+
+impl GetNetworkHashPsFuture {
+    fn on_message(&self, message: JsonResponse) -> Result<i64, RpcServerError> {
+        trace!("server sent a Get Network Hash PS result");
+
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        match serde_json::from_value(message.result) {
+            Ok(val) => Ok(val),
+
+            Err(e) => {
+                warn!("error marshalling Get Network Hash PS result");
+                Err(RpcServerError::Marshaller(e))
+            }
+        }
+    }
+}
+
+build_future![
+    GetPeerInfoFuture,
+    Result<Vec<result_types::GetPeerInfoResult>, RpcServerError>
+];
+impl GetPeerInfoFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<Vec<result_types::GetPeerInfoResult>, RpcServerError> {
+        trace!("server sent a Get Peer Info result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        let peers: Vec<serde_json::Value> = match serde_json::from_value(message.result) {
+            Ok(val) => val,
+
+            Err(e) => {
+                warn!("error marshalling Get Peer Info result");
+                return Err(RpcServerError::Marshaller(e));
+            }
+        };
+
+        peers
+            .into_iter()
+            .map(|peer| decode_result(peer, self.policy))
+            .collect()
+    }
+}
+
+build_future![
+    GetRawMempoolVerboseFuture,
+    Result<HashMap<String, result_types::GetRawMempoolVerboseResult>, RpcServerError>
+];
+impl GetRawMempoolVerboseFuture {
+    fn on_message(
+        &self,
+        message: JsonResponse,
+    ) -> Result<HashMap<String, result_types::GetRawMempoolVerboseResult>, RpcServerError> {
+        trace!("server sent a Get Raw Mempool Verbose result");
+        if !message.error.is_null() {
+            return Err(get_error_value(message.error));
+        }
+
+        let transactions: HashMap<String, serde_json::Value> =
+            match serde_json::from_value(message.result) {
+                Ok(val) => val,
+
+                Err(e) => {
+                    warn!("error marshalling Get Raw Mempool Verbose result");
+                    return Err(RpcServerError::Marshaller(e));
+                }
+            };
+
+        transactions
+            .into_iter()
+            .map(|(hash, tx)| Ok((hash, decode_result(tx, self.policy)?)))
+            .collect()
+    }
+}
+
 fn get_error_value(error: serde_json::Value) -> RpcServerError {
+    // Errors raised by the client on the server's behalf.
+    if let Ok(ClientError::ResponseTooLarge { size, max }) = serde::Deserialize::deserialize(&error)
+    {
+        return RpcServerError::ResponseTooLarge { size, max };
+    }
+
+    if let serde_json::Value::String(error) = error {
+        return RpcServerError::InvalidResponse(error);
+    }
+
     let error_value: RpcError = match serde_json::from_value(error) {
         Ok(val) => val,
 
@@ -1,17 +1,30 @@
 use {
-    super::{chain_notification, connection::Websocket},
+    super::{
+        chain_notification,
+        connection::{KeepAlive, Websocket},
+        future_type, logging,
+        logging::{debug, info, trace, warn},
+        notify::{Direction, DisconnectReason, ReconnectReason, ServerClose},
+    },
     crate::{
-        dcrjson::{commands, result_types::JsonResponse},
-        rpcclient::{connection, constants, infrastructure},
+        dcrjson::{
+            commands, parse_hex_parameters, result_types,
+            result_types::{ClientError, JsonResponse},
+            RpcServerError,
+        },
+        rpcclient::{connection, constants, error::RpcClientError, infrastructure},
     },
     futures_util::{
-        stream::{SplitSink, SplitStream, StreamExt},
-        SinkExt,
+        stream::{SplitStream, StreamExt},
+        Sink, SinkExt,
     },
-    log::{debug, info, trace, warn},
     std::{
-        collections::{HashMap, VecDeque},
-        sync::Arc,
+        collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+        hash::{Hash, Hasher},
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
     },
     tokio::{
         sync::{mpsc, Mutex, RwLock},
@@ -20,6 +33,23 @@ use {
     tokio_tungstenite::{tungstenite, tungstenite::Error as WSError, tungstenite::Message},
 };
 
+/// Registered notifications keyed by registration command, with the ID and parameters they are
+/// registered again with on reconnection.
+pub(super) type NotificationState = Arc<RwLock<HashMap<String, (u64, Vec<serde_json::Value>)>>>;
+
+/// Height of the last block connected notification received, if any, tracked to recover blocks
+/// missed while reconnecting.
+pub(super) type LastBlockHeight = Arc<RwLock<Option<i64>>>;
+
+/// Notification registrations made by helpers such as `block_stream` rather than by the user,
+/// keyed by registration command, with the ID they were registered with and the number of
+/// helpers using them.
+pub(super) type HelperRegistrations = Arc<Mutex<HashMap<String, (u64, usize)>>>;
+
+/// Channels notifications are forwarded to, keyed by notification method.
+pub(super) type NotificationSubscribers =
+    Arc<Mutex<HashMap<String, Vec<mpsc::UnboundedSender<JsonResponse>>>>>;
+
 /// Contains RPC Json ID, channel used to send RPC result and message to be sent to server.
 pub struct Command {
     /// ID to track server to client commands.
@@ -41,28 +71,71 @@ pub struct Command {
 /// which is done automatically.
 ///
 /// `websocket_read_new` is a channel that retrieves a reconnected websocket on websocket disconnect, this is if reconnect is enabled.
+/// A reconnected websocket may also be received while reading if reconnection was signalled by a stalled websocket writer.
 ///
 /// `is_ws_disconnected` indicates if websocket is disconnected.
 ///
 /// `ws_disconnected_acknowledgement` signals on normal websocket closure this normally returns a result when users calls the Disconnect command.
 ///
 /// `signal_ws_reconnect` signals websocket reconnect handler to create a new websocket connection and send new ws stream through receiving
-/// channels.
+/// channels, with the reason the websocket was lost.
+///
+/// `last_received` is updated with the time each message is received.
+///
+/// `server_close` is set to the close frame sent by the server, if any, before a server close is signalled.
+///
+/// `receiver_channel_id_mapper` and `requests_queue_container` are used to fail requests sent on a connection
+/// lost to a message over the maximum response size, which the websocket stream refuses while reading.
 ///
 /// Handles messages received from websocket read which are sent to a message handler which processes received messages.
+/// Messages fragmented into continuation frames, such as large verbose blocks, are reassembled by the websocket stream so
+/// each message read is complete.
 /// If websocket disconnects either through a protocol error or a normal close, `handle_websocket_in` calls for a new websocket connection.
 /// ToDo: Add a condvar to signal all functionalities on websocket close.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn handle_websocket_in(
     send_rcvd_websocket_msg: mpsc::UnboundedSender<Message>,
     mut websocket_read: SplitStream<Websocket>,
     mut websocket_read_new: mpsc::Receiver<SplitStream<Websocket>>,
-    signal_ws_reconnect: mpsc::Sender<()>,
+    signal_ws_reconnect: mpsc::Sender<ReconnectReason>,
+    last_received: Arc<RwLock<time::Instant>>,
+    server_close: Arc<RwLock<Option<ServerClose>>>,
+    on_raw_message: Option<fn(Direction, &[u8])>,
+    receiver_channel_id_mapper: Arc<Mutex<HashMap<u64, mpsc::Sender<JsonResponse>>>>,
+    requests_queue_container: Arc<Mutex<VecDeque<Vec<u8>>>>,
 ) {
     'outer_loop: loop {
-        while let Some(message) = websocket_read.next().await {
+        let reason = loop {
+            let message = tokio::select! {
+                message = websocket_read.next() => match message {
+                    Some(message) => message,
+
+                    None => break ReconnectReason::ServerClosed,
+                },
+
+                // Reconnection was signalled elsewhere, change to new websocket stream.
+                Some(ws) = websocket_read_new.recv() => {
+                    info!("Changing websocket_read channel.");
+                    websocket_read = ws;
+                    *server_close.write().await = None;
+                    continue;
+                }
+            };
+
             match message {
                 // Send received message to message handler function.
                 Ok(message) => {
+                    *last_received.write().await = time::Instant::now();
+
+                    inspect_frame(on_raw_message, Direction::Inbound, &message);
+
+                    if let Message::Close(frame) = &message {
+                        *server_close.write().await = frame.as_ref().map(|frame| ServerClose {
+                            code: frame.code.into(),
+                            reason: frame.reason.to_string(),
+                        });
+                    }
+
                     if let Err(e) = send_rcvd_websocket_msg.send(message) {
                         // On error indicates send_rcvd_websocket_msg channel
                         // is closed which calls for handle_websocket_in exit.
@@ -79,19 +152,34 @@ pub(super) async fn handle_websocket_in(
                             return;
                         }
 
+                        WSError::Capacity(tungstenite::error::CapacityError::MessageTooLong {
+                            size,
+                            max_size,
+                        }) => {
+                            warn!("websocket message of {} bytes exceeds maximum response size of {} bytes, calling for reconnection", size, max_size);
+                            fail_unanswered_requests(
+                                size,
+                                max_size,
+                                &receiver_channel_id_mapper,
+                                &requests_queue_container,
+                            )
+                            .await;
+                            break ReconnectReason::ReadError(e.to_string());
+                        }
+
                         _ => {
                             warn!("websocket disconnected unexpectedly with error: {}, calling for reconnection", e);
-                            break;
+                            break ReconnectReason::ReadError(e.to_string());
                         }
                     };
                 }
             }
-        }
+        };
 
         info!("reconnecting websocket");
 
         // Fall through for reconnection.
-        if let Err(e) = signal_ws_reconnect.send(()).await {
+        if let Err(e) = signal_ws_reconnect.send(reason).await {
             warn!(
                 "websocket reconnection failed, error: {}. Closing websocket connection.",
                 e
@@ -112,6 +200,7 @@ pub(super) async fn handle_websocket_in(
         // Change to new websocket stream and loop for new connection.
         info!("Changing websocket_read channel.");
         websocket_read = ws;
+        *server_close.write().await = None;
     }
 
     info!("handle_websocket_in exited")
@@ -124,21 +213,41 @@ pub(super) async fn handle_websocket_in(
 ///
 /// `ws_disconnected_acknowledgement` sends websocket disconnect acknowledgement to client.
 ///
+/// `is_ws_disconnected` tells a close echoed back after the client disconnected, which ends the handler, from
+/// a close sent by the server, after which messages of the next connection are handled.
+///
 /// `receiver_channel_ID_mapper` maps client command sender to receiver channel using unique ID.
 ///
 /// `notification_handler` sends notification messages to their receiving channel.
 ///
+/// `max_response_size` is the size in bytes above which messages are dropped, for websocket streams which
+/// do not refuse them while reading. The request of a dropped response is answered with an error instead.
+///
+/// `on_rpc_error` is called with the ID, code and message of each response carrying a server error, before the
+/// response is sent to the request awaiting it.
+///
 /// Messages received are unmarshalled and ID gotten, ID is mapped to get client command sender channel.
 /// Sender channel is `disconnected` immediately message is sent to client.
-/// If websocket disconnects either through a protocol error or a normal close, `handle_received_message` closes and has to be recalled to
-/// function.
+/// If the client disconnects, `handle_received_message` closes on the server's close reply and has to be
+/// recalled to function.
 pub(super) async fn handle_received_message(
     mut rcvd_msg_consumer: mpsc::UnboundedReceiver<Message>,
     notification_handler: mpsc::Sender<JsonResponse>,
     ws_disconnected_acknowledgement: mpsc::Sender<()>,
+    is_ws_disconnected: Arc<RwLock<bool>>,
     receiver_channel_id_mapper: Arc<Mutex<HashMap<u64, mpsc::Sender<JsonResponse>>>>,
+    max_response_size: Option<usize>,
+    on_rpc_error: Option<fn(u64, i32, &str)>,
 ) {
     while let Some(message) = rcvd_msg_consumer.recv().await {
+        if let Some(max_response_size) = max_response_size {
+            if (message.is_text() || message.is_binary()) && message.len() > max_response_size {
+                drop_oversized_response(message, max_response_size, &receiver_channel_id_mapper)
+                    .await;
+                continue;
+            }
+        }
+
         let json_content: JsonResponse = match message {
             Message::Binary(m) => match serde_json::from_slice(&m) {
                 Ok(m) => m,
@@ -168,6 +277,12 @@ pub(super) async fn handle_received_message(
             },
 
             Message::Close(_) => {
+                // The reconnect handler takes over when the server closed the connection.
+                if !*is_ws_disconnected.read().await {
+                    info!("Received close message from server.");
+                    continue;
+                }
+
                 info!("Received close message from server, closing now.");
 
                 match ws_disconnected_acknowledgement.send(()).await {
@@ -214,7 +329,7 @@ pub(super) async fn handle_received_message(
                 }
             };
         } else {
-            let id = match json_content.id.as_u64() {
+            let id = match result_types::parse_request_id(&json_content.id) {
                 Some(id) => id,
 
                 None => {
@@ -229,9 +344,15 @@ pub(super) async fn handle_received_message(
             id
         };
 
-        let mut receiver_channel_id_mapper = receiver_channel_id_mapper.lock().await;
+        if let Some(on_rpc_error) = on_rpc_error {
+            inspect_rpc_error(on_rpc_error, id, &json_content.error);
+        }
+
+        // Responses settle their request, so the channel is removed under the lock and the
+        // lock released before sending.
+        let user_channel = receiver_channel_id_mapper.lock().await.remove(&id);
 
-        match receiver_channel_id_mapper.get_mut(&id) {
+        match user_channel {
             Some(val) => {
                 match val.send(json_content).await {
                     Ok(_) => {}
@@ -245,13 +366,201 @@ pub(super) async fn handle_received_message(
                 };
             }
 
-            None => warn!("Could not retrieve senders request channel from map"),
+            // Replies to notifications registered on connection have no waiting request.
+            None => debug!("No pending request for response ID {}", id),
         };
     }
 
     info!("handle_received_message exited");
 }
 
+/// Passes the code and message of a server error carried by the response to request ID to the
+/// on_rpc_error callback. Responses without an error are skipped.
+fn inspect_rpc_error(on_rpc_error: fn(u64, i32, &str), id: u64, error: &serde_json::Value) {
+    if error.is_null() {
+        return;
+    }
+
+    let code = error
+        .get("code")
+        .and_then(serde_json::Value::as_i64)
+        .and_then(|code| i32::try_from(code).ok());
+
+    match code {
+        Some(code) => on_rpc_error(
+            id,
+            code,
+            error
+                .get("message")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default(),
+        ),
+
+        None => warn!("Server sent an error without a code for request ID {}", id),
+    }
+}
+
+/// ID of a request or response, decoded skipping over the rest of the message.
+#[derive(serde::Deserialize)]
+struct MessageId {
+    #[serde(default)]
+    id: serde_json::Value,
+}
+
+/// Answers requests sent on a connection lost to a message refused for exceeding the maximum
+/// response size with an error, as the message cannot be told apart before it is refused and
+/// no response arrives on the lost connection. Requests still queued are sent once reconnected.
+async fn fail_unanswered_requests(
+    size: usize,
+    max_response_size: usize,
+    receiver_channel_id_mapper: &Mutex<HashMap<u64, mpsc::Sender<JsonResponse>>>,
+    requests_queue_container: &Mutex<VecDeque<Vec<u8>>>,
+) {
+    let queued: Vec<u64> = requests_queue_container
+        .lock()
+        .await
+        .iter()
+        .filter_map(|message| serde_json::from_slice::<MessageId>(message).ok())
+        .filter_map(|message| result_types::parse_request_id(&message.id))
+        .collect();
+
+    let unanswered: Vec<(u64, mpsc::Sender<JsonResponse>)> = {
+        let mut mapper = receiver_channel_id_mapper.lock().await;
+
+        let ids: Vec<u64> = mapper
+            .keys()
+            .filter(|id| !queued.contains(id))
+            .copied()
+            .collect();
+
+        ids.into_iter()
+            .filter_map(|id| mapper.remove(&id).map(|user_channel| (id, user_channel)))
+            .collect()
+    };
+
+    for (id, user_channel) in unanswered {
+        let response = JsonResponse {
+            id: serde_json::json!(id),
+            error: serde_json::json!(ClientError::ResponseTooLarge {
+                size,
+                max: max_response_size,
+            }),
+            ..Default::default()
+        };
+
+        if let Err(e) = user_channel.send(response).await {
+            warn!(
+                "Client RPC result receiver channel closed abruptly, error: {}. ID is {}",
+                e, id,
+            );
+        }
+    }
+}
+
+/// Drops a message exceeding the maximum response size, answering the request it responds to
+/// with an error. Messages are refused while read by websocket streams created with
+/// `connection::websocket_config`, so this only applies to streams dialed otherwise.
+async fn drop_oversized_response(
+    message: Message,
+    max_response_size: usize,
+    receiver_channel_id_mapper: &Mutex<HashMap<u64, mpsc::Sender<JsonResponse>>>,
+) {
+    let size = message.len();
+
+    let id = match serde_json::from_slice::<MessageId>(&message.into_data()) {
+        Ok(response) => result_types::parse_request_id(&response.id),
+
+        Err(e) => {
+            warn!("Error unmarshalling oversized message ID, error: {}", e);
+            None
+        }
+    };
+
+    let id = match id {
+        Some(id) => id,
+
+        None => {
+            warn!(
+                "Dropped message of {} bytes exceeding maximum response size of {} bytes",
+                size, max_response_size
+            );
+            return;
+        }
+    };
+
+    warn!(
+        "Dropped response of {} bytes to request ID {} exceeding maximum response size of {} bytes",
+        size, id, max_response_size
+    );
+
+    let user_channel = receiver_channel_id_mapper.lock().await.remove(&id);
+
+    if let Some(user_channel) = user_channel {
+        let response = JsonResponse {
+            id: serde_json::json!(id),
+            error: serde_json::json!(ClientError::ResponseTooLarge {
+                size,
+                max: max_response_size,
+            }),
+            ..Default::default()
+        };
+
+        if let Err(e) = user_channel.send(response).await {
+            warn!(
+                "Client RPC result receiver channel closed abruptly, error: {}. ID is {}",
+                e, id,
+            );
+        }
+    }
+}
+
+/// Drops a request queued while disconnected from the queue once `timeout` passes, answering it
+/// with an error. Nothing is done if the request was sent by then.
+pub(super) async fn expire_queued_request(
+    id: u64,
+    rpc_message: Vec<u8>,
+    timeout: std::time::Duration,
+    requests_queue_container: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    receiver_channel_id_mapper: Arc<Mutex<HashMap<u64, mpsc::Sender<JsonResponse>>>>,
+) {
+    time::sleep(timeout).await;
+
+    {
+        let mut queue = requests_queue_container.lock().await;
+
+        match queue.iter().position(|message| *message == rpc_message) {
+            Some(position) => queue.remove(position),
+
+            None => return,
+        };
+    }
+
+    warn!(
+        "Dropped request ID {} queued while disconnected for {:?}",
+        id, timeout
+    );
+
+    let user_channel = receiver_channel_id_mapper.lock().await.remove(&id);
+
+    if let Some(user_channel) = user_channel {
+        let response = JsonResponse {
+            id: serde_json::json!(id),
+            error: serde_json::json!(format!(
+                "request queued while disconnected was not sent within {:?}",
+                timeout
+            )),
+            ..Default::default()
+        };
+
+        if let Err(e) = user_channel.send(response).await {
+            warn!(
+                "Client RPC result receiver channel closed abruptly, error: {}. ID is {}",
+                e, id,
+            );
+        }
+    }
+}
+
 /// Middleman between websocket writer/out and database. ws_write_middleman is non-blocking.
 ///
 /// `user_command` receives a `clients RPC command and a sender channel` to update client async command on success
@@ -268,16 +577,27 @@ pub(super) async fn handle_received_message(
 ///
 /// On user rpc request to server, command is converted to a `Command` which consists of command ID user channel and a result channel
 /// that updates on success. User channel is save to database against their ID.
+/// Requests already queued on start, such as those queued while disconnected, are sent once the websocket writer is ready.
 /// If websocket disconnects either through a protocol error or a normal close, `ws_write_middleman` closes and has to be recalled to
 /// function.
 pub(super) async fn ws_write_middleman(
     mut user_command: mpsc::Receiver<Command>,
     request_queue_updated: mpsc::Sender<()>,
-    mut message_sent_acknowledgement: mpsc::Receiver<Result<(), Vec<u8>>>,
+    mut message_sent_acknowledgement: mpsc::UnboundedReceiver<Result<(), Vec<u8>>>,
     send_queue_command: mpsc::Sender<Vec<u8>>,
     requests_queue_container: Arc<Mutex<VecDeque<Vec<u8>>>>,
     receiver_channel_id_mapper: Arc<Mutex<HashMap<u64, mpsc::Sender<JsonResponse>>>>,
 ) {
+    // The websocket writer requests the first queued message on start, each queue update
+    // requesting one more.
+    let queued = requests_queue_container.lock().await.len();
+    for _ in 1..queued {
+        if request_queue_updated.send(()).await.is_err() {
+            warn!("request_queue_updated sending channel closed. Closing websocket connection.");
+            return;
+        }
+    }
+
     // Check for updates from client for new commands or
     // websocket writer if it is to send next command in queue.
     loop {
@@ -357,6 +677,70 @@ pub(super) async fn ws_write_middleman(
     info!("ws_write_middleman exited")
 }
 
+/// Stands in for `ws_write_middleman` on listen only clients, which send no requests and so keep
+/// no request queue.
+///
+/// Requests are refused before reaching `user_command`, which is only drained. Queue channels are
+/// held so the websocket writer runs until the client is dropped, and `message_sent_acknowledgement`
+/// is drained of acknowledgements of failed writes, as failed registrations are sent again on
+/// reconnection.
+pub(super) async fn listen_only_middleman(
+    mut user_command: mpsc::Receiver<Command>,
+    mut message_sent_acknowledgement: mpsc::UnboundedReceiver<Result<(), Vec<u8>>>,
+    _request_queue_updated: mpsc::Sender<()>,
+    _send_queue_command: mpsc::Sender<Vec<u8>>,
+) {
+    loop {
+        tokio::select! {
+            command = user_command.recv() => match command {
+                Some(command) => warn!("listen only client dropped request, ID: {}", command.id),
+
+                None => break,
+            },
+
+            ack = message_sent_acknowledgement.recv() => {
+                if ack.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    info!("listen_only_middleman exited")
+}
+
+/// Returns the JSON-RPC request registering a notification, sent as is on connection. The ID
+/// is sent as a string if `string_request_ids` is set.
+pub(super) fn registration_message(
+    method: &str,
+    id: u64,
+    params: &[serde_json::Value],
+    string_request_ids: bool,
+) -> String {
+    serde_json::json!({
+        "jsonrpc": "1.0",
+        "method": method,
+        "params": params,
+        "id": result_types::request_id(id, string_request_ids),
+    })
+    .to_string()
+}
+
+/// Returns the message keeping the connection alive, either a websocket ping or a keep-alive
+/// request.
+fn keep_alive_message(keep_alive: &KeepAlive, string_request_ids: bool) -> Message {
+    match keep_alive {
+        KeepAlive::Ping => Message::Ping(Vec::new()),
+
+        KeepAlive::Request(method) => Message::Text(registration_message(
+            method,
+            constants::KEEP_ALIVE_REQUEST_ID,
+            &[],
+            string_request_ids,
+        )),
+    }
+}
+
 /// Handles sending commands to RPC server through websocket.
 ///
 /// `ws_sender` is an mpsc channel that forwars RPC commands to websocket,
@@ -376,20 +760,39 @@ pub(super) async fn ws_write_middleman(
 /// `disconnect_cmd_rcv` handle websocket closure on request from client. On disconnected command, Close message is sent to server
 /// and websocket is closed when server acknowledges close command.
 ///
+/// `idle_timeout` is the time after which an idle connection is pinged, and a ping left unanswered calls for reconnection.
+///
+/// `last_received` is the time a message was last received from the websocket.
+///
+/// `signal_ws_reconnect` signals websocket reconnect handler when an idle ping is unanswered.
+///
+/// `keep_alive` selects whether pings are sent as websocket pings or application-level requests.
+///
+/// `string_request_ids` sends the ID of keep-alive requests as a string.
+///
 /// When an RPC command is sent, an acknowledgement message is broadcasted to a middle man which either sends next rpc command
 /// in queue on success or resends last errored message on error, middle man also acknowledges user on queue update.
 /// If websocket disconnects either through a protocol error or a normal close, `websocket_out` closes and has to be recalled to
-/// function. Ping commands are sent at intervals.
+/// function. Ping commands are sent at intervals, and when `idle_timeout` is set, once nothing has been sent or received for
+/// `idle_timeout`.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn handle_websocket_out(
     mut ws_sender: mpsc::Sender<Message>,
     mut ws_sender_new: mpsc::Receiver<mpsc::Sender<Message>>,
     mut queue_command: mpsc::Receiver<Vec<u8>>,
-    message_sent_acknowledgement: mpsc::Sender<Result<(), Vec<u8>>>,
+    message_sent_acknowledgement: mpsc::UnboundedSender<Result<(), Vec<u8>>>,
     mut request_queue_updated: mpsc::Receiver<()>,
     mut disconnect_cmd_rcv: mpsc::Receiver<()>,
+    idle_timeout: Option<std::time::Duration>,
+    last_received: Arc<RwLock<time::Instant>>,
+    signal_ws_reconnect: mpsc::Sender<ReconnectReason>,
+    keep_alive: KeepAlive,
+    string_request_ids: bool,
 ) {
-    let send_ack = |msg_ack: mpsc::Sender<Result<(), Vec<u8>>>| async move {
-        match msg_ack.send(Ok(())).await {
+    // Acknowledgements are unbounded so the writer never blocks on the middleman while the
+    // middleman blocks on the writer, allowing several requests to be in flight at once.
+    let send_ack = |msg_ack: mpsc::UnboundedSender<Result<(), Vec<u8>>>| async move {
+        match msg_ack.send(Ok(())) {
             Ok(_) => {}
 
             Err(e) => warn!("error sending websocket open acknowledgement, error: {}", e),
@@ -401,8 +804,62 @@ pub(super) async fn handle_websocket_out(
 
     let mut ping_sender = ws_sender.clone();
 
+    let mut last_sent = time::Instant::now();
+
+    // Time an idle ping awaiting an answer was sent.
+    let mut idle_ping: Option<time::Instant> = None;
+
     loop {
+        let idle_deadline = match idle_timeout {
+            Some(idle_timeout) => {
+                let last_received = *last_received.read().await;
+
+                match idle_ping {
+                    Some(sent) if last_received < sent => Some(sent + idle_timeout),
+
+                    _ => {
+                        idle_ping = None;
+                        Some(last_sent.max(last_received) + idle_timeout)
+                    }
+                }
+            }
+
+            None => None,
+        };
+
         tokio::select! {
+            // Connection has been idle for idle_timeout or an idle ping was not answered within idle_timeout.
+            _ = time::sleep_until(idle_deadline.unwrap_or_else(time::Instant::now)), if idle_deadline.is_some() => {
+                let received = *last_received.read().await;
+
+                match idle_ping.take() {
+                    // Ping was answered while waiting, recompute the deadline.
+                    Some(sent) if received >= sent => continue,
+
+                    Some(_) => {
+                        warn!("Idle ping unanswered, calling for reconnection");
+
+                        // A full channel already holds a pending reconnection signal.
+                        signal_ws_reconnect.try_send(ReconnectReason::PingTimeout).ok();
+                        last_sent = time::Instant::now();
+                        continue;
+                    }
+
+                    None => {}
+                }
+
+                debug!("Connection idle, sending ping to websocket server");
+
+                match ws_sender.send(keep_alive_message(&keep_alive, string_request_ids)).await {
+                    Ok(_) => {
+                        last_sent = time::Instant::now();
+                        idle_ping = Some(last_sent);
+                    }
+
+                    Err(e) => warn!("Error sending idle ping message, error: {}", e),
+                };
+            }
+
             disconnect = disconnect_cmd_rcv.recv() => {
                 match disconnect {
                     Some(_) => {
@@ -440,7 +897,7 @@ pub(super) async fn handle_websocket_out(
             _ = time::sleep(tokio::time::Duration::from_secs(constants::KEEP_ALIVE)) => {
                 debug!("Sending keep alive ping to websocket server");
 
-                match ping_sender.send(Message::Ping(Vec::new())).await {
+                match ping_sender.send(keep_alive_message(&keep_alive, string_request_ids)).await {
                     Ok(_) => {
                         continue;
                     },
@@ -468,6 +925,8 @@ pub(super) async fn handle_websocket_out(
                     Some(new_ws)=>{
                         ping_sender = new_ws.clone();
                         ws_sender = new_ws;
+                        idle_ping = None;
+                        last_sent = time::Instant::now();
 
                         info!("Websocket reconnected");
                         continue;
@@ -490,8 +949,10 @@ pub(super) async fn handle_websocket_out(
                     // acknowledgement.
                     // We only handle error if the tokio sender fails to receive the message
                     // thereby not sending any data to the futures_channel sender.
-                    Some(msg) => if let Err(e) = ws_sender.send(Message::Binary(msg)).await {
-                        match message_sent_acknowledgement.send(Err(e.0.into_data())).await {
+                    Some(msg) => match ws_sender.send(Message::Binary(msg)).await {
+                        Ok(_) => last_sent = time::Instant::now(),
+
+                        Err(e) => match message_sent_acknowledgement.send(Err(e.0.into_data())) {
                             Ok(_) => continue,
 
                             Err(e) => {
@@ -514,60 +975,172 @@ pub(super) async fn handle_websocket_out(
     }
 }
 
+/// Passes the payload of a websocket frame to the on_raw_message callback, if set.
+fn inspect_frame(
+    on_raw_message: Option<fn(Direction, &[u8])>,
+    direction: Direction,
+    message: &Message,
+) {
+    let on_raw_message = match on_raw_message {
+        Some(on_raw_message) => on_raw_message,
+
+        None => return,
+    };
+
+    match message {
+        Message::Text(text) => on_raw_message(direction, text.as_bytes()),
+
+        Message::Binary(data) | Message::Ping(data) | Message::Pong(data) => {
+            on_raw_message(direction, data)
+        }
+
+        Message::Close(frame) => {
+            let data = frame
+                .as_ref()
+                .map(|frame| {
+                    let mut data = u16::from(frame.code).to_be_bytes().to_vec();
+                    data.extend_from_slice(frame.reason.as_bytes());
+                    data
+                })
+                .unwrap_or_default();
+
+            on_raw_message(direction, &data)
+        }
+    }
+}
+
 // TODO: this is a bugger, we cant be handling channel context every
 // time we need to send messages using the writer.
 // We use tokio channel to pipe to our future_channel mpsc.
-pub(super) async fn get_ws_sink(
+//
+// A write not completing within `write_timeout`, as when the send buffer is full against a dead
+// peer, drops the websocket writer and signals a reconnection through `signal_ws_reconnect`.
+pub(super) async fn get_ws_sink<S>(
     mut sink: mpsc::Receiver<Message>,
-    mut ws_sender: SplitSink<Websocket, Message>,
-    ack: mpsc::Sender<Result<(), Vec<u8>>>,
-) {
+    mut ws_sender: S,
+    ack: mpsc::UnboundedSender<Result<(), Vec<u8>>>,
+    signal_ws_reconnect: mpsc::Sender<ReconnectReason>,
+    write_timeout: std::time::Duration,
+    on_raw_message: Option<fn(Direction, &[u8])>,
+) where
+    S: Sink<Message> + Unpin + Send + 'static,
+    S::Error: std::fmt::Display,
+{
     // TODO: add ack
-    tokio::spawn(async move {
+    logging::spawn(async move {
         while let Some(msg) = sink.recv().await {
-            if let Err(e) = ws_sender.send(msg.clone()).await {
-                warn!("websocket sender dropped: {}", e);
-                // We return the dropped message back to the top
-                // of the queue.
-                ack.send(Err(msg.into_data())).await.ok();
-                return;
+            match time::timeout(write_timeout, ws_sender.send(msg.clone())).await {
+                Ok(Ok(_)) => inspect_frame(on_raw_message, Direction::Outbound, &msg),
+
+                Ok(Err(e)) => {
+                    warn!("websocket sender dropped: {}", e);
+                    // We return the dropped message back to the top
+                    // of the queue.
+                    ack.send(Err(msg.into_data())).ok();
+                    return;
+                }
+
+                Err(_) => {
+                    warn!("websocket write timed out, calling for reconnection");
+                    ack.send(Err(msg.into_data())).ok();
+
+                    // A full channel already holds a pending reconnection signal.
+                    signal_ws_reconnect
+                        .try_send(ReconnectReason::WriteStall)
+                        .ok();
+                    return;
+                }
             };
         }
     });
 }
 
+/// Returns true once `attempts` consecutive failed connection attempts reach the connection's
+/// `max_reconnect_attempts`. A limit of zero gives up after the first failed attempt.
+pub(super) fn reconnect_attempts_exhausted(conn: &impl connection::RPCConn, attempts: u32) -> bool {
+    conn.max_reconnect_attempts()
+        .is_some_and(|max_attempts| attempts >= max_attempts)
+}
+
 /// Reconnects websocket on failure if user specifies Auto Connect as true.
 ///
 /// `config` contains websocket credentials for a reconnection.
 ///
-/// `ws_reconnect_signal` receives signal to initiate a websocket reconnection.
+/// `ws_reconnect_signal` receives signal to initiate a websocket reconnection, with the reason the websocket was lost.
+///
+/// `server_close` holds the close frame sent by the server when the server closed the websocket. A close
+/// with the policy violation code leaves the client disconnected rather than reconnecting.
+///
+/// `last_disconnect_reason` records why the websocket was last lost, unless the client disconnected.
 ///
 /// `websocket_read_new` sends new websocket stream to handler.
 ///
 /// `ws_writer_new` sends new websocket writer to handler.
 ///
-/// `notification_state` contains stored registered notification which are registered on reconnection.
+/// `notification_state` contains stored registered notification which are registered on reconnection,
+/// with the parameters they were last registered with. A reconnection fails unless the server accepts
+/// every registration, and is then retried with all of them.
+///
+/// `send_rcvd_websocket_msg` forwards messages received while registering notifications, other than the
+/// registration responses, to the message handler.
+///
+/// `signal_ws_reconnect` is passed to each reconnected websocket writer to signal reconnection when a write stalls.
 ///
 /// `on_reconnect` is a callback function defined by client that is called on websocket connection. If a
 /// callback function is not defined by user, a unit callback is called.
 ///
+/// `on_reconnecting` is a callback function defined by client that is called with the reason for reconnection
+/// before reconnection attempts begin.
+///
+/// `on_disconnect` is a callback function defined by client that is called when reconnection is abandoned.
+///
+/// `on_giveup` is a callback function defined by client that is called, before `on_disconnect`, when
+/// `max_reconnect_attempts` reconnection attempts have failed.
+///
+/// `is_ws_reconnecting` is set while reconnecting, letting the client queue requests made meanwhile in
+/// `requests_queue_container`. Queued requests are sent once reconnected, after notifications are registered.
+///
 /// On websocket disconnect a new websocket channel is to be created and sent across handler for
-/// a successful reconnection. Reconnection is only called if Auto Connect is enabled.
+/// a successful reconnection. Reconnection is only called if Auto Connect is enabled and is abandoned
+/// once the server rejects the client's credentials `max_auth_failures` consecutive times or after
+/// `max_reconnect_attempts` failed attempts.
 #[allow(clippy::too_many_arguments)]
-pub(super) async fn ws_reconnect_handler<F>(
+pub(super) async fn ws_reconnect_handler<R, F>(
     mut conn: impl connection::RPCConn,
     is_ws_disconnected: Arc<RwLock<bool>>,
-    mut ws_reconnect_signal: mpsc::Receiver<()>,
+    is_ws_reconnecting: Arc<RwLock<bool>>,
+    requests_queue_container: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    mut ws_reconnect_signal: mpsc::Receiver<ReconnectReason>,
+    server_close: Arc<RwLock<Option<ServerClose>>>,
+    last_disconnect_reason: Arc<std::sync::Mutex<Option<DisconnectReason>>>,
     websocket_read_new: mpsc::Sender<SplitStream<Websocket>>,
     ws_writer_new: mpsc::Sender<mpsc::Sender<Message>>,
-    notification_state: Arc<RwLock<HashMap<String, u64>>>,
-    message_sent_acknowledgement: mpsc::Sender<Result<(), Vec<u8>>>,
-    on_reconnect: F,
+    notification_state: NotificationState,
+    send_rcvd_websocket_msg: mpsc::UnboundedSender<Message>,
+    message_sent_acknowledgement: mpsc::UnboundedSender<Result<(), Vec<u8>>>,
+    signal_ws_reconnect: mpsc::Sender<ReconnectReason>,
+    on_reconnect: R,
+    on_reconnecting: fn(ReconnectReason),
+    on_disconnect: F,
+    on_giveup: F,
+    on_raw_message: Option<fn(Direction, &[u8])>,
 ) where
+    R: Fn(),
     F: Fn(),
 {
-    while ws_reconnect_signal.recv().await.is_some() {
-        info!("reconnecting websocket connection.");
+    loop {
+        let reason = tokio::select! {
+            signal = ws_reconnect_signal.recv() => match signal {
+                Some(reason) => reason,
+
+                None => break,
+            },
+
+            // Websocket reader exited, reconnection is no longer required.
+            _ = websocket_read_new.closed() => break,
+        };
+
+        info!("reconnecting websocket connection, reason: {}.", reason);
 
         // Check if client disconnected.
         let is_ws_disconnected_clone = is_ws_disconnected.read().await;
@@ -577,29 +1150,108 @@ pub(super) async fn ws_reconnect_handler<F>(
         }
         drop(is_ws_disconnected_clone);
 
-        let mut backoff = std::time::Duration::new(0, 0);
+        let disconnect_reason = match &reason {
+            ReconnectReason::ServerClosed => {
+                DisconnectReason::ServerClosed(server_close.read().await.clone())
+            }
+            ReconnectReason::PingTimeout => DisconnectReason::PingTimeout,
+            ReconnectReason::ReadError(e) => DisconnectReason::ReadError(e.clone()),
+            ReconnectReason::WriteStall => DisconnectReason::WriteStall,
+            ReconnectReason::Forced => DisconnectReason::Forced,
+        };
+        // Reconnecting is pointless once the server refuses the client by policy.
+        let refused = matches!(
+            &disconnect_reason,
+            DisconnectReason::ServerClosed(Some(close))
+                if close.code == constants::CLOSE_POLICY_VIOLATION
+        );
+        if refused {
+            warn!("Server closed websocket on policy violation, not reconnecting.");
+        }
+
+        set_disconnect_reason(&last_disconnect_reason, disconnect_reason);
 
         // Drop all websocket connection if auto reconnect is disabled or websocket is disconnected.
-        if conn.disable_auto_reconnect() {
+        if conn.disable_auto_reconnect() || refused {
             info!("Websocket reconnect disabled. Dropping all websocket handler.");
 
             let mut is_ws_disconnected_clone = is_ws_disconnected.write().await;
             *is_ws_disconnected_clone = true;
+            drop(is_ws_disconnected_clone);
 
+            on_disconnect();
             break;
         }
 
+        on_reconnecting(reason);
+        *is_ws_reconnecting.write().await = true;
+
+        let backoff = conn.backoff();
+        let mut auth_failures = 0;
+        let mut attempts = 0;
+        let mut abandoned = false;
+        let mut gave_up = false;
+
         // Continuosly retry websocket connection.
         loop {
-            backoff += crate::rpcclient::constants::CONNECTION_RETRY_INTERVAL_SECS;
+            // Registered notifications are replayed before the connection is handed over, so a
+            // connection that fails to register all of them is dropped and retried as a whole.
+            let connected = match conn.ws_split_stream().await {
+                Ok((mut ws_rcv, mut ws_writer)) => replay_notifications(
+                    &mut ws_rcv,
+                    &mut ws_writer,
+                    &notification_state,
+                    conn.string_request_ids(),
+                    &send_rcvd_websocket_msg,
+                    on_raw_message,
+                )
+                .await
+                .map(|_| (ws_rcv, ws_writer)),
+
+                Err(e) => Err(e),
+            };
 
-            let (ws_rcv, ws_writer) = match conn.ws_split_stream().await {
+            let (ws_rcv, ws_writer) = match connected {
                 Ok(ws) => ws,
 
                 Err(e) => {
+                    attempts += 1;
+
+                    if let RpcClientError::AuthenticationFailed = e {
+                        auth_failures += 1;
+
+                        if auth_failures == conn.max_auth_failures() {
+                            warn!(
+                                "server rejected credentials on {} reconnection attempts. Closing websocket connection.",
+                                auth_failures
+                            );
+
+                            set_disconnect_reason(
+                                &last_disconnect_reason,
+                                DisconnectReason::AuthFailure,
+                            );
+
+                            abandoned = true;
+                            break;
+                        }
+                    } else {
+                        auth_failures = 0;
+                    }
+
+                    if reconnect_attempts_exhausted(&conn, attempts) {
+                        warn!(
+                            "unable to reconnect websocket after {} attempts, error: {}. Closing websocket connection.",
+                            attempts, e
+                        );
+
+                        abandoned = true;
+                        gave_up = true;
+                        break;
+                    }
+
                     warn!("unable to reconnect websocket, error: {}. Reconnecting.", e);
 
-                    std::thread::sleep(backoff);
+                    time::sleep(backoff.next_delay(attempts)).await;
                     continue;
                 }
             };
@@ -608,30 +1260,15 @@ pub(super) async fn ws_reconnect_handler<F>(
 
             let message_sent_acknowledgement = message_sent_acknowledgement.clone();
 
-            infrastructure::get_ws_sink(rcvr, ws_writer, message_sent_acknowledgement).await;
-
-            // Register registered notifications on reconnection.
-            let notification_state_clone = notification_state.read().await;
-            for iter in notification_state_clone.clone().into_iter() {
-                debug!("Registering {} notification on reconnection.", iter.0);
-
-                let data = format!(
-                    "{{ \"jsonrpc\": \"1.0\", \"method\":\"{}\", \"params\":[], \"id\":{} }}",
-                    iter.0, iter.1
-                );
-
-                trace!(
-                    "Registering notification on reconnection, notification: {}",
-                    iter.0
-                );
-
-                if let Err(e) = writer.send(Message::Text(data)).await {
-                    warn!(
-                        "Error registering notification on reconnection, error: {}",
-                        e
-                    );
-                }
-            }
+            infrastructure::get_ws_sink(
+                rcvr,
+                ws_writer,
+                message_sent_acknowledgement,
+                signal_ws_reconnect.clone(),
+                constants::WRITE_TIMEOUT_SECS,
+                on_raw_message,
+            )
+            .await;
 
             trace!("Reconnection websocket message reader");
 
@@ -657,133 +1294,570 @@ pub(super) async fn ws_reconnect_handler<F>(
             break;
         }
 
+        // Reconnection was abandoned, leave client disconnected.
+        if abandoned {
+            *is_ws_disconnected.write().await = true;
+            *is_ws_reconnecting.write().await = false;
+
+            if gave_up {
+                on_giveup();
+            }
+
+            on_disconnect();
+            break;
+        }
+
+        drain_queued_requests(
+            &is_ws_reconnecting,
+            &requests_queue_container,
+            &message_sent_acknowledgement,
+        )
+        .await;
+
         on_reconnect();
     }
 
     info!("_ws_reconnect_handler exited")
 }
 
+/// Sends the requests queued while reconnecting over the new connection by acknowledging the
+/// websocket write middleman once per queued request, each acknowledgement sending the next
+/// request in queue.
+async fn drain_queued_requests(
+    is_ws_reconnecting: &RwLock<bool>,
+    requests_queue_container: &Mutex<VecDeque<Vec<u8>>>,
+    message_sent_acknowledgement: &mpsc::UnboundedSender<Result<(), Vec<u8>>>,
+) {
+    let queued = {
+        let queue = requests_queue_container.lock().await;
+        *is_ws_reconnecting.write().await = false;
+        queue.len()
+    };
+
+    if queued > 0 {
+        debug!("Sending {} requests queued while reconnecting", queued);
+    }
+
+    for _ in 0..queued {
+        if message_sent_acknowledgement.send(Ok(())).is_err() {
+            warn!("Error sending queued requests, websocket write middleman closed");
+            return;
+        }
+    }
+}
+
+/// Registers every registered notification again on a new connection, waiting for the server to
+/// accept each registration. Other messages received meanwhile, such as notifications of
+/// methods already registered, are forwarded to the message handler through
+/// `send_rcvd_websocket_msg`.
+///
+/// A NotificationRegistration error is returned if the server rejects a registration, and an
+/// RpcDisconnected or RequestTimeout error if the connection is lost or the server does not
+/// answer within `NOTIFICATION_REPLAY_TIMEOUT`.
+async fn replay_notifications<S>(
+    ws_rcv: &mut SplitStream<Websocket>,
+    ws_writer: &mut S,
+    notification_state: &NotificationState,
+    string_request_ids: bool,
+    send_rcvd_websocket_msg: &mpsc::UnboundedSender<Message>,
+    on_raw_message: Option<fn(Direction, &[u8])>,
+) -> Result<(), RpcClientError>
+where
+    S: Sink<Message> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    let registrations = notification_state.read().await.clone();
+
+    // Registration methods keyed by request ID.
+    let mut pending = HashMap::with_capacity(registrations.len());
+
+    for (method, (id, params)) in registrations.into_iter() {
+        debug!("Registering {} notification on reconnection.", method);
+
+        let message = Message::Text(registration_message(
+            &method,
+            id,
+            &params,
+            string_request_ids,
+        ));
+
+        if let Err(e) = ws_writer.send(message.clone()).await {
+            warn!(
+                "Error registering notification on reconnection, error: {}",
+                e
+            );
+            return Err(RpcClientError::RpcDisconnected);
+        }
+
+        inspect_frame(on_raw_message, Direction::Outbound, &message);
+
+        pending.insert(id, method);
+    }
+
+    let deadline = time::Instant::now() + constants::NOTIFICATION_REPLAY_TIMEOUT;
+
+    while !pending.is_empty() {
+        let message = match time::timeout_at(deadline, ws_rcv.next()).await {
+            Ok(Some(Ok(message))) => message,
+
+            Ok(Some(Err(e))) => {
+                warn!(
+                    "Error reading notification registrations on reconnection, error: {}",
+                    e
+                );
+                return Err(RpcClientError::RpcDisconnected);
+            }
+
+            Ok(None) => return Err(RpcClientError::RpcDisconnected),
+
+            Err(_) => {
+                warn!("Server did not answer notification registrations on reconnection");
+                return Err(RpcClientError::RequestTimeout);
+            }
+        };
+
+        inspect_frame(on_raw_message, Direction::Inbound, &message);
+
+        let response = match &message {
+            Message::Text(m) => serde_json::from_str::<JsonResponse>(m).ok(),
+            Message::Binary(m) => serde_json::from_slice::<JsonResponse>(m).ok(),
+            Message::Close(_) => return Err(RpcClientError::RpcDisconnected),
+            _ => None,
+        };
+
+        let method = response
+            .as_ref()
+            .and_then(|response| result_types::parse_request_id(&response.id))
+            .and_then(|id| pending.remove(&id));
+
+        let (response, method) = match (response, method) {
+            (Some(response), Some(method)) => (response, method),
+
+            _ => {
+                if send_rcvd_websocket_msg.send(message).is_err() {
+                    return Err(RpcClientError::RpcDisconnected);
+                }
+
+                continue;
+            }
+        };
+
+        if !response.error.is_null() {
+            let error = match serde_json::from_value(response.error) {
+                Ok(error) => RpcServerError::ServerError(error),
+
+                Err(e) => RpcServerError::Marshaller(e),
+            };
+
+            warn!(
+                "Server rejected {} registration on reconnection, error: {}",
+                method, error
+            );
+
+            return Err(RpcClientError::NotificationRegistration(vec![(
+                method,
+                RpcClientError::RpcServer(error),
+            )]));
+        }
+
+        trace!("Registered {} notification on reconnection", method);
+    }
+
+    Ok(())
+}
+
+/// Records why the websocket connection was lost.
+pub(super) fn set_disconnect_reason(
+    last_disconnect_reason: &std::sync::Mutex<Option<DisconnectReason>>,
+    reason: DisconnectReason,
+) {
+    match last_disconnect_reason.lock() {
+        Ok(mut last_disconnect_reason) => *last_disconnect_reason = Some(reason),
+
+        Err(e) => warn!("Error recording disconnect reason, error: {}", e),
+    }
+}
+
 /// Handles all notifications received by websocket.
 ///
 /// `channel_recv` is the receiving channel that receives all channel from `handle_received_message`.
 ///
 /// `notif` contains all registered notification callbacks.
 ///
+/// `workers` is the number of notification workers callbacks are dispatched to. If zero, callbacks are
+/// called on the notification handler.
+///
+/// `subscribers` receive a copy of each notification of the method they subscribed to, before callbacks
+/// are called. Subscribers whose receiver was dropped are removed.
+///
+/// `last_block_height` is updated with the height of each block connected notification when set.
+///
+/// `helper_registrations` are the registrations made by helpers, whose notifications are not expected to
+/// have a callback set.
+///
 /// RPC notifications are sent to handler and are processed accordingly, registered callbacks are called
-/// if available.
+/// if available. When dispatched to workers, each notification type is always handled by the same worker
+/// so notifications of a type are handled in the order they were received, while a slow callback does not
+/// hold up handling of messages received from the websocket. Worker queues are unbounded so queueing never
+/// waits on a worker, and workers exit once their queue is dropped with the notification handler.
 /// Note: This function requires websocket connection.
 pub(super) async fn handle_notification(
     mut channel_recv: mpsc::Receiver<JsonResponse>,
     notif: Arc<super::notify::NotificationHandlers>,
+    workers: usize,
+    subscribers: NotificationSubscribers,
+    last_block_height: Option<LastBlockHeight>,
+    helper_registrations: HelperRegistrations,
 ) {
+    let workers: Vec<std::sync::mpsc::Sender<(JsonResponse, bool)>> = (0..workers)
+        .filter_map(|i| {
+            let (sender, receiver) = std::sync::mpsc::channel::<(JsonResponse, bool)>();
+            let notif = notif.clone();
+            let connection_name = logging::current();
+
+            // Callbacks are synchronous and may be CPU bound, and workers live as long as the client,
+            // so each worker runs on a dedicated thread rather than the runtime's blocking pool.
+            let worker = std::thread::Builder::new()
+                .name(format!("notification-worker-{}", i))
+                .spawn(move || {
+                    logging::sync_scope(connection_name, || {
+                        while let Ok((msg, helper_owned)) = receiver.recv() {
+                            dispatch_notification(msg, &notif, helper_owned);
+                        }
+                    })
+                });
+
+            match worker {
+                Ok(_) => Some(sender),
+
+                Err(e) => {
+                    warn!("Error spawning notification worker, error: {}", e);
+                    None
+                }
+            }
+        })
+        .collect();
+
     while let Some(msg) = channel_recv.recv().await {
         info!("Received notification");
 
-        if msg.params.is_empty() {
-            warn!("server sent an invalid notification result: {:?}", msg);
+        if let Some(last_block_height) = &last_block_height {
+            if msg.method.as_str() == Some(commands::NOTIFICATION_METHOD_BLOCK_CONNECTED) {
+                if let Some(height) = msg.params.first().and_then(block_height) {
+                    let mut last_block_height = last_block_height.write().await;
+                    *last_block_height = Some(last_block_height.map_or(height, |h| h.max(height)));
+                }
+            }
+        }
+
+        if let Some(method) = msg.method.as_str() {
+            let mut subscribers = subscribers.lock().await;
+
+            if let Some(channels) = subscribers.get_mut(method) {
+                channels.retain(|channel| channel.send(msg.clone()).is_ok());
+
+                if channels.is_empty() {
+                    subscribers.remove(method);
+                }
+            }
+        }
+
+        let helper_owned = match msg
+            .method
+            .as_str()
+            .and_then(chain_notification::registration_method)
+        {
+            Some(registration) => helper_registrations.lock().await.contains_key(registration),
+
+            None => false,
+        };
+
+        if workers.is_empty() {
+            dispatch_notification(msg, &notif, helper_owned);
             continue;
         }
 
-        match msg.method.as_str() {
-            Some(method) => match method {
-                commands::NOTIFICATION_METHOD_BLOCK_CONNECTED => match notif.on_block_connected {
-                    Some(e) => chain_notification::on_block_connected(&msg.params, e),
+        let mut hasher = DefaultHasher::new();
+        msg.method.as_str().hash(&mut hasher);
+        let worker = &workers[hasher.finish() as usize % workers.len()];
 
-                    None => {
-                        warn!("On block connected notification callback not registered.");
-                        continue;
-                    }
-                },
+        if worker.send((msg, helper_owned)).is_err() {
+            warn!("Notification worker closed abruptly.");
+        }
+    }
+
+    trace!("Closing notification handler.");
+}
 
-                commands::NOTIFICATION_METHOD_BLOCK_DISCONNECTED => {
-                    match notif.on_block_disconnected {
-                        Some(e) => chain_notification::on_block_disconnected(&msg.params, e),
+/// Returns the height of a hex-encoded block header.
+fn block_height(header: &serde_json::Value) -> Option<i64> {
+    let header = parse_hex_parameters(header)?;
 
-                        None => {
-                            warn!("On block disconnected notification callback not registered.");
-                            continue;
-                        }
-                    }
+    match result_types::GetBlockHeaderVerboseResult::from_bytes(&header) {
+        Ok(header) => Some(i64::from(header.height)),
+
+        Err(e) => {
+            warn!("Error parsing block connected header, error: {}", e);
+            None
+        }
+    }
+}
+
+/// Replays `on_block_connected` for the blocks connected since `last_block_height` up to the
+/// server's best block, requesting them through `user_command` with IDs taken from `id`, sent
+/// as strings if `string_request_ids` is set.
+///
+/// Blocks are passed to the callback with their header and no transactions, matching block
+/// connected notifications sent without a transaction filter. Recovery stops at the first
+/// failed request, leaving `last_block_height` at the last replayed block.
+pub(super) async fn recover_missed_blocks(
+    user_command: mpsc::Sender<Command>,
+    id: Arc<AtomicU64>,
+    string_request_ids: bool,
+    last_block_height: LastBlockHeight,
+    on_block_connected: fn(block_header: Vec<u8>, transactions: Vec<Vec<u8>>),
+) -> Result<(), RpcClientError> {
+    let last_height = match *last_block_height.read().await {
+        Some(height) => height,
+
+        // No block was notified before reconnection, so there is nothing to recover from.
+        None => return Ok(()),
+    };
+
+    let request = |method: &'static str, params: Vec<serde_json::Value>| {
+        let user_command = user_command.clone();
+        let id = id.fetch_add(1, Ordering::SeqCst);
+
+        async move {
+            let rpc_message = serde_json::to_vec(&result_types::JsonRequest {
+                jsonrpc: "1.0",
+                id: result_types::request_id(id, string_request_ids),
+                method,
+                params: &params,
+            })
+            .map_err(RpcClientError::Marshaller)?;
+
+            let (user_channel, receiver) = mpsc::channel(1);
+
+            user_command
+                .send(Command {
+                    id,
+                    user_channel,
+                    rpc_message,
+                })
+                .await
+                .map_err(|_| RpcClientError::RpcDisconnected)?;
+
+            Ok::<_, RpcClientError>(receiver)
+        }
+    };
+
+    let best_height = future_type::GetBlockCountFuture::new(
+        request(commands::METHOD_GET_BLOCK_COUNT, vec![]).await?,
+    )
+    .await?;
+
+    if best_height > last_height {
+        info!(
+            "Recovering {} blocks connected while reconnecting",
+            best_height - last_height
+        );
+    }
+
+    for height in last_height + 1..=best_height {
+        let block_hash = future_type::GetBlockHashFuture::new(
+            request(
+                commands::METHOD_GET_BLOCK_HASH,
+                vec![serde_json::json!(height)],
+            )
+            .await?,
+        )
+        .await?;
+        let block_hash = block_hash.string().map_err(RpcClientError::ChainHash)?;
+
+        let block = future_type::GetBlockHexFuture::new(
+            request(
+                commands::METHOD_GET_BLOCK,
+                vec![serde_json::json!(block_hash), serde_json::json!(false)],
+            )
+            .await?,
+        )
+        .await?;
+
+        let block = match hex::decode(&block) {
+            Ok(block) if block.len() >= result_types::BLOCK_HEADER_SIZE => block,
+
+            _ => {
+                return Err(RpcServerError::InvalidResponse(format!(
+                    "invalid serialized block {}",
+                    block_hash
+                ))
+                .into())
+            }
+        };
+
+        on_block_connected(
+            block[..result_types::BLOCK_HEADER_SIZE].to_vec(),
+            Vec::new(),
+        );
+
+        let mut last_block_height = last_block_height.write().await;
+        *last_block_height = Some(last_block_height.map_or(height, |h| h.max(height)));
+    }
+
+    Ok(())
+}
+
+/// Logs a notification received with no callback set. Notifications of registrations made by
+/// helpers are consumed by the helper, so they are only logged at debug level.
+fn missing_callback(helper_owned: bool, message: &str) {
+    if helper_owned {
+        debug!("{}", message);
+    } else {
+        warn!("{}", message);
+    }
+}
+
+/// Calls the registered callback of a notification. `helper_owned` tells the notification is of a
+/// registration made by a helper rather than by the user.
+fn dispatch_notification(
+    msg: JsonResponse,
+    notif: &super::notify::NotificationHandlers,
+    helper_owned: bool,
+) {
+    if msg.params.is_empty() {
+        warn!("server sent an invalid notification result: {:?}", msg);
+        return;
+    }
+
+    match msg.method.as_str() {
+        Some(method) => match method {
+            commands::NOTIFICATION_METHOD_BLOCK_CONNECTED => match notif.on_block_connected {
+                Some(e) => chain_notification::on_block_connected(&msg.params, e),
+
+                None => missing_callback(
+                    helper_owned,
+                    "On block connected notification callback not registered.",
+                ),
+            },
+
+            commands::NOTIFICATION_METHOD_BLOCK_DISCONNECTED => {
+                if let Some(e) = notif.on_block_disconnected {
+                    chain_notification::on_block_disconnected(&msg.params, e);
                 }
 
-                commands::NOTIFICATION_METHOD_WORK => match notif.on_work {
-                    Some(e) => chain_notification::on_work(&msg.params, e),
+                if let Some(e) = notif.on_block_disconnected_header {
+                    chain_notification::on_block_disconnected_header(&msg.params, e);
+                }
 
-                    None => {
-                        warn!("On work notification callback not registered.");
-                        continue;
-                    }
-                },
+                if notif.on_block_disconnected.is_none()
+                    && notif.on_block_disconnected_header.is_none()
+                {
+                    missing_callback(
+                        helper_owned,
+                        "On block disconnected notification callback not registered.",
+                    );
+                }
+            }
 
-                commands::NOTIFICATION_METHOD_NEW_TICKETS => match notif.on_new_tickets {
-                    Some(e) => chain_notification::on_new_tickets(&msg.params, e),
+            commands::NOTIFICATION_METHOD_WORK => match notif.on_work {
+                Some(e) => chain_notification::on_work(&msg.params, e),
 
-                    None => {
-                        warn!("On new tickets notification callback not registered.");
-                        continue;
-                    }
-                },
+                None => {
+                    warn!("On work notification callback not registered.");
+                }
+            },
 
-                commands::NOTIFICATION_METHOD_TX_ACCEPTED => match notif.on_tx_accepted {
-                    Some(e) => chain_notification::on_tx_accepted(&msg.params, e),
+            commands::NOTIFICATION_METHOD_NEW_TICKETS => match notif.on_new_tickets {
+                Some(e) => chain_notification::on_new_tickets(&msg.params, e),
 
-                    None => {
-                        warn!("On transaction accepted notification callback not registered.");
-                        continue;
-                    }
-                },
+                None => {
+                    warn!("On new tickets notification callback not registered.");
+                }
+            },
 
-                commands::NOTIFICATION_METHOD_TX_ACCEPTED_VERBOSE => {
-                    match notif.on_tx_accepted_verbose {
-                        Some(e) => chain_notification::on_tx_accepted_verbose(&msg.params, e),
+            commands::NOTIFICATION_METHOD_STAKE_DIFFICULTY => match notif.on_stake_difficulty {
+                Some(e) => chain_notification::on_stake_difficulty(&msg.params, e),
 
-                        None => {
-                            warn!("On transaction accepted verbose notification callback not registered.");
-                            continue;
-                        }
-                    }
+                // Stake difficulty updates may be consumed as a stream rather than a callback.
+                None => {
+                    debug!("On stake difficulty notification callback not registered.");
                 }
+            },
 
-                commands::NOTIFICATION_METHOD_REORGANIZATION => match notif.on_reorganization {
-                    Some(e) => chain_notification::on_reorganization(&msg.params, e),
+            commands::NOTIFICATION_METHOD_WINNING_TICKETS => match notif.on_winning_tickets {
+                Some(e) => chain_notification::on_winning_tickets(&msg.params, e),
 
-                    None => {
-                        warn!("On block reorganization callback not registered.");
-                        continue;
-                    }
-                },
+                // Winning tickets may be consumed from a channel rather than a callback.
+                None => {
+                    debug!("On winning tickets notification callback not registered.");
+                }
+            },
 
-                commands::NOTIFICATION_METHOD_SPENT_AND_MISSING_TICKETS => {
-                    match notif.on_spent_and_missed_tickets {
-                        Some(e) => chain_notification::on_spent_and_missed_tickets(&msg.params, e),
-                        None => {
-                            warn!("on spent and missing tickets callback not registered.");
-                            continue;
-                        }
-                    }
+            commands::NOTIFICATION_METHOD_TX_ACCEPTED => match notif.on_tx_accepted {
+                Some(e) => chain_notification::on_tx_accepted(&msg.params, e),
+
+                None => missing_callback(
+                    helper_owned,
+                    "On transaction accepted notification callback not registered.",
+                ),
+            },
+
+            commands::NOTIFICATION_METHOD_TX_ACCEPTED_VERBOSE => {
+                match notif.on_tx_accepted_verbose {
+                    Some(e) => chain_notification::on_tx_accepted_verbose(&msg.params, e),
+
+                    None => missing_callback(
+                        helper_owned,
+                        "On transaction accepted verbose notification callback not registered.",
+                    ),
                 }
+            }
+
+            commands::NOTIFICATION_METHOD_REORGANIZATION => match notif.on_reorganization {
+                Some(e) => chain_notification::on_reorganization(&msg.params, e),
+
+                None => missing_callback(
+                    helper_owned,
+                    "On block reorganization callback not registered.",
+                ),
+            },
 
-                _ => match notif.on_unknown_notification {
-                    Some(e) => {
-                        e(method.to_string(), msg);
+            commands::NOTIFICATION_METHOD_RELEVANT_TX_ACCEPTED => {
+                match notif.on_relevant_tx_accepted {
+                    Some(e) => chain_notification::on_relevant_tx_accepted(&msg.params, e),
+
+                    None => {
+                        warn!("On relevant transaction accepted callback not registered.");
                     }
+                }
+            }
 
+            commands::NOTIFICATION_METHOD_SPENT_AND_MISSING_TICKETS => {
+                match notif.on_spent_and_missed_tickets {
+                    Some(e) => chain_notification::on_spent_and_missed_tickets(&msg.params, e),
                     None => {
-                        warn!(
-                            "On unknown notification callback not registered. Method: {}",
-                            method
-                        );
-                        continue;
+                        warn!("on spent and missing tickets callback not registered.");
                     }
-                },
+                }
+            }
+
+            _ => match notif.on_unknown_notification {
+                Some(e) => {
+                    e(method.to_string(), msg);
+                }
+
+                None => {
+                    warn!(
+                        "On unknown notification callback not registered. Method: {}",
+                        method
+                    );
+                }
             },
+        },
 
-            None => {
-                warn!("Received a nil or unsupported method type on notify blocks.");
-                continue;
-            }
+        None => {
+            warn!("Received a nil or unsupported method type on notify blocks.");
         }
     }
-
-    trace!("Closing notification handler.");
 }
@@ -0,0 +1,88 @@
+//! Connection Logging.
+//! Prefixes log lines emitted by the tasks of a client with its connection name, so logs of
+//! clients connected to different nodes can be told apart.
+
+use std::{future::Future, sync::Arc};
+
+tokio::task_local! {
+    /// Name of the connection the current task belongs to.
+    static CONNECTION_NAME: Option<Arc<str>>;
+}
+
+/// Returns the name of the connection the current task belongs to, if any.
+pub(super) fn current() -> Option<Arc<str>> {
+    CONNECTION_NAME.try_with(Clone::clone).ok().flatten()
+}
+
+/// Returns the log line prefix of the connection the current task belongs to.
+pub(super) fn prefix() -> String {
+    match current() {
+        Some(name) => format!("[{}] ", name),
+
+        None => String::new(),
+    }
+}
+
+/// Runs future as a task of the named connection.
+pub(super) async fn scope<F: Future>(name: Option<Arc<str>>, future: F) -> F::Output {
+    CONNECTION_NAME.scope(name, future).await
+}
+
+/// Runs f on the current thread as part of the named connection.
+pub(super) fn sync_scope<R>(name: Option<Arc<str>>, f: impl FnOnce() -> R) -> R {
+    CONNECTION_NAME.sync_scope(name, f)
+}
+
+/// Spawns future as a task of the named connection.
+pub(super) fn spawn_named<F>(
+    name: Option<Arc<str>>,
+    future: F,
+) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(scope(name, future))
+}
+
+/// Spawns future as a task of the connection the current task belongs to.
+pub(super) fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    spawn_named(current(), future)
+}
+
+macro_rules! log_prefixed {
+    ($level:ident, $($arg:tt)+) => {
+        ::log::$level!(
+            "{}{}",
+            $crate::rpcclient::logging::prefix(),
+            format_args!($($arg)+)
+        )
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::rpcclient::logging::log_prefixed!(trace, $($arg)+) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::rpcclient::logging::log_prefixed!(debug, $($arg)+) };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::rpcclient::logging::log_prefixed!(info, $($arg)+) };
+}
+
+// Named apart from `warn` as a single `warn` path is ambiguous with the lint attribute.
+macro_rules! log_warn {
+    ($($arg:tt)+) => { $crate::rpcclient::logging::log_prefixed!(warn, $($arg)+) };
+}
+
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::rpcclient::logging::log_prefixed!(error, $($arg)+) };
+}
+
+pub(super) use {debug, error, info, log_prefixed, log_warn as warn, trace};
@@ -1,5 +1,7 @@
 #![cfg(feature = "rpcclient")]
+pub mod backoff;
 pub mod chain_command;
+pub mod chain_helper;
 pub mod chain_notification;
 pub mod client;
 pub mod connection;
@@ -7,12 +9,18 @@ pub(crate) mod constants;
 pub mod error;
 mod future_type;
 mod infrastructure;
+mod logging;
 pub mod notify;
+mod rate_limit;
 pub mod test;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 
 macro_rules! check_config {
     ($self:ident) => {
-        if $self.conn.is_http_mode() || $self.is_disconnected().await {
+        if $self.conn.is_http_mode()
+            || ($self.is_disconnected().await && !$self.conn.queue_while_disconnected())
+        {
             return Err(RpcClientError::RpcDisconnected);
         }
     };
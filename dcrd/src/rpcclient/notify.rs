@@ -1,7 +1,110 @@
 //! Notification Handlers
 //! On notification callback functions for websocket.
 
-use {crate::chaincfg::chainhash::Hash, std::collections::HashMap};
+use {
+    crate::{chaincfg::chainhash::Hash, dcrjson::result_types::GetBlockHeaderVerboseResult},
+    serde::{Deserialize, Serialize},
+    std::collections::{BTreeMap, HashMap},
+};
+
+/// Direction of a websocket frame passed to on_raw_message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Frame received from the server.
+    Inbound,
+    /// Frame sent to the server.
+    Outbound,
+}
+
+/// Why the websocket connection is being reconnected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconnectReason {
+    /// The server closed the websocket stream.
+    ServerClosed,
+    /// A ping sent once the connection was idle went unanswered within `idle_timeout`.
+    PingTimeout,
+    /// Reading from the websocket failed, such as when the connection was reset.
+    ReadError(String),
+    /// A websocket write did not complete in time, as when the peer stopped reading.
+    WriteStall,
+    /// The client requested a reconnection with `force_reconnect`.
+    Forced,
+}
+
+impl std::fmt::Display for ReconnectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReconnectReason::ServerClosed => write!(f, "server closed the connection"),
+            ReconnectReason::PingTimeout => write!(f, "idle ping unanswered"),
+            ReconnectReason::ReadError(e) => write!(f, "read error: {}", e),
+            ReconnectReason::WriteStall => write!(f, "websocket write stalled"),
+            ReconnectReason::Forced => write!(f, "reconnection forced by client"),
+        }
+    }
+}
+
+/// Close frame sent by the server when it closed the websocket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerClose {
+    /// Close code, such as 1001 when the server is going away.
+    pub code: u16,
+    /// Reason given by the server, possibly empty.
+    pub reason: String,
+}
+
+/// Why the websocket connection was last lost, as returned by `last_disconnect_reason`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The client disconnected with `disconnect` or `shutdown`.
+    Shutdown,
+    /// The server closed the websocket stream, with its close frame if it sent one. The client
+    /// stops reconnecting when the server closes with the policy violation code 1008, as when
+    /// the client's access was revoked.
+    ServerClosed(Option<ServerClose>),
+    /// Reading from the websocket failed, such as when the connection was reset.
+    ReadError(String),
+    /// A ping sent once the connection was idle went unanswered within `idle_timeout`.
+    PingTimeout,
+    /// A websocket write did not complete in time, as when the peer stopped reading.
+    WriteStall,
+    /// The client requested a reconnection with `force_reconnect`.
+    Forced,
+    /// The server rejected the client's credentials `max_auth_failures` consecutive times while
+    /// reconnecting.
+    AuthFailure,
+}
+
+impl std::fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisconnectReason::Shutdown => write!(f, "client disconnected"),
+            DisconnectReason::ServerClosed(Some(close)) if close.reason.is_empty() => {
+                write!(f, "server closed the connection with code {}", close.code)
+            }
+            DisconnectReason::ServerClosed(Some(close)) => write!(
+                f,
+                "server closed the connection with code {}: {}",
+                close.code, close.reason
+            ),
+            DisconnectReason::ServerClosed(None) => write!(f, "server closed the connection"),
+            DisconnectReason::ReadError(e) => write!(f, "read error: {}", e),
+            DisconnectReason::PingTimeout => write!(f, "idle ping unanswered"),
+            DisconnectReason::WriteStall => write!(f, "websocket write stalled"),
+            DisconnectReason::Forced => write!(f, "reconnection forced by client"),
+            DisconnectReason::AuthFailure => write!(f, "server rejected credentials"),
+        }
+    }
+}
+
+/// Notification registrations of a client, as returned by `export_notification_state`. A client
+/// created with `with_notification_state` registers them on connection, taking over the
+/// subscriptions of the client they were exported from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotificationStateSnapshot {
+    /// Registration commands, such as `notifyblocks`, mapped to the parameters they were last
+    /// registered with.
+    pub registrations: BTreeMap<String, Vec<serde_json::Value>>,
+}
 
 /// NotificationHandlers defines callback function pointers to invoke with notifications.
 /// Since all of the functions are None by default, all notifications are effectively
@@ -17,6 +120,36 @@ pub struct NotificationHandlers {
     /// reconnects to the RPC server.
     pub on_client_connected: Option<fn()>,
 
+    /// on_reconnected callback function is invoked, after on_client_connected, when the client
+    /// reconnects to the RPC server. Unlike on_client_connected, it is not invoked on the
+    /// initial connection.
+    pub on_reconnected: Option<fn()>,
+
+    /// on_client_disconnected callback function is invoked when the websocket connection is
+    /// lost and the client stops reconnecting, either because auto reconnect is disabled or
+    /// the server repeatedly rejected the client's credentials.
+    pub on_client_disconnected: Option<fn()>,
+
+    /// on_reconnecting callback function is invoked with the reason the connection was lost
+    /// when the client begins reconnecting, before the first reconnection attempt. It is not
+    /// invoked when auto reconnect is disabled.
+    pub on_reconnecting: Option<fn(reason: ReconnectReason)>,
+
+    /// on_raw_message callback function is invoked with the payload of every websocket frame
+    /// exchanged with the server, before inbound frames are parsed and after outbound frames
+    /// are written, to debug the protocol. Credentials are sent in the handshake headers,
+    /// which are never passed to the callback.
+    pub on_raw_message: Option<fn(direction: Direction, bytes: &[u8])>,
+
+    /// on_rpc_error callback function is invoked with the request ID, error code and message of
+    /// every response to a websocket request carrying a server error, before the error is
+    /// mapped and delivered to the request's future, to log server errors in one place.
+    pub on_rpc_error: Option<fn(id: u64, code: i32, message: &str)>,
+
+    /// on_reconnect_giveup callback function is invoked when the client stops reconnecting
+    /// after `max_reconnect_attempts` failed attempts, before on_client_disconnected.
+    pub on_reconnect_giveup: Option<fn()>,
+
     /// on_block_connected callback function is invoked when a block is connected to the
     /// longest `best` chain. It will only be invoked if a preceding call to
     /// NotifyBlocks has been made to register for the notification and the
@@ -27,6 +160,11 @@ pub struct NotificationHandlers {
     /// the longest `best` chain.
     pub on_block_disconnected: Option<fn(block_header: Vec<u8>)>,
 
+    /// on_block_disconnected_header callback function is invoked with the decoded header of a
+    /// block disconnected from the longest `best` chain. It is invoked alongside
+    /// on_block_disconnected when both are set.
+    pub on_block_disconnected_header: Option<fn(block_header: GetBlockHeaderVerboseResult)>,
+
     /// on_work callback function is invoked when a new block template is generated.
     /// It will only be invoked if a preceding call to NotifyWork has
     /// been made to register for the notification and the function is non-nil.
@@ -61,6 +199,12 @@ pub struct NotificationHandlers {
     /// notification and the function is non-nil.
     pub on_new_tickets: Option<fn(hash: Hash, height: i64, stake_diff: i64, tickets: Vec<Hash>)>,
 
+    /// on_stake_difficulty callback function is invoked when a block is connected to the longest
+    /// `best` chain and a new stake difficulty is calculated. It will only be invoked if a
+    /// preceding call to NotifyStakeDifficulty has been made to register for the notification
+    /// and the function is non-nil.
+    pub on_stake_difficulty: Option<fn(hash: Hash, height: i64, stake_diff: i64)>,
+
     /// on_tx_accepted is invoked when a transaction is accepted into the
     /// memory pool.  It will only be invoked if a preceding call to
     /// NotifyNewTransactions with the verbose flag set to false has been
@@ -0,0 +1,73 @@
+//! Request Rate Limiting.
+//! Paces requests sent to the server with a token bucket, for servers limiting the rate of
+//! requests they accept.
+
+use {
+    std::{sync::Mutex, time::Duration},
+    tokio::time::{self, Instant},
+};
+
+/// Token bucket refilled at a constant rate up to its burst size. A request takes a token,
+/// waiting for one to be refilled if the bucket is empty.
+#[derive(Debug)]
+pub(super) struct RateLimiter {
+    bucket: Mutex<Bucket>,
+    requests_per_second: f64,
+    burst: f64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Tokens in the bucket. Negative while tokens are reserved by waiting requests.
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a full bucket of burst tokens refilled at requests_per_second. A burst of zero
+    /// is a burst of one.
+    pub(super) fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+
+        RateLimiter {
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                updated: Instant::now(),
+            }),
+            requests_per_second,
+            burst,
+        }
+    }
+
+    /// Takes a token, waiting until one is refilled if the bucket is empty. Tokens are reserved
+    /// in the order they are requested, so waiting requests are sent in order. Returns false
+    /// without taking a token if none is refilled before deadline.
+    pub(super) async fn acquire(&self, deadline: Option<Instant>) -> bool {
+        let ready = {
+            let mut bucket = self.bucket.lock().unwrap();
+
+            let now = Instant::now();
+            let refilled =
+                now.duration_since(bucket.updated).as_secs_f64() * self.requests_per_second;
+            bucket.tokens = (bucket.tokens + refilled).min(self.burst);
+            bucket.updated = now;
+
+            let ready = if bucket.tokens >= 1.0 {
+                now
+            } else {
+                now + Duration::from_secs_f64((1.0 - bucket.tokens) / self.requests_per_second)
+            };
+
+            if deadline.is_some_and(|deadline| ready > deadline) {
+                return false;
+            }
+
+            bucket.tokens -= 1.0;
+            ready
+        };
+
+        time::sleep_until(ready).await;
+
+        true
+    }
+}
@@ -0,0 +1,623 @@
+//! Test Utilities.
+//! Contains a websocket RPC server, reached over the loopback interface or in memory, and a
+//! connection dialing it for exercising clients without a dcrd node, and helpers to drive the
+//! message and notification handlers directly.
+//! Enabled with the `test-util` feature.
+
+use {
+    super::{
+        backoff::Backoff,
+        connection::{self, KeepAlive, RPCConn, Transport, Websocket},
+        constants,
+        error::RpcClientError,
+        infrastructure,
+    },
+    crate::dcrjson::{
+        result_types::{self, JsonResponse},
+        DeserializationPolicy,
+    },
+    async_trait::async_trait,
+    futures_util::{
+        stream::{SplitSink, SplitStream, StreamExt},
+        SinkExt,
+    },
+    log::warn,
+    std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    },
+    tokio::sync::{mpsc, watch},
+    tokio_tungstenite::tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        http::StatusCode,
+        Message,
+    },
+};
+
+/// Size in bytes of the buffer of each direction of an in-memory stream.
+const MEMORY_STREAM_CAPACITY: usize = 64 * 1024;
+
+/// JSON RPC request received by `MockServer`.
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct MockRequest {
+    #[serde(deserialize_with = "deserialize_request_id")]
+    pub id: u64,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<serde_json::Value>,
+}
+
+/// Deserializes a request ID sent either as a number or as a string.
+fn deserialize_request_id<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let id = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+
+    result_types::parse_request_id(&id)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid request ID {}", id)))
+}
+
+/// A websocket server that forwards every request it receives to the test and writes back any
+/// response or notification the test sends it. The server listens on the loopback interface, or
+/// is reached over in-memory streams when started with `start_in_memory`.
+///
+/// Connections are served one at a time, a new connection replacing the one being served, so a
+/// reconnecting client is served by the same channels. Replaced connections are left open and
+/// unserved, as a peer that went silent would leave them. Dropping the server closes the
+/// connection being served and stops listening, so reconnections are refused.
+pub struct MockServer {
+    requests: mpsc::UnboundedReceiver<MockRequest>,
+    responses: mpsc::UnboundedSender<Message>,
+    drop_connection: mpsc::UnboundedSender<()>,
+    reject_auth: Arc<AtomicBool>,
+    unresponsive: watch::Sender<bool>,
+}
+
+impl MockServer {
+    /// Starts a mock server returning it with a connection that dials it.
+    pub async fn start() -> (MockServer, MockConn) {
+        MockServer::start_at(SocketAddr::from(([127, 0, 0, 1], 0))).await
+    }
+
+    /// Starts a mock server listening on addr, such as the address of a `MockConn` created
+    /// before the server, returning it with a connection that dials it.
+    pub async fn start_at(addr: SocketAddr) -> (MockServer, MockConn) {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .expect("unable to bind mock server");
+
+        let addr = listener
+            .local_addr()
+            .expect("unable to retrieve mock server address");
+
+        let (connector, incoming) = mpsc::unbounded_channel::<Box<dyn Transport>>();
+
+        // Accepts connections until the server stops, after which they are refused.
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = connector.closed() => return,
+
+                    stream = listener.accept() => match stream {
+                        Ok((stream, _)) => {
+                            if connector.send(Box::new(stream)).is_err() {
+                                return;
+                            }
+                        }
+
+                        Err(_) => return,
+                    },
+                }
+            }
+        });
+
+        (MockServer::serve(incoming), MockConn::new(addr))
+    }
+
+    /// Starts a mock server reached over in-memory streams rather than a socket, returning it
+    /// with a connection that dials it.
+    pub fn start_in_memory() -> (MockServer, MockConn) {
+        let (connector, incoming) = mpsc::unbounded_channel();
+
+        (
+            MockServer::serve(incoming),
+            MockConn::with_endpoint(Endpoint::Memory(connector)),
+        )
+    }
+
+    /// Serves the connections received on incoming, one at a time.
+    fn serve(mut incoming: mpsc::UnboundedReceiver<Box<dyn Transport>>) -> MockServer {
+        let (request_sender, requests) = mpsc::unbounded_channel();
+        let (responses, mut response_receiver) = mpsc::unbounded_channel::<Message>();
+        let (drop_connection, mut drop_receiver) = mpsc::unbounded_channel();
+        let reject_auth = Arc::new(AtomicBool::new(false));
+        let reject = reject_auth.clone();
+        let (unresponsive, mut stalled) = watch::channel(false);
+
+        tokio::spawn(async move {
+            let mut accepted = None;
+            let mut replaced = Vec::new();
+
+            loop {
+                let stream = match accepted.take() {
+                    Some(stream) => stream,
+
+                    None => match incoming.recv().await {
+                        Some(stream) => stream,
+
+                        None => return,
+                    },
+                };
+
+                // Tungstenite's handshake callbacks return their error response by value.
+                #[allow(clippy::result_large_err)]
+                let authenticate = |_: &Request, response: Response| {
+                    if !reject.load(Ordering::SeqCst) {
+                        return Ok(response);
+                    }
+
+                    let mut rejection = ErrorResponse::new(None);
+                    *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+
+                    Err(rejection)
+                };
+
+                let websocket =
+                    match tokio_tungstenite::accept_hdr_async(stream, authenticate).await {
+                        Ok(websocket) => websocket,
+
+                        Err(e) => {
+                            warn!("mock server handshake failed, error: {}", e);
+                            continue;
+                        }
+                    };
+
+                let (mut write, mut read) = websocket.split();
+
+                loop {
+                    tokio::select! {
+                        msg = read.next(), if !*stalled.borrow() => {
+                            let msg = match msg {
+                                Some(Ok(msg)) => msg,
+
+                                _ => break,
+                            };
+
+                            if msg.is_close() {
+                                break;
+                            }
+
+                            if !msg.is_text() && !msg.is_binary() {
+                                continue;
+                            }
+
+                            match serde_json::from_slice::<MockRequest>(&msg.into_data()) {
+                                Ok(request) => {
+                                    if request_sender.send(request).is_err() {
+                                        return;
+                                    }
+                                }
+
+                                Err(e) => warn!("mock server received an invalid request, error: {}", e),
+                            }
+                        }
+
+                        response = response_receiver.recv() => {
+                            match response {
+                                Some(response) => {
+                                    if write.send(response).await.is_err() {
+                                        break;
+                                    }
+                                }
+
+                                None => return,
+                            }
+                        }
+
+                        _ = drop_receiver.recv() => break,
+
+                        // Reevaluates whether the connection is read.
+                        _ = stalled.changed() => continue,
+
+                        stream = incoming.recv() => {
+                            accepted = stream;
+                            break;
+                        }
+                    }
+                }
+
+                if accepted.is_some() {
+                    replaced.push((write, read));
+                }
+            }
+        });
+
+        MockServer {
+            requests,
+            responses,
+            drop_connection,
+            reject_auth,
+            unresponsive,
+        }
+    }
+
+    /// Waits for the next request sent by a client.
+    pub async fn next_request(&mut self) -> Option<MockRequest> {
+        self.requests.recv().await
+    }
+
+    /// Sends a crafted response or notification to the connected client.
+    pub fn send(&self, response: JsonResponse) {
+        let marshalled = serde_json::to_string(&response).expect("unable to marshal response");
+
+        self.send_message(Message::Text(marshalled));
+    }
+
+    /// Sends a raw websocket message to the connected client.
+    pub fn send_message(&self, message: Message) {
+        if self.responses.send(message).is_err() {
+            warn!("mock server closed");
+        }
+    }
+
+    /// Replies a request with a result.
+    pub fn reply(&self, id: u64, result: serde_json::Value) {
+        self.send(JsonResponse {
+            id: serde_json::json!(id),
+            result,
+            ..Default::default()
+        });
+    }
+
+    /// Replies a request with an error.
+    pub fn reply_error(&self, id: u64, code: i64, message: &str) {
+        self.send(JsonResponse {
+            id: serde_json::json!(id),
+            error: serde_json::json!({ "code": code, "message": message }),
+            ..Default::default()
+        });
+    }
+
+    /// Drops the connection to the client without a closing handshake, as a lost connection would.
+    pub fn drop_connection(&self) {
+        if self.drop_connection.send(()).is_err() {
+            warn!("mock server closed");
+        }
+    }
+
+    /// Sets whether new connections are rejected with an unauthorized status.
+    pub fn reject_auth(&self, reject: bool) {
+        self.reject_auth.store(reject, Ordering::SeqCst);
+    }
+
+    /// Sets whether messages from the client, including pings, are left unread and unanswered,
+    /// as a dead peer would.
+    pub fn unresponsive(&self, unresponsive: bool) {
+        self.unresponsive.send_replace(unresponsive);
+    }
+
+    /// Sends a notification to the connected client.
+    pub fn notify(&self, method: &str, params: Vec<serde_json::Value>) {
+        self.send(JsonResponse {
+            method: serde_json::json!(method),
+            params,
+            ..Default::default()
+        });
+    }
+}
+
+/// Where a `MockConn` reaches its server.
+#[derive(Clone, Debug)]
+enum Endpoint {
+    /// Address the server listens on.
+    Tcp(SocketAddr),
+
+    /// Channel handing the server its end of each in-memory stream.
+    Memory(mpsc::UnboundedSender<Box<dyn Transport>>),
+}
+
+/// Connection dialing a `MockServer`.
+#[derive(Clone, Debug)]
+pub struct MockConn {
+    endpoint: Endpoint,
+
+    /// Whether the initial connection is retried like a reconnection.
+    pub connect_retry_on_new: bool,
+
+    /// Number of consecutive rejected reconnections after which the client stops reconnecting.
+    /// Zero retries indefinitely.
+    pub max_auth_failures: u32,
+
+    /// Number of consecutive failed reconnection attempts after which the client stops
+    /// reconnecting. None retries indefinitely.
+    pub max_reconnect_attempts: Option<u32>,
+
+    /// Strategy deciding the delay between failed reconnection attempts.
+    pub backoff: Backoff,
+
+    /// Number of worker threads notification callbacks are dispatched to.
+    pub notification_workers: usize,
+
+    /// Time without messages after which the server is pinged.
+    pub idle_timeout: Option<std::time::Duration>,
+
+    /// How the connection is kept alive.
+    pub keep_alive: KeepAlive,
+
+    /// Whether blocks connected while reconnecting are replayed.
+    pub recover_missed_blocks: bool,
+
+    /// Size in bytes above which websocket messages are dropped.
+    pub max_response_size: Option<usize>,
+
+    /// Whether request IDs are sent as JSON strings.
+    pub string_request_ids: bool,
+
+    /// How result fields unknown to their result type are handled.
+    pub deserialization_policy: DeserializationPolicy,
+
+    /// Name prefixing the log lines emitted by the client's tasks.
+    pub connection_name: Option<String>,
+
+    /// Time after which a request not answered by the server fails.
+    pub request_timeout: Option<std::time::Duration>,
+
+    /// Timeouts of requests of the given methods, overriding `request_timeout`.
+    pub method_timeouts: std::collections::HashMap<String, std::time::Duration>,
+
+    /// Rate requests are sent at, in requests per second.
+    pub requests_per_second: Option<f64>,
+
+    /// Number of requests that can be sent at once before being paced.
+    pub burst: u32,
+
+    /// Whether requests made while disconnected are queued until the client reconnects.
+    pub queue_while_disconnected: bool,
+
+    /// Number of requests that can be queued while disconnected.
+    pub max_queued_requests: usize,
+
+    /// Time a request queued while disconnected waits for the client to reconnect.
+    pub queued_request_timeout: std::time::Duration,
+}
+
+impl MockConn {
+    /// Creates a connection dialing addr with the default options.
+    pub fn new(addr: SocketAddr) -> MockConn {
+        MockConn::with_endpoint(Endpoint::Tcp(addr))
+    }
+
+    fn with_endpoint(endpoint: Endpoint) -> MockConn {
+        MockConn {
+            endpoint,
+            connect_retry_on_new: false,
+            max_auth_failures: 0,
+            max_reconnect_attempts: None,
+            backoff: Backoff::default(),
+            notification_workers: 0,
+            idle_timeout: None,
+            keep_alive: KeepAlive::default(),
+            recover_missed_blocks: false,
+            max_response_size: None,
+            string_request_ids: false,
+            deserialization_policy: DeserializationPolicy::default(),
+            connection_name: None,
+            request_timeout: None,
+            method_timeouts: std::collections::HashMap::new(),
+            requests_per_second: None,
+            burst: 1,
+            queue_while_disconnected: false,
+            max_queued_requests: constants::DEFAULT_MAX_QUEUED_REQUESTS,
+            queued_request_timeout: constants::DEFAULT_QUEUED_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+#[async_trait]
+impl RPCConn for MockConn {
+    async fn ws_split_stream(
+        &mut self,
+    ) -> Result<(SplitStream<Websocket>, SplitSink<Websocket, Message>), RpcClientError> {
+        let (url, stream): (_, Box<dyn Transport>) = match &self.endpoint {
+            Endpoint::Tcp(addr) => {
+                let stream = tokio::net::TcpStream::connect(addr)
+                    .await
+                    .map_err(RpcClientError::TcpStream)?;
+
+                (format!("ws://{}", addr), Box::new(stream))
+            }
+
+            Endpoint::Memory(connector) => {
+                let (stream, server_stream) = tokio::io::duplex(MEMORY_STREAM_CAPACITY);
+
+                // A stopped server refuses connections as a closed listener would.
+                connector.send(Box::new(server_stream)).map_err(|_| {
+                    RpcClientError::TcpStream(std::io::ErrorKind::ConnectionRefused.into())
+                })?;
+
+                ("ws://mock".to_string(), Box::new(stream))
+            }
+        };
+
+        let (ws_stream, _) = tokio_tungstenite::client_async_with_config(
+            url,
+            stream,
+            connection::websocket_config(self.max_response_size),
+        )
+        .await
+        .map_err(connection::handshake_error)?;
+
+        let (ws_send, ws_rcv) = ws_stream.split();
+
+        Ok((ws_rcv, ws_send))
+    }
+
+    async fn handle_post_methods(
+        &self,
+        _http_user_command: mpsc::Receiver<infrastructure::Command>,
+    ) -> Result<(), RpcClientError> {
+        Ok(())
+    }
+
+    fn is_http_mode(&self) -> bool {
+        false
+    }
+
+    fn disable_connect_on_new(&self) -> bool {
+        false
+    }
+
+    fn connect_retry_on_new(&self) -> bool {
+        self.connect_retry_on_new
+    }
+
+    fn disable_auto_reconnect(&self) -> bool {
+        false
+    }
+
+    fn max_auth_failures(&self) -> u32 {
+        self.max_auth_failures
+    }
+
+    fn max_reconnect_attempts(&self) -> Option<u32> {
+        self.max_reconnect_attempts
+    }
+
+    fn backoff(&self) -> Backoff {
+        self.backoff.clone()
+    }
+
+    fn notification_workers(&self) -> usize {
+        self.notification_workers
+    }
+
+    fn idle_timeout(&self) -> Option<std::time::Duration> {
+        self.idle_timeout
+    }
+
+    fn keep_alive(&self) -> KeepAlive {
+        self.keep_alive.clone()
+    }
+
+    fn recover_missed_blocks(&self) -> bool {
+        self.recover_missed_blocks
+    }
+
+    fn max_response_size(&self) -> Option<usize> {
+        self.max_response_size
+    }
+
+    fn string_request_ids(&self) -> bool {
+        self.string_request_ids
+    }
+
+    fn deserialization_policy(&self) -> DeserializationPolicy {
+        self.deserialization_policy
+    }
+
+    fn connection_name(&self) -> Option<String> {
+        self.connection_name.clone()
+    }
+
+    fn request_timeout(&self) -> Option<std::time::Duration> {
+        self.request_timeout
+    }
+
+    fn method_timeout(&self, method: &str) -> Option<std::time::Duration> {
+        match self.method_timeouts.get(method) {
+            Some(timeout) => Some(*timeout),
+
+            None => self.request_timeout,
+        }
+    }
+
+    fn requests_per_second(&self) -> Option<f64> {
+        self.requests_per_second
+    }
+
+    fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    fn queue_while_disconnected(&self) -> bool {
+        self.queue_while_disconnected
+    }
+
+    fn max_queued_requests(&self) -> usize {
+        self.max_queued_requests
+    }
+
+    fn queued_request_timeout(&self) -> std::time::Duration {
+        self.queued_request_timeout
+    }
+}
+
+/// Passes each message through the received message handler as if read from a websocket.
+/// Responses are delivered to the channel mapped to their ID in `receivers` and notifications
+/// are returned in the order they were received.
+pub async fn dispatch_messages(
+    messages: Vec<JsonResponse>,
+    receivers: HashMap<u64, mpsc::Sender<JsonResponse>>,
+) -> Vec<JsonResponse> {
+    let (message_sender, message_receiver) = mpsc::unbounded_channel();
+    let (notification_sender, mut notification_receiver) = mpsc::channel(messages.len().max(1));
+    let (disconnect_ack, _) = mpsc::channel(1);
+
+    for message in messages {
+        let marshalled = serde_json::to_string(&message).expect("unable to marshal message");
+        message_sender
+            .send(Message::Text(marshalled))
+            .expect("message handler closed");
+    }
+    drop(message_sender);
+
+    infrastructure::handle_received_message(
+        message_receiver,
+        notification_sender,
+        disconnect_ack,
+        Arc::new(tokio::sync::RwLock::new(false)),
+        Arc::new(tokio::sync::Mutex::new(receivers)),
+        None,
+        None,
+    )
+    .await;
+
+    let mut notifications = Vec::new();
+    while let Some(notification) = notification_receiver.recv().await {
+        notifications.push(notification);
+    }
+
+    notifications
+}
+
+/// Passes each notification through the notification handler, invoking the matching
+/// callbacks in `handlers`.
+pub async fn dispatch_notifications(
+    handlers: super::notify::NotificationHandlers,
+    notifications: Vec<JsonResponse>,
+) {
+    let (sender, receiver) = mpsc::channel(notifications.len().max(1));
+
+    for notification in notifications {
+        sender
+            .send(notification)
+            .await
+            .expect("notification handler closed");
+    }
+    drop(sender);
+
+    infrastructure::handle_notification(
+        receiver,
+        Arc::new(handlers),
+        0,
+        Default::default(),
+        None,
+        Default::default(),
+    )
+    .await;
+}